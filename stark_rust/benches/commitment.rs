@@ -0,0 +1,42 @@
+// benches/commitment.rs - Proving at several security levels with a fresh
+// vs a shared trace commitment
+//
+// Run with: cargo bench --bench commitment
+use stark_prover_verifier::computation::{Computation, NthPrime};
+use stark_prover_verifier::STARKProver;
+use std::time::Instant;
+
+const SECURITY_LEVELS: [u32; 3] = [80, 128, 256];
+const ITERATIONS: usize = 20;
+
+fn main() {
+    let (result, trace) = NthPrime.run(20_000).unwrap();
+    println!("{} trace steps, {} rounds of {:?} bits", trace.steps.len(), ITERATIONS, SECURITY_LEVELS);
+
+    let start = Instant::now();
+    let mut checksum = 0usize;
+    for _ in 0..ITERATIONS {
+        for security in SECURITY_LEVELS {
+            let proof = STARKProver::new(security).prove("nth_prime", result, &trace).unwrap();
+            checksum = checksum.wrapping_add(proof.fri_layers.len());
+        }
+    }
+    report("fresh commitment", start, checksum);
+
+    let start = Instant::now();
+    let mut checksum = 0usize;
+    for _ in 0..ITERATIONS {
+        let commitment = STARKProver::new(SECURITY_LEVELS[0]).commit_to_trace(&trace);
+        for security in SECURITY_LEVELS {
+            let proof = STARKProver::new(security)
+                .prove_with_commitment("nth_prime", result, &trace, commitment.clone())
+                .unwrap();
+            checksum = checksum.wrapping_add(proof.fri_layers.len());
+        }
+    }
+    report("shared commitment", start, checksum);
+}
+
+fn report(name: &str, start: Instant, checksum: usize) {
+    println!("{:<18} {:>10.2?}  (checksum {})", name, start.elapsed(), checksum);
+}
@@ -0,0 +1,66 @@
+// benches/constraints.rs - Allocating vs buffered constraint evaluation
+//
+// Run with: cargo bench --bench constraints
+use stark_prover_verifier::computation::fibonacci_with_trace;
+use stark_prover_verifier::STARKProver;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+/// Global allocator that counts allocations
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const ITERATIONS: usize = 100_000;
+
+fn main() {
+    let prover = STARKProver::new(128);
+    let (_, trace) = fibonacci_with_trace(90).unwrap();
+
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    let mut checksum = 0u64;
+    for _ in 0..ITERATIONS {
+        let evaluations = prover.evaluate_constraints(&trace);
+        checksum = checksum.wrapping_add(evaluations[0]);
+    }
+    report("evaluate_constraints", start, before, checksum);
+
+    let mut buffer = Vec::new();
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    let mut checksum = 0u64;
+    for _ in 0..ITERATIONS {
+        prover.evaluate_constraints_into(&trace, &mut buffer);
+        checksum = checksum.wrapping_add(buffer[0]);
+    }
+    report("evaluate_constraints_into", start, before, checksum);
+}
+
+fn report(name: &str, start: Instant, allocations_before: usize, checksum: u64) {
+    let elapsed = start.elapsed();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations_before;
+    println!(
+        "{:<28} {:>10.2?} total  {:>8.1} ns/iter  {:>7} allocations  (checksum {})",
+        name,
+        elapsed,
+        elapsed.as_nanos() as f64 / ITERATIONS as f64,
+        allocations,
+        checksum
+    );
+}
@@ -0,0 +1,69 @@
+// benches/fri.rs - Allocating vs in-place FRI folding on a large vector
+//
+// Run with: cargo bench --bench fri
+use stark_prover_verifier::crypto::fold_in_place;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+/// Global allocator that tracks live and peak heap bytes
+struct PeakAllocator;
+
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for PeakAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let live = LIVE.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+        PEAK.fetch_max(live, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: PeakAllocator = PeakAllocator;
+
+const LEN: u64 = 1 << 22;
+const FOLD_FACTOR: usize = 2;
+
+fn main() {
+    let evaluations: Vec<u64> = (0..LEN).collect();
+
+    let mut current = evaluations.clone();
+    let (start, baseline) = reset_peak();
+    while current.len() > 1 {
+        current = current.iter().step_by(FOLD_FACTOR).copied().collect();
+    }
+    report("step_by + collect", start, baseline, current[0]);
+
+    let mut current = evaluations.clone();
+    let (start, baseline) = reset_peak();
+    while current.len() > 1 {
+        fold_in_place(&mut current, FOLD_FACTOR);
+    }
+    report("fold_in_place", start, baseline, current[0]);
+}
+
+/// Start timing and make the current live size the new peak baseline
+fn reset_peak() -> (Instant, usize) {
+    let live = LIVE.load(Ordering::Relaxed);
+    PEAK.store(live, Ordering::Relaxed);
+    (Instant::now(), live)
+}
+
+fn report(name: &str, start: Instant, baseline: usize, checksum: u64) {
+    let elapsed = start.elapsed();
+    let extra = PEAK.load(Ordering::Relaxed) - baseline;
+    println!(
+        "{:<20} {:>10.2?}  {:>8} KiB extra peak memory  (checksum {})",
+        name,
+        elapsed,
+        extra / 1024,
+        checksum
+    );
+}
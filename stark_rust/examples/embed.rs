@@ -0,0 +1,33 @@
+// examples/embed.rs - Using the library directly, without the CLI
+//
+// Run with: cargo run --example embed
+use stark_prover_verifier::computation::fibonacci_with_trace;
+use stark_prover_verifier::{Error, Format, Proof, STARKProver, STARKVerifier};
+
+fn main() -> Result<(), Error> {
+    let prover = STARKProver::new(128);
+    let verifier = STARKVerifier::new(128);
+
+    // Run the computation, recording its trace
+    let (result, trace) = fibonacci_with_trace(20)?;
+
+    // Prove it, refusing to hand back a proof that doesn't verify
+    let proof = prover.prove_verified("fibonacci", result, &trace, &verifier)?;
+    println!("fibonacci(20) = {} ({} FRI layers)", proof.result, proof.fri_layers.len());
+
+    // Ship it anywhere that implements `Write`...
+    let mut buffer = Vec::new();
+    prover
+        .write_proof(&proof, &mut buffer, Format::Json)
+        .expect("writing to a Vec cannot fail");
+
+    // ...and verify it on the other side
+    let received = Proof::from_json(std::str::from_utf8(&buffer).expect("proofs are UTF-8"))?;
+    let verification = verifier.verify(&received);
+    if !verification.valid {
+        return Err(verification.into());
+    }
+    println!("Received proof is valid ({} checks passed)", verification.checks_passed.len());
+
+    Ok(())
+}
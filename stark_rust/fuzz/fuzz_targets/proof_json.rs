@@ -0,0 +1,31 @@
+// fuzz/fuzz_targets/proof_json.rs - Feed arbitrary bytes through proof
+// parsing and verification
+//
+// Run with `cargo fuzz run proof_json -- -rss_limit_mb=512` from
+// stark_rust/. Any panic, any disagreement between `verify` and
+// `is_valid`, or memory growth past the limit is a bug.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use stark_prover_verifier::crypto::{Sha256Oracle, Transcript};
+use stark_prover_verifier::{Proof, STARKVerifier};
+
+/// Inputs are tiny next to `MAX_PROOF_BYTES`; a tight bound keeps each
+/// run fast while still exercising the size check
+const MAX_INPUT_BYTES: usize = 1 << 20;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(proof) = Proof::from_json_limited(data, MAX_INPUT_BYTES) else {
+        return;
+    };
+
+    let verifier = STARKVerifier::new(128);
+    let report = verifier.verify(&proof);
+    assert_eq!(report.valid, verifier.is_valid(&proof), "verify and is_valid disagree");
+
+    // Everything else that reads untrusted proof fields
+    let _ = proof.lint();
+    let _ = proof.fingerprint();
+    let _ = verifier.recompute_result(&proof);
+    let _ = Transcript::for_proof(&proof, &Sha256Oracle).export();
+});
@@ -0,0 +1,678 @@
+// src/checks.rs - Individual verification checks
+use crate::crypto::{
+    challenge_seed, fri_layers_for_degree, interactive_challenge, query_indices, verify_challenge_with,
+    RandomOracle,
+};
+use crate::types::{Proof, ResultValue};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+/// What a single check concluded about a proof
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckOutcome {
+    /// The check passed; the message is reported under checks passed
+    Passed(String),
+    /// The check failed, invalidating the proof
+    Failed(String),
+    /// Nothing could be checked, but the proof stays valid
+    Warning(String),
+    /// The check does not apply to this proof
+    Skipped,
+}
+
+/// One verification step run by `STARKVerifier::verify`.
+///
+/// Built-in checks cover the proof's own consistency; callers can add
+/// policy checks of their own with `STARKVerifier::add_check`.
+pub trait VerificationCheck: Send + Sync {
+    /// Short identifier, used as the key in `VerificationResult::timings`
+    fn name(&self) -> &str;
+
+    /// Check `proof`, describing the outcome for the report
+    fn run(&self, proof: &Proof) -> CheckOutcome;
+
+    /// Whether `proof` passes, for `STARKVerifier::is_valid`. Override when
+    /// the answer is cheaper to get than a full report message.
+    fn passes(&self, proof: &Proof) -> bool {
+        !matches!(self.run(proof), CheckOutcome::Failed(_))
+    }
+}
+
+/// Recomputes a computation's result from its public inputs.
+///
+/// Returns `None` if the inputs are missing or out of range.
+pub type RecomputeFn = fn(&BTreeMap<String, u64>) -> Option<ResultValue>;
+
+/// The trace commitment must be a SHA-256 hex digest
+pub struct TraceCommitmentCheck;
+
+impl VerificationCheck for TraceCommitmentCheck {
+    fn name(&self) -> &str {
+        "trace_commitment"
+    }
+
+    fn run(&self, proof: &Proof) -> CheckOutcome {
+        match hex_digest_error("trace_commitment", &proof.trace_commitment) {
+            None => CheckOutcome::Passed("Trace commitment verified".to_string()),
+            Some(error) => CheckOutcome::Failed(format!("Trace commitment verification failed ({})", error)),
+        }
+    }
+
+    fn passes(&self, proof: &Proof) -> bool {
+        hex_digest_error("trace_commitment", &proof.trace_commitment).is_none()
+    }
+}
+
+/// Every constraint evaluation must lie below the field modulus, and equal
+/// `target` when one is set
+pub struct ConstraintCheck {
+    pub security_level: u32,
+    pub target: Option<u64>,
+}
+
+impl VerificationCheck for ConstraintCheck {
+    fn name(&self) -> &str {
+        "constraints"
+    }
+
+    fn run(&self, proof: &Proof) -> CheckOutcome {
+        let (satisfied, total) = count_satisfied_constraints(proof, self.security_level, self.target);
+        if total > 0 && satisfied == total {
+            CheckOutcome::Passed("Constraint evaluations verified".to_string())
+        } else if let Some(target) = self.target {
+            CheckOutcome::Failed(format!(
+                "Constraint evaluation verification failed ({} of {} evaluations differ from {})",
+                total - satisfied,
+                total,
+                target
+            ))
+        } else {
+            CheckOutcome::Failed("Constraint evaluation verification failed".to_string())
+        }
+    }
+
+    fn passes(&self, proof: &Proof) -> bool {
+        let (satisfied, total) = count_satisfied_constraints(proof, self.security_level, self.target);
+        total > 0 && satisfied == total
+    }
+}
+
+/// FRI must carry exactly the layers the domain needs, each a hex digest
+pub struct FriLayersCheck;
+
+impl VerificationCheck for FriLayersCheck {
+    fn name(&self) -> &str {
+        "fri_layers"
+    }
+
+    fn run(&self, proof: &Proof) -> CheckOutcome {
+        if proof.is_commitment_only() {
+            return CheckOutcome::Skipped;
+        }
+        let required_layers = required_fri_layers(proof);
+        if self.passes(proof) {
+            CheckOutcome::Passed("FRI proof layers verified".to_string())
+        } else if proof.fri_layers.len() < required_layers {
+            CheckOutcome::Failed(format!(
+                "Too few FRI layers for constraint degree {} ({} < {})",
+                proof.constraint_degree,
+                proof.fri_layers.len(),
+                required_layers
+            ))
+        } else if let Some(error) = malformed_fri_layer(proof) {
+            CheckOutcome::Failed(format!("FRI proof layer verification failed ({})", error))
+        } else {
+            CheckOutcome::Failed("FRI proof layer verification failed".to_string())
+        }
+    }
+
+    fn passes(&self, proof: &Proof) -> bool {
+        if proof.is_commitment_only() {
+            return true;
+        }
+        // The layer count is fixed by the evaluation count, constraint
+        // degree and fold factor
+        if proof.fri_layers.is_empty() || proof.fri_layers.len() != required_fri_layers(proof) {
+            return false;
+        }
+
+        // Check that each layer is a valid hash
+        malformed_fri_layer(proof).is_none()
+    }
+}
+
+/// Each FRI layer must be the evaluations folded along the schedule implied
+/// by the evaluation count, constraint degree and fold factor. Only layer
+/// contents are compared; `FriLayersCheck` owns the layer count.
+///
+/// Layers are recomputed with `HashAlgorithm::fold_layer`, the same round
+/// the prover runs. Domains above `MAX_FOLDED_DOMAIN` points are rejected
+/// before any are built, so an absurd `constraint_degree` costs nothing.
+pub struct FriFoldingCheck;
+
+/// Largest FRI domain (evaluations times constraint degree) the verifier
+/// will rebuild to check the folding
+pub const MAX_FOLDED_DOMAIN: usize = 1 << 24;
+
+impl FriFoldingCheck {
+    /// The first layer (by index) that does not match the recomputed fold.
+    /// Layers past the end of the schedule are left to `FriLayersCheck`.
+    fn first_mismatch(proof: &Proof) -> Option<usize> {
+        let Some(algorithm) = proof.parsed_hash_algorithm() else {
+            return Some(0);
+        };
+        let evaluations = &proof.constraint_evaluations;
+        let domain_size = evaluations.len().saturating_mul(proof.constraint_degree.max(1));
+        if domain_size > MAX_FOLDED_DOMAIN {
+            return Some(0);
+        }
+        let mut domain: Vec<u64> = evaluations.iter().copied().cycle().take(domain_size).collect();
+        for (layer_idx, layer) in proof.fri_layers.iter().enumerate() {
+            if domain.is_empty() {
+                break;
+            }
+            let (hash, next) = algorithm.fold_layer(&domain, &proof.challenge, layer_idx, proof.fri_fold_factor);
+            if hash != *layer {
+                return Some(layer_idx);
+            }
+            domain = next;
+        }
+        None
+    }
+}
+
+impl VerificationCheck for FriFoldingCheck {
+    fn name(&self) -> &str {
+        "fri_folding"
+    }
+
+    fn run(&self, proof: &Proof) -> CheckOutcome {
+        if proof.is_commitment_only() {
+            return CheckOutcome::Skipped;
+        }
+        match Self::first_mismatch(proof) {
+            None => CheckOutcome::Passed("FRI layers match the folded evaluations".to_string()),
+            Some(layer_idx) => {
+                CheckOutcome::Failed(format!("FRI layer {} does not match the folded evaluations", layer_idx))
+            }
+        }
+    }
+
+    fn passes(&self, proof: &Proof) -> bool {
+        if proof.is_commitment_only() {
+            return true;
+        }
+        Self::first_mismatch(proof).is_none()
+    }
+}
+
+/// Commitment-only proofs skip FRI, so they are accepted only when the
+/// verifier opts in, and then with a warning
+pub struct CommitmentOnlyCheck {
+    pub allowed: bool,
+}
+
+impl VerificationCheck for CommitmentOnlyCheck {
+    fn name(&self) -> &str {
+        "commitment_only"
+    }
+
+    fn run(&self, proof: &Proof) -> CheckOutcome {
+        if !proof.is_commitment_only() {
+            CheckOutcome::Skipped
+        } else if self.allowed {
+            CheckOutcome::Warning(
+                "Commitment-only proof: FRI was skipped, so the evaluations are not low-degree tested".to_string(),
+            )
+        } else {
+            CheckOutcome::Failed("Commitment-only proof has no FRI layers".to_string())
+        }
+    }
+
+    fn passes(&self, proof: &Proof) -> bool {
+        self.allowed || !proof.is_commitment_only()
+    }
+}
+
+/// The challenge must be re-derivable from the commitment (and step root).
+/// Interactive challenges drawn from a recorded seed fail unless `allow_interactive`.
+pub struct ChallengeCheck {
+    pub oracle: Arc<dyn RandomOracle>,
+    pub allow_interactive: bool,
+}
+
+impl VerificationCheck for ChallengeCheck {
+    fn name(&self) -> &str {
+        "challenge"
+    }
+
+    fn run(&self, proof: &Proof) -> CheckOutcome {
+        if proof.interactive_seed.is_some() && !self.allow_interactive {
+            return CheckOutcome::Failed(
+                "Challenge comes from a prover-chosen seed, not the commitment (interactive challenges not allowed)"
+                    .to_string(),
+            );
+        }
+        match (self.passes(proof), proof.interactive_seed) {
+            (true, None) => CheckOutcome::Passed("Challenge generation verified".to_string()),
+            (true, Some(seed)) => CheckOutcome::Passed(format!(
+                "Challenge matches the interactive verifier seeded with {} (not Fiat-Shamir)",
+                seed
+            )),
+            (false, _) => CheckOutcome::Failed("Challenge verification failed".to_string()),
+        }
+    }
+
+    /// An interactive challenge must be allowed and come from the recorded
+    /// seed; any other must be the Fiat-Shamir challenge of the commitment
+    fn passes(&self, proof: &Proof) -> bool {
+        if let Some(seed) = proof.interactive_seed {
+            return self.allow_interactive && interactive_challenge(seed, proof.security_bits) == proof.challenge;
+        }
+        verify_challenge_with(
+            self.oracle.as_ref(),
+            &challenge_seed(
+                &proof.trace_commitment,
+                proof.step_root.as_deref(),
+                proof.inputs_bound.then_some(&proof.public_inputs),
+            ),
+            proof.security_bits,
+            &proof.challenge,
+        )
+    }
+}
+
+/// The proof must meet the verifier's minimum security level
+pub struct SecurityLevelCheck {
+    pub min_bits: u32,
+}
+
+impl VerificationCheck for SecurityLevelCheck {
+    fn name(&self) -> &str {
+        "security_level"
+    }
+
+    fn run(&self, proof: &Proof) -> CheckOutcome {
+        if self.passes(proof) {
+            CheckOutcome::Passed(format!("Security level adequate ({} bits)", proof.security_bits))
+        } else {
+            CheckOutcome::Failed(format!("Insufficient security level ({} bits)", proof.security_bits))
+        }
+    }
+
+    fn passes(&self, proof: &Proof) -> bool {
+        proof.security_bits >= self.min_bits
+    }
+}
+
+/// The claimed result must match a recomputation from the public inputs,
+/// for computations with a registered recompute function.
+///
+/// Proofs from before public inputs were recorded (none recorded and not
+/// bound into the challenge) only get a warning that the result was not
+/// recomputed.
+#[derive(Clone)]
+pub struct RecomputeCheck {
+    pub recompute: HashMap<String, RecomputeFn>,
+}
+
+impl RecomputeCheck {
+    /// Whether `proof` predates public inputs, so there is nothing to
+    /// recompute from
+    fn is_legacy(proof: &Proof) -> bool {
+        proof.public_inputs.is_empty() && !proof.inputs_bound
+    }
+
+    /// The result recomputed from the proof's public inputs, if possible
+    pub fn recompute_result(&self, proof: &Proof) -> Option<ResultValue> {
+        let recompute = self.recompute.get(&proof.computation)?;
+        recompute(&proof.public_inputs)
+    }
+}
+
+impl VerificationCheck for RecomputeCheck {
+    fn name(&self) -> &str {
+        "recompute"
+    }
+
+    fn run(&self, proof: &Proof) -> CheckOutcome {
+        if !self.recompute.contains_key(&proof.computation) {
+            return CheckOutcome::Passed("Result not independently checked (no recompute available)".to_string());
+        }
+        if Self::is_legacy(proof) {
+            return CheckOutcome::Warning(
+                "Result not independently checked (legacy proof without public inputs)".to_string(),
+            );
+        }
+        match self.recompute_result(proof) {
+            Some(expected) if expected == proof.result => {
+                CheckOutcome::Passed(format!("Result verified by recomputation ({})", expected))
+            }
+            Some(expected) => CheckOutcome::Failed(format!(
+                "Result mismatch (claimed {}, recomputed {})",
+                proof.result, expected
+            )),
+            None => CheckOutcome::Failed("Result could not be recomputed from public inputs".to_string()),
+        }
+    }
+
+    fn passes(&self, proof: &Proof) -> bool {
+        !self.recompute.contains_key(&proof.computation)
+            || Self::is_legacy(proof)
+            || self.recompute_result(proof).as_ref() == Some(&proof.result)
+    }
+}
+
+/// The claimed result must be plausible for its computation (see
+/// `ComputationInfo::validate_result`), even where it cannot be recomputed.
+/// Computations without a bound are skipped rather than reported as passed.
+pub struct ResultBoundsCheck;
+
+impl VerificationCheck for ResultBoundsCheck {
+    fn name(&self) -> &str {
+        "result_bounds"
+    }
+
+    fn run(&self, proof: &Proof) -> CheckOutcome {
+        let Some(info) = crate::computation::info(&proof.computation) else {
+            return CheckOutcome::Skipped;
+        };
+        match info.validate_result(&proof.result) {
+            Some(true) => CheckOutcome::Passed(format!("Result is a plausible {} result", info.name)),
+            Some(false) => CheckOutcome::Failed(format!("Result {} is not a possible {} result", proof.result, info.name)),
+            None => CheckOutcome::Skipped,
+        }
+    }
+}
+
+/// Warn that a sampled proof leaves its unsampled steps unchecked
+pub struct SamplingCheck;
+
+impl VerificationCheck for SamplingCheck {
+    fn name(&self) -> &str {
+        "sampling"
+    }
+
+    fn run(&self, proof: &Proof) -> CheckOutcome {
+        if proof.num_steps == 0 || proof.sampled_steps.is_empty() {
+            return CheckOutcome::Skipped;
+        }
+        CheckOutcome::Warning(format!(
+            "Only {} of {} steps were sampled; unsampled steps are unchecked",
+            proof.sampled_steps.len(),
+            proof.num_steps
+        ))
+    }
+
+    fn passes(&self, _proof: &Proof) -> bool {
+        true
+    }
+}
+
+/// Detect truncated evaluations via the committed step count
+pub struct StepCountCheck;
+
+impl VerificationCheck for StepCountCheck {
+    fn name(&self) -> &str {
+        "step_count"
+    }
+
+    fn run(&self, proof: &Proof) -> CheckOutcome {
+        if proof.num_steps == 0 {
+            CheckOutcome::Warning("Proof does not record num_steps; truncation check skipped".to_string())
+        } else if !proof.sampled_steps.is_empty() {
+            if self.passes(proof) {
+                CheckOutcome::Passed("Sampled steps match the challenge".to_string())
+            } else {
+                CheckOutcome::Failed("Sampled steps do not match the challenge".to_string())
+            }
+        } else if self.passes(proof) {
+            CheckOutcome::Passed(format!("Evaluation count matches step count ({})", describe_step_count(proof)))
+        } else {
+            CheckOutcome::Failed(format!(
+                "Evaluation count ({}) does not match step count ({})",
+                proof.unpadded_evaluations().len(),
+                describe_step_count(proof)
+            ))
+        }
+    }
+
+    /// True if the unpadded evaluation count is the recorded step count
+    /// times `constraints_per_step` (or the proof predates `num_steps`). For
+    /// sampled proofs, the sample must instead be exactly the
+    /// challenge-derived indices.
+    fn passes(&self, proof: &Proof) -> bool {
+        if proof.num_steps == 0 {
+            return true;
+        }
+        step_count_matches_evaluations(proof)
+            && (proof.sampled_steps.is_empty()
+                || proof.sampled_steps
+                    == query_indices(&proof.challenge, proof.sampled_steps.len(), proof.num_steps))
+    }
+}
+
+/// Padding must extend the evaluations to a power-of-two domain
+pub struct PaddingCheck;
+
+impl VerificationCheck for PaddingCheck {
+    fn name(&self) -> &str {
+        "padding"
+    }
+
+    fn run(&self, proof: &Proof) -> CheckOutcome {
+        match &proof.padding {
+            None => CheckOutcome::Skipped,
+            Some(padding) if padding.matches(&proof.constraint_evaluations) => CheckOutcome::Passed(format!(
+                "Evaluation padding verified ({} -> {})",
+                padding.original_len, padding.padded_len
+            )),
+            Some(_) => CheckOutcome::Failed("Evaluation padding is inconsistent".to_string()),
+        }
+    }
+
+    fn passes(&self, proof: &Proof) -> bool {
+        proof.padding.is_none_or(|padding| padding.matches(&proof.constraint_evaluations))
+    }
+}
+
+/// Opened trace steps must authenticate against the step root
+pub struct QueryOpeningsCheck;
+
+impl VerificationCheck for QueryOpeningsCheck {
+    fn name(&self) -> &str {
+        "query_openings"
+    }
+
+    fn run(&self, proof: &Proof) -> CheckOutcome {
+        if proof.step_root.is_none() && proof.fri_queries.is_empty() {
+            CheckOutcome::Skipped
+        } else if query_openings_are_valid(proof) {
+            CheckOutcome::Passed(format!("Query openings verified ({} steps)", proof.fri_queries.len()))
+        } else {
+            CheckOutcome::Failed("Query opening verification failed".to_string())
+        }
+    }
+
+    fn passes(&self, proof: &Proof) -> bool {
+        proof.step_root.is_none() && proof.fri_queries.is_empty() || query_openings_are_valid(proof)
+    }
+}
+
+/// Opened constraint evaluations must sit at the derived positions, match
+/// the evaluations, and authenticate against the evaluation root
+pub struct EvaluationOpeningsCheck;
+
+impl VerificationCheck for EvaluationOpeningsCheck {
+    fn name(&self) -> &str {
+        "evaluation_openings"
+    }
+
+    fn run(&self, proof: &Proof) -> CheckOutcome {
+        if proof.evaluation_root.is_none() && proof.evaluation_queries.is_empty() {
+            CheckOutcome::Skipped
+        } else if evaluation_openings_are_valid(proof) {
+            CheckOutcome::Passed(format!("Evaluation openings verified ({} queries)", proof.evaluation_queries.len()))
+        } else {
+            CheckOutcome::Failed("Evaluation opening verification failed".to_string())
+        }
+    }
+
+    fn passes(&self, proof: &Proof) -> bool {
+        proof.evaluation_root.is_none() && proof.evaluation_queries.is_empty() || evaluation_openings_are_valid(proof)
+    }
+}
+
+/// Every evaluation must be bound by the constraint commitment
+pub struct ConstraintCommitmentCheck;
+
+impl VerificationCheck for ConstraintCommitmentCheck {
+    fn name(&self) -> &str {
+        "constraint_commitment"
+    }
+
+    fn run(&self, proof: &Proof) -> CheckOutcome {
+        if proof.constraint_commitment.is_empty() {
+            CheckOutcome::Warning("Proof has no constraint commitment; evaluation binding check skipped".to_string())
+        } else if self.passes(proof) {
+            CheckOutcome::Passed("Constraint commitment verified".to_string())
+        } else {
+            CheckOutcome::Failed("Constraint commitment does not match evaluations".to_string())
+        }
+    }
+
+    /// True if the constraint commitment matches the evaluations (or the
+    /// proof predates `constraint_commitment`)
+    fn passes(&self, proof: &Proof) -> bool {
+        proof.constraint_commitment.is_empty()
+            || proof.compute_constraint_commitment().as_ref() == Some(&proof.constraint_commitment)
+    }
+}
+
+/// Whether one constraint evaluation satisfies its relation: it lies below
+/// `modulus` and equals the target when one is configured
+pub(crate) fn constraint_satisfied(value: u64, modulus: u64, target: Option<u64>) -> bool {
+    value < modulus && target.is_none_or(|target| value == target)
+}
+
+/// Count how many constraint evaluations satisfy their relation,
+/// returned as (satisfied, total)
+pub(crate) fn count_satisfied_constraints(proof: &Proof, security_level: u32, target: Option<u64>) -> (usize, usize) {
+    let max_val = 1u64 << security_level.min(32);
+    let evaluations = proof.unpadded_evaluations();
+    let satisfied = evaluations.iter().filter(|&&val| constraint_satisfied(val, max_val, target)).count();
+    (satisfied, evaluations.len())
+}
+
+/// True if the unpadded evaluation count is the number of evaluated steps
+/// (all `num_steps`, or the sample) times `constraints_per_step`
+fn step_count_matches_evaluations(proof: &Proof) -> bool {
+    let evaluated_steps = if proof.sampled_steps.is_empty() {
+        proof.num_steps
+    } else {
+        proof.sampled_steps.len()
+    };
+    evaluated_steps.checked_mul(proof.constraints_per_step) == Some(proof.unpadded_evaluations().len())
+}
+
+/// Check every opened step: it must sit at a challenge-derived position,
+/// authenticate against `step_root`, and agree with the constraint
+/// evaluation committed at that position.
+///
+/// `num_queries` and `num_steps` come from the proof, so both are checked
+/// against data the proof actually carries before deriving any positions.
+fn query_openings_are_valid(proof: &Proof) -> bool {
+    let (Some(root), Some(algorithm)) = (&proof.step_root, proof.parsed_hash_algorithm()) else {
+        return false;
+    };
+    if proof.fri_queries.is_empty() {
+        return false;
+    }
+    // One opening per query, or per step when there are fewer steps;
+    // without a recorded count, accept however many openings there are
+    if proof.num_queries > 0 && proof.fri_queries.len() != proof.num_queries.min(proof.num_steps) {
+        return false;
+    }
+    if !step_count_matches_evaluations(proof) {
+        return false;
+    }
+    let expected = query_indices(&proof.challenge, proof.fri_queries.len(), proof.num_steps);
+    if proof.fri_queries.iter().map(|o| o.index).ne(expected) {
+        return false;
+    }
+    let modulus = 1u64 << proof.security_bits.min(32);
+    proof.fri_queries.iter().all(|opening| {
+        algorithm.verify_merkle_path(&algorithm.merkle_leaf(&opening.step), opening.index, &opening.path, root)
+            && (0..proof.constraints_per_step).all(|constraint| {
+                match proof.constraint_evaluation_for_step(opening.index, constraint) {
+                    Some(evaluation) => evaluation == opening.step.constraint_value(constraint, modulus),
+                    None => !proof.sampled_steps.is_empty(),
+                }
+            })
+    })
+}
+
+/// The step count for reports, with the constraints per step when there
+/// is more than one
+fn describe_step_count(proof: &Proof) -> String {
+    if proof.constraints_per_step == 1 {
+        proof.num_steps.to_string()
+    } else {
+        format!("{} x {} constraints", proof.num_steps, proof.constraints_per_step)
+    }
+}
+
+/// Check every opened evaluation: the root must commit to exactly the
+/// proof's evaluations, the positions must be the ones derived from the
+/// challenge and root, and each value must match and authenticate
+fn evaluation_openings_are_valid(proof: &Proof) -> bool {
+    let (Some(root), Some(algorithm)) = (&proof.evaluation_root, proof.parsed_hash_algorithm()) else {
+        return false;
+    };
+    if *root != algorithm.evaluation_root(&proof.constraint_evaluations) {
+        return false;
+    }
+    let expected = query_indices(
+        &challenge_seed(&proof.challenge, Some(root), None),
+        proof.evaluation_queries.len(),
+        proof.constraint_evaluations.len(),
+    );
+    if proof.evaluation_queries.is_empty() || proof.evaluation_queries.iter().map(|o| o.index).ne(expected) {
+        return false;
+    }
+    proof.evaluation_queries.iter().all(|opening| {
+        proof.constraint_evaluations.get(opening.index) == Some(&opening.value)
+            && algorithm.verify_merkle_path(&algorithm.evaluation_leaf(opening.index, opening.value), opening.index, &opening.path, root)
+    })
+}
+
+/// Describe why `value` is not a SHA-256 hex digest, naming the field and
+/// the first offending character, or `None` if it is well formed
+pub(crate) fn hex_digest_error(field: &str, value: &str) -> Option<String> {
+    if let Some((position, c)) = value.chars().enumerate().find(|(_, c)| !c.is_ascii_hexdigit()) {
+        return Some(format!("{}: non-hex char {:?} at position {}", field, c, position));
+    }
+    // A valid SHA-256 hash should be 64 hex characters
+    if value.len() != 64 {
+        return Some(format!("{}: expected 64 hex characters, found {}", field, value.len()));
+    }
+    None
+}
+
+/// The first FRI layer that is not a hex digest, described precisely
+fn malformed_fri_layer(proof: &Proof) -> Option<String> {
+    proof
+        .fri_layers
+        .iter()
+        .enumerate()
+        .find_map(|(i, layer)| hex_digest_error(&format!("fri_layers[{}]", i), layer))
+}
+
+/// FRI layers a proof must carry to fold its constraint polynomial away
+fn required_fri_layers(proof: &Proof) -> usize {
+    fri_layers_for_degree(
+        proof.constraint_evaluations.len(),
+        proof.constraint_degree,
+        proof.fri_fold_factor,
+    )
+}
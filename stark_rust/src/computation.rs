@@ -1,18 +1,450 @@
 // src/computation.rs - Computation implementations
-use crate::types::{ProofTrace, TraceStep};
+use crate::error::ComputationError;
+use crate::prover::CancellationToken;
+use crate::types::{ProofTrace, ResultValue, TraceStep};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Static description of a provable computation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComputationInfo {
+    /// Name used in `Proof::computation`
+    pub name: &'static str,
+    /// One-line description for help text
+    pub description: &'static str,
+    /// Number of arguments the computation takes
+    pub arity: usize,
+    /// Name of the input checked against `max_input`
+    pub input: &'static str,
+    /// Largest input accepted for performance reasons
+    pub max_input: u64,
+    /// Whether every result up to `max_input` fits in a u64
+    pub fits_u64: bool,
+    /// Shape every result of the computation has, if any (see
+    /// `validate_result`)
+    pub result_bound: Option<ResultBound>,
+}
+
+/// A property every result of a computation has, cheap to test without
+/// recomputing the result
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResultBound {
+    /// A Fibonacci number
+    Fibonacci,
+    /// n! for some n up to `FACTORIAL.max_input`
+    Factorial,
+    /// A prime that fits in a u64
+    Prime,
+    /// A full SHA-256 digest
+    Digest,
+}
+
+impl ResultBound {
+    /// Whether `result` has this property
+    pub fn accepts(&self, result: &ResultValue) -> bool {
+        match self {
+            ResultBound::Fibonacci => result.as_u128().is_some_and(is_fibonacci_number),
+            ResultBound::Factorial => result.as_u64().is_some_and(is_factorial),
+            ResultBound::Prime => result.as_u64().is_some_and(is_prime),
+            ResultBound::Digest => result.as_digest().is_some_and(|hex| hex.len() == 64),
+        }
+    }
+}
+
+/// Fibonacci: results overflow u64 from fibonacci(94) onwards
+pub const FIBONACCI: ComputationInfo = ComputationInfo {
+    name: "fibonacci",
+    description: "n-th Fibonacci number (memoized recursion)",
+    arity: 1,
+    input: "n",
+    max_input: 100,
+    fits_u64: false,
+    result_bound: Some(ResultBound::Fibonacci),
+};
+
+/// Modular multiplication chain: bounded by the number of factors
+pub const MODMUL_CHAIN: ComputationInfo = ComputationInfo {
+    name: "modmul_chain",
+    description: "Product of a list of values modulo a modulus",
+    arity: 2,
+    input: "count",
+    max_input: 1000,
+    fits_u64: true,
+    result_bound: None,
+};
+
+/// Factorial: 20! is the largest factorial that fits in a u64
+pub const FACTORIAL: ComputationInfo = ComputationInfo {
+    name: "factorial",
+    description: "n! by repeated multiplication",
+    arity: 1,
+    input: "n",
+    max_input: 20,
+    fits_u64: true,
+    result_bound: Some(ResultBound::Factorial),
+};
+
+/// Sum of the u64 values in a file: bounded by the number of values
+pub const SUM_FILE: ComputationInfo = ComputationInfo {
+    name: "sum_file",
+    description: "Sum of newline-separated u64 values read from a file",
+    arity: 1,
+    input: "count",
+    max_input: 100_000,
+    fits_u64: false,
+    result_bound: None,
+};
+
+/// n-th prime by trial division: bounded to keep the search short
+pub const NTH_PRIME: ComputationInfo = ComputationInfo {
+    name: "nth_prime",
+    description: "n-th prime number (trial division)",
+    arity: 1,
+    input: "n",
+    max_input: 100_000,
+    fits_u64: true,
+    result_bound: Some(ResultBound::Prime),
+};
+
+/// Order-k linear recurrence: bounded by the index computed
+pub const LINEAR_RECURRENCE: ComputationInfo = ComputationInfo {
+    name: "linear_recurrence",
+    description: "n-th term of a linear recurrence a_i = sum(c_j * a_(i-1-j))",
+    arity: 3,
+    input: "n",
+    max_input: 1000,
+    fits_u64: false,
+    result_bound: None,
+};
+
+/// Signed linear recurrence: terms and coefficients may be negative
+pub const SIGNED_LINEAR_RECURRENCE: ComputationInfo = ComputationInfo {
+    name: "signed_linear_recurrence",
+    description: "n-th term of a linear recurrence over signed integers",
+    arity: 3,
+    input: "n",
+    max_input: 1000,
+    fits_u64: false,
+    result_bound: None,
+};
+
+/// Rounds of SHA-256 over the hex input: the result is a digest, not a number
+pub const HASH_COMPUTATION: ComputationInfo = ComputationInfo {
+    name: "hash_computation",
+    description: "SHA-256 applied five times to the hex input",
+    arity: 1,
+    input: "input",
+    max_input: u64::MAX,
+    fits_u64: false,
+    result_bound: Some(ResultBound::Digest),
+};
+
+impl ComputationInfo {
+    /// Cheap sanity check of a claimed result, independent of recomputing
+    /// it: fibonacci results must be Fibonacci numbers, factorial results
+    /// factorials, nth_prime results prime and hash_computation results
+    /// SHA-256 digests. `None` for computations without a bound, which
+    /// accept any result.
+    pub fn validate_result(&self, result: &ResultValue) -> Option<bool> {
+        self.result_bound.map(|bound| bound.accepts(result))
+    }
+}
+
+/// Whether `value` appears in the Fibonacci sequence, walking it until
+/// the terms reach `value` (false once they overflow a u128)
+fn is_fibonacci_number(value: u128) -> bool {
+    let (mut a, mut b) = (0u128, 1u128);
+    while a < value {
+        match a.checked_add(b) {
+            Some(next) => (a, b) = (b, next),
+            None => return false,
+        }
+    }
+    a == value
+}
+
+/// Whether `value` is n! for some n up to `FACTORIAL.max_input`
+fn is_factorial(value: u64) -> bool {
+    let mut factorial = 1u64;
+    for k in 2..=FACTORIAL.max_input {
+        if factorial >= value {
+            break;
+        }
+        factorial *= k;
+    }
+    factorial == value
+}
+
+/// Deterministic Miller-Rabin; these bases are exact for every u64
+fn is_prime(value: u64) -> bool {
+    const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    if value < 2 {
+        return false;
+    }
+    if let Some(&base) = BASES.iter().find(|&&base| value.is_multiple_of(base)) {
+        return value == base;
+    }
+
+    let mul_mod = |a: u64, b: u64| ((a as u128 * b as u128) % value as u128) as u64;
+    let pow_mod = |mut base: u64, mut exp: u64| {
+        let mut acc = 1u64;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                acc = mul_mod(acc, base);
+            }
+            base = mul_mod(base, base);
+            exp >>= 1;
+        }
+        acc
+    };
+
+    let shift = (value - 1).trailing_zeros();
+    let odd = (value - 1) >> shift;
+    BASES.iter().all(|&base| {
+        let mut x = pow_mod(base, odd);
+        if x == 1 || x == value - 1 {
+            return true;
+        }
+        for _ in 1..shift {
+            x = mul_mod(x, x);
+            if x == value - 1 {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+/// A computation over a single u64 input that records its own trace, for
+/// `STARKProver::prove_computation`
+pub trait Computation {
+    /// Static description, including the name recorded in the proof
+    fn info(&self) -> ComputationInfo;
+
+    /// Run the computation, returning the result and its trace
+    fn run(&self, input: u64) -> Result<(u64, ProofTrace), ComputationError>;
+
+    /// Cheap sanity check of a claimed result, independent of recomputing
+    /// it, or `None` when nothing can be said (see
+    /// `ComputationInfo::validate_result`)
+    fn validate_result(&self, result: &ResultValue) -> Option<bool> {
+        self.info().validate_result(result)
+    }
+}
+
+/// n-th Fibonacci number, see `fibonacci_with_trace`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Fibonacci;
+
+impl Computation for Fibonacci {
+    fn info(&self) -> ComputationInfo {
+        FIBONACCI
+    }
+
+    fn run(&self, n: u64) -> Result<(u64, ProofTrace), ComputationError> {
+        fibonacci_with_trace(n)
+    }
+}
+
+/// n!, see `factorial_with_trace`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Factorial;
+
+impl Computation for Factorial {
+    fn info(&self) -> ComputationInfo {
+        FACTORIAL
+    }
+
+    fn run(&self, n: u64) -> Result<(u64, ProofTrace), ComputationError> {
+        if n > FACTORIAL.max_input {
+            return Err(ComputationError::InputTooLarge { max: FACTORIAL.max_input });
+        }
+        Ok(factorial_with_trace(n))
+    }
+}
+
+/// n-th prime, see `nth_prime_with_trace`; runs to completion
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NthPrime;
+
+impl Computation for NthPrime {
+    fn info(&self) -> ComputationInfo {
+        NTH_PRIME
+    }
+
+    fn run(&self, n: u64) -> Result<(u64, ProofTrace), ComputationError> {
+        nth_prime_with_trace(n, &CancellationToken::new())
+    }
+}
+
+/// Re-execute `computation` from the `inputs` recorded in its trace,
+/// returning the result and a freshly generated trace to compare against
+/// the stored one. The result is the one a proof would claim, e.g. the
+/// full digest for `hash_computation`.
+///
+/// Fails with `ComputationError::NotReplayable` for computations whose
+/// inputs are not all in the trace (such as `sum_file`) or when an input
+/// is missing or out of range.
+pub fn replay(computation: &str, inputs: &BTreeMap<String, u64>) -> Result<(ResultValue, ProofTrace), ComputationError> {
+    let not_replayable = || ComputationError::NotReplayable { computation: computation.to_string() };
+    let input = |key: String| inputs.get(&key).copied().ok_or_else(not_replayable);
+    let inputs_list = |prefix: &str, count: u64| -> Result<Vec<u64>, ComputationError> {
+        (0..count).map(|j| input(format!("{}_{}", prefix, j))).collect()
+    };
+
+    let numeric = |(result, trace): (u64, ProofTrace)| (ResultValue::U64(result), trace);
+    match computation {
+        "fibonacci" => fibonacci_with_trace(input("n".to_string())?).map(numeric),
+        "factorial" => Factorial.run(input("n".to_string())?).map(numeric),
+        "nth_prime" => NthPrime.run(input("n".to_string())?).map(numeric),
+        "hash_computation" => {
+            let (digest, trace) = hash_computation_with_trace(input("input".to_string())?);
+            Ok((ResultValue::Digest(digest), trace))
+        }
+        "modmul_chain" => {
+            let modulus = input("modulus".to_string())?;
+            let count = input("count".to_string())?;
+            if modulus < 2 || count > MODMUL_CHAIN.max_input {
+                return Err(not_replayable());
+            }
+            let values = inputs_list("value", count)?;
+            Ok(numeric(modmul_chain_with_trace(&values, modulus)))
+        }
+        "linear_recurrence" | "signed_linear_recurrence" => {
+            let order = input("order".to_string())?;
+            if order == 0 || order > LINEAR_RECURRENCE.max_input {
+                return Err(not_replayable());
+            }
+            let n = input("n".to_string())?;
+            let coeffs = inputs_list("coeff", order)?;
+            let initial = inputs_list("initial", order)?;
+            if computation == "linear_recurrence" {
+                return linear_recurrence_with_trace(&coeffs, &initial, n).map(numeric);
+            }
+            // Signed inputs are stored in two's complement
+            let signed = |values: Vec<u64>| values.into_iter().map(|v| v as i64).collect::<Vec<i64>>();
+            signed_linear_recurrence_with_trace(&signed(coeffs), &signed(initial), n)
+                .map(|(result, trace)| (ResultValue::from(result), trace))
+        }
+        _ => Err(not_replayable()),
+    }
+}
+
+const COMPUTATIONS: &[ComputationInfo] = &[
+    FIBONACCI,
+    FACTORIAL,
+    MODMUL_CHAIN,
+    SUM_FILE,
+    NTH_PRIME,
+    LINEAR_RECURRENCE,
+    SIGNED_LINEAR_RECURRENCE,
+    HASH_COMPUTATION,
+];
+
+/// A computation named in a proof, as a closed set for matching on
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ComputationKind {
+    Fibonacci,
+    Factorial,
+    ModmulChain,
+    SumFile,
+    NthPrime,
+    LinearRecurrence,
+    SignedLinearRecurrence,
+    HashComputation,
+    /// A computation this crate does not know, such as a proved JSON trace
+    Other(String),
+}
+
+impl ComputationKind {
+    /// The kind for a `Proof::computation` name
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "fibonacci" => ComputationKind::Fibonacci,
+            "factorial" => ComputationKind::Factorial,
+            "modmul_chain" => ComputationKind::ModmulChain,
+            "sum_file" => ComputationKind::SumFile,
+            "nth_prime" => ComputationKind::NthPrime,
+            "linear_recurrence" => ComputationKind::LinearRecurrence,
+            "signed_linear_recurrence" => ComputationKind::SignedLinearRecurrence,
+            "hash_computation" => ComputationKind::HashComputation,
+            other => ComputationKind::Other(other.to_string()),
+        }
+    }
+
+    /// The name recorded in `Proof::computation`
+    pub fn name(&self) -> &str {
+        match self {
+            ComputationKind::Fibonacci => FIBONACCI.name,
+            ComputationKind::Factorial => FACTORIAL.name,
+            ComputationKind::ModmulChain => MODMUL_CHAIN.name,
+            ComputationKind::SumFile => SUM_FILE.name,
+            ComputationKind::NthPrime => NTH_PRIME.name,
+            ComputationKind::LinearRecurrence => LINEAR_RECURRENCE.name,
+            ComputationKind::SignedLinearRecurrence => SIGNED_LINEAR_RECURRENCE.name,
+            ComputationKind::HashComputation => HASH_COMPUTATION.name,
+            ComputationKind::Other(name) => name,
+        }
+    }
+
+    /// Description of a known computation
+    pub fn info(&self) -> Option<ComputationInfo> {
+        info(self.name())
+    }
+}
+
+impl std::fmt::Display for ComputationKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// List every computation the crate can prove
+pub fn supported() -> Vec<ComputationInfo> {
+    COMPUTATIONS.to_vec()
+}
+
+/// Look up the description of a computation by name
+pub fn info(name: &str) -> Option<ComputationInfo> {
+    COMPUTATIONS.iter().find(|info| info.name == name).copied()
+}
+
+/// Deepest recursion a recursive computation may reach before failing with
+/// `ComputationError::RecursionTooDeep` instead of risking a stack overflow
+pub const MAX_RECURSION_DEPTH: usize = 256;
 
 /// Fibonacci computation with trace
-pub fn fibonacci_with_trace(n: u64) -> (u64, ProofTrace) {
+///
+/// Fails with `ComputationError::InputTooLarge` above `FIBONACCI.max_input`
+/// and with `ComputationError::Overflow` as soon as an intermediate value no
+/// longer fits in a u64 (from fibonacci(94) onwards).
+pub fn fibonacci_with_trace(n: u64) -> Result<(u64, ProofTrace), ComputationError> {
+    fibonacci_with_trace_limited(n, MAX_RECURSION_DEPTH)
+}
+
+/// `fibonacci_with_trace` with a custom recursion limit: computing
+/// fibonacci(n) recurses `n - 1` calls deep, so fails with
+/// `ComputationError::RecursionTooDeep` when that exceeds `max_depth`
+pub fn fibonacci_with_trace_limited(n: u64, max_depth: usize) -> Result<(u64, ProofTrace), ComputationError> {
     let mut trace = ProofTrace::new();
     trace.set_input("n", n);
 
-    if n > 100 {
-        panic!("n must be <= 100 for performance reasons");
+    if n > FIBONACCI.max_input {
+        return Err(ComputationError::InputTooLarge { max: FIBONACCI.max_input });
     }
 
     let mut memo = std::collections::HashMap::new();
 
-    fn fib_memo(num: u64, memo: &mut std::collections::HashMap<u64, u64>, trace: &mut ProofTrace, depth: usize) -> u64 {
+    fn fib_memo(
+        num: u64,
+        memo: &mut std::collections::HashMap<u64, u64>,
+        trace: &mut ProofTrace,
+        depth: usize,
+        max_depth: usize,
+    ) -> Result<u64, ComputationError> {
+        if depth > max_depth {
+            return Err(ComputationError::RecursionTooDeep { max: max_depth });
+        }
         if let Some(&result) = memo.get(&num) {
             trace.add_step(TraceStep {
                 step: trace.steps.len(),
@@ -21,7 +453,7 @@ pub fn fibonacci_with_trace(n: u64) -> (u64, ProofTrace) {
                 output: result,
                 depth,
             });
-            return result;
+            return Ok(result);
         }
 
         let result = if num == 0 {
@@ -29,7 +461,9 @@ pub fn fibonacci_with_trace(n: u64) -> (u64, ProofTrace) {
         } else if num == 1 {
             1
         } else {
-            fib_memo(num - 1, memo, trace, depth + 1) + fib_memo(num - 2, memo, trace, depth + 1)
+            let a = fib_memo(num - 1, memo, trace, depth + 1, max_depth)?;
+            let b = fib_memo(num - 2, memo, trace, depth + 1, max_depth)?;
+            a.checked_add(b).ok_or(ComputationError::Overflow { index: num })?
         };
 
         memo.insert(num, result);
@@ -41,60 +475,648 @@ pub fn fibonacci_with_trace(n: u64) -> (u64, ProofTrace) {
             depth,
         });
 
+        Ok(result)
+    }
+
+    let result = fib_memo(n, &mut memo, &mut trace, 0, max_depth)?;
+    trace.set_output("result", result);
+
+    Ok((result, trace))
+}
+
+/// Largest n accepted by `fibonacci_naive_with_trace`; its trace has
+/// `2 * fib(n + 1) - 1` steps, about 240,000 at this cap
+pub const FIBONACCI_NAIVE_MAX_INPUT: u64 = 25;
+
+/// Fibonacci by plain recursion, without memoization, for teaching.
+///
+/// Every call of the exponential recursion tree is recorded as a step
+/// (with its depth), which shows how the trace explodes compared with
+/// `fibonacci_with_trace`. Fails with `ComputationError::InputTooLarge`
+/// above `FIBONACCI_NAIVE_MAX_INPUT`.
+pub fn fibonacci_naive_with_trace(n: u64) -> Result<(u64, ProofTrace), ComputationError> {
+    let mut trace = ProofTrace::new();
+    trace.set_input("n", n);
+
+    if n > FIBONACCI_NAIVE_MAX_INPUT {
+        return Err(ComputationError::InputTooLarge { max: FIBONACCI_NAIVE_MAX_INPUT });
+    }
+
+    fn fib_naive(num: u64, trace: &mut ProofTrace, depth: usize) -> u64 {
+        let (operation, result) = if num < 2 {
+            ("fib_base", num)
+        } else {
+            let a = fib_naive(num - 1, trace, depth + 1);
+            let b = fib_naive(num - 2, trace, depth + 1);
+            ("fib_compute", a + b)
+        };
+        trace.add_step(TraceStep {
+            step: trace.steps.len(),
+            operation: operation.to_string(),
+            input: num,
+            output: result,
+            depth,
+        });
         result
     }
 
-    let result = fib_memo(n, &mut memo, &mut trace, 0);
+    let result = fib_naive(n, &mut trace, 0);
     trace.set_output("result", result);
 
-    (result, trace)
+    Ok((result, trace))
 }
 
-/// Simple hash-based computation for testing
+/// Factorial computation with trace
+pub fn factorial_with_trace(n: u64) -> (u64, ProofTrace) {
+    let mut trace = ProofTrace::new();
+    trace.set_input("n", n);
+
+    if n > FACTORIAL.max_input {
+        panic!("n must be <= {} to fit in a u64", FACTORIAL.max_input);
+    }
+
+    let mut acc = 1u64;
+    for i in 1..=n {
+        acc *= i;
+        trace.add_step(TraceStep {
+            step: trace.steps.len(),
+            operation: "multiply".to_string(),
+            input: i,
+            output: acc,
+            depth: 0,
+        });
+    }
+
+    trace.set_output("result", acc);
+    (acc, trace)
+}
+
+/// Product of `values` modulo `modulus`, recording each multiply-and-reduce
+///
+/// The modulus, the factor count and every factor (`value_<i>`) are stored
+/// as inputs so a verifier can recompute the result.
+pub fn modmul_chain_with_trace(values: &[u64], modulus: u64) -> (u64, ProofTrace) {
+    let mut trace = ProofTrace::new();
+    trace.try_set_input("modulus", modulus).expect("modmul_chain input keys are distinct");
+    trace.try_set_input("count", values.len() as u64).expect("modmul_chain input keys are distinct");
+
+    if modulus < 2 {
+        panic!("modulus must be at least 2");
+    }
+
+    let mut acc = 1 % modulus;
+    for (i, &value) in values.iter().enumerate() {
+        trace
+            .try_set_input(format!("value_{}", i), value)
+            .expect("modmul_chain input keys are distinct");
+        acc = ((acc as u128 * value as u128) % modulus as u128) as u64;
+        trace.add_step(TraceStep {
+            step: i,
+            operation: "modmul".to_string(),
+            input: value,
+            output: acc,
+            depth: 0,
+        });
+    }
+
+    trace.set_output("result", acc);
+    (acc, trace)
+}
+
+/// Sum newline-separated u64 values from a file, recording each addition
+///
+/// Blank lines are ignored. An empty file sums to 0 and records a single
+/// boundary step so the trace still has a constraint to prove. The values
+/// themselves stay private; only their count is a public input.
+pub fn sum_file_with_trace(path: impl AsRef<std::path::Path>) -> Result<(u64, ProofTrace), ComputationError> {
+    let contents = std::fs::read_to_string(path).map_err(|e| ComputationError::Io(e.to_string()))?;
+
+    let mut trace = ProofTrace::new();
+    let mut sum = 0u64;
+    for (line_idx, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let value: u64 = line.parse().map_err(|_| ComputationError::InvalidInput {
+            line: line_idx + 1,
+            content: line.to_string(),
+        })?;
+        sum = sum
+            .checked_add(value)
+            .ok_or(ComputationError::Overflow { index: trace.steps.len() as u64 })?;
+        trace.add_step(TraceStep {
+            step: trace.steps.len(),
+            operation: "add".to_string(),
+            input: value,
+            output: sum,
+            depth: 0,
+        });
+    }
+    trace.set_input("count", trace.steps.len() as u64);
+
+    if trace.steps.is_empty() {
+        trace.add_step(TraceStep {
+            step: 0,
+            operation: "boundary".to_string(),
+            input: 0,
+            output: 0,
+            depth: 0,
+        });
+    }
+
+    trace.set_output("result", sum);
+    Ok((sum, trace))
+}
+
+/// The n-th prime (1-indexed, so `nth_prime(1) = 2`), recording each prime found
+///
+/// Checks `cancel` before testing every candidate and returns
+/// `ComputationError::Cancelled` once it is set, so long searches can be
+/// stopped by `run_with_timeout` or a signal handler.
+pub fn nth_prime_with_trace(n: u64, cancel: &CancellationToken) -> Result<(u64, ProofTrace), ComputationError> {
+    let mut trace = ProofTrace::new();
+    trace.set_input("n", n);
+
+    if n == 0 || n > NTH_PRIME.max_input {
+        return Err(ComputationError::InputTooLarge { max: NTH_PRIME.max_input });
+    }
+
+    let mut primes: Vec<u64> = Vec::new();
+    let mut candidate = 2u64;
+    while (primes.len() as u64) < n {
+        if cancel.is_cancelled() {
+            return Err(ComputationError::Cancelled);
+        }
+        let is_prime = primes
+            .iter()
+            .take_while(|&&p| p * p <= candidate)
+            .all(|&p| !candidate.is_multiple_of(p));
+        if is_prime {
+            primes.push(candidate);
+            trace.add_step(TraceStep {
+                step: trace.steps.len(),
+                operation: "prime".to_string(),
+                input: primes.len() as u64,
+                output: candidate,
+                depth: 0,
+            });
+        }
+        candidate += 1;
+    }
+
+    let result = *primes.last().expect("n >= 1 primes were found");
+    trace.set_output("result", result);
+    Ok((result, trace))
+}
+
+/// Run `compute` on a worker thread, giving up after `timeout`.
+///
+/// On timeout the token passed to `compute` is cancelled and
+/// `ComputationError::Timeout` is returned immediately. Computations that
+/// poll the token stop promptly; others keep running detached until they
+/// finish on their own.
+pub fn run_with_timeout<F, R>(compute: F, timeout: Duration) -> Result<(R, ProofTrace), ComputationError>
+where
+    F: FnOnce(&CancellationToken) -> Result<(R, ProofTrace), ComputationError> + Send + 'static,
+    R: Send + 'static,
+{
+    let cancel = CancellationToken::new();
+    let worker_cancel = cancel.clone();
+    let (sender, receiver) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        // The receiver is gone if we already timed out
+        let _ = sender.send(compute(&worker_cancel));
+    });
+
+    match receiver.recv_timeout(timeout) {
+        Ok(outcome) => outcome,
+        Err(_) => {
+            cancel.cancel();
+            Err(ComputationError::Timeout { limit_ms: timeout.as_millis() as u64 })
+        }
+    }
+}
+
+/// n-th term of the order-k linear recurrence `a_i = sum(coeffs[j] * a_(i-1-j))`
+///
+/// `initial` holds `a_0..a_(k-1)` and must be as long as `coeffs`, so
+/// fibonacci is `coeffs = [1, 1]`, `initial = [0, 1]`. The order, every
+/// coefficient (`coeff_<j>`) and initial value (`initial_<j>`) are stored
+/// as inputs so a verifier can recompute the result. Fails with
+/// `ComputationError::Overflow` once a term no longer fits in a u64.
+pub fn linear_recurrence_with_trace(
+    coeffs: &[u64],
+    initial: &[u64],
+    n: u64,
+) -> Result<(u64, ProofTrace), ComputationError> {
+    assert!(!coeffs.is_empty(), "a recurrence needs at least one coefficient");
+    assert_eq!(coeffs.len(), initial.len(), "need one initial value per coefficient");
+
+    let mut trace = ProofTrace::new();
+    trace.try_set_input("n", n).expect("linear_recurrence input keys are distinct");
+    trace
+        .try_set_input("order", coeffs.len() as u64)
+        .expect("linear_recurrence input keys are distinct");
+    for (j, (&coeff, &value)) in coeffs.iter().zip(initial).enumerate() {
+        trace
+            .try_set_input(format!("coeff_{}", j), coeff)
+            .expect("linear_recurrence input keys are distinct");
+        trace
+            .try_set_input(format!("initial_{}", j), value)
+            .expect("linear_recurrence input keys are distinct");
+    }
+
+    if n > LINEAR_RECURRENCE.max_input {
+        return Err(ComputationError::InputTooLarge { max: LINEAR_RECURRENCE.max_input });
+    }
+
+    let mut terms: Vec<u64> = Vec::with_capacity(n as usize + 1);
+    for i in 0..=n {
+        let (operation, term) = match initial.get(i as usize) {
+            Some(&value) => ("initial", value),
+            None => {
+                let term = coeffs
+                    .iter()
+                    .zip(terms.iter().rev())
+                    .try_fold(0u64, |acc, (&c, &a)| c.checked_mul(a).and_then(|w| acc.checked_add(w)))
+                    .ok_or(ComputationError::Overflow { index: i })?;
+                ("weighted_sum", term)
+            }
+        };
+        terms.push(term);
+        trace.add_step(TraceStep {
+            step: i as usize,
+            operation: operation.to_string(),
+            input: i,
+            output: term,
+            depth: 0,
+        });
+    }
+
+    let result = terms[n as usize];
+    trace.set_output("result", result);
+    Ok((result, trace))
+}
+
+/// n-th term of a linear recurrence over signed integers, like
+/// `linear_recurrence_with_trace` but with `i64` coefficients and terms.
+///
+/// Steps are built with `TraceStep::signed` and the coefficients and
+/// initial values are stored as signed inputs. Fails with
+/// `ComputationError::Overflow` once a term no longer fits in an i64.
+pub fn signed_linear_recurrence_with_trace(
+    coeffs: &[i64],
+    initial: &[i64],
+    n: u64,
+) -> Result<(i64, ProofTrace), ComputationError> {
+    assert!(!coeffs.is_empty(), "a recurrence needs at least one coefficient");
+    assert_eq!(coeffs.len(), initial.len(), "need one initial value per coefficient");
+
+    let mut trace = ProofTrace::new();
+    trace.try_set_input("n", n).expect("signed_linear_recurrence input keys are distinct");
+    trace
+        .try_set_input("order", coeffs.len() as u64)
+        .expect("signed_linear_recurrence input keys are distinct");
+    for (j, (&coeff, &value)) in coeffs.iter().zip(initial).enumerate() {
+        trace
+            .try_set_signed_input(format!("coeff_{}", j), coeff)
+            .expect("signed_linear_recurrence input keys are distinct");
+        trace
+            .try_set_signed_input(format!("initial_{}", j), value)
+            .expect("signed_linear_recurrence input keys are distinct");
+    }
+
+    if n > SIGNED_LINEAR_RECURRENCE.max_input {
+        return Err(ComputationError::InputTooLarge { max: SIGNED_LINEAR_RECURRENCE.max_input });
+    }
+
+    let mut terms: Vec<i64> = Vec::with_capacity(n as usize + 1);
+    for i in 0..=n {
+        let (operation, term) = match initial.get(i as usize) {
+            Some(&value) => ("initial", value),
+            None => {
+                let term = coeffs
+                    .iter()
+                    .zip(terms.iter().rev())
+                    .try_fold(0i64, |acc, (&c, &a)| c.checked_mul(a).and_then(|w| acc.checked_add(w)))
+                    .ok_or(ComputationError::Overflow { index: i })?;
+                ("weighted_sum", term)
+            }
+        };
+        terms.push(term);
+        trace.add_step(TraceStep::signed(i as usize, operation, i as i64, term, 0));
+    }
+
+    let result = terms[n as usize];
+    trace.set_signed_output("result", result);
+    Ok((result, trace))
+}
+
+/// Number of SHA-256 rounds in `hash_computation_with_trace`
+pub const HASH_ROUNDS: usize = 5;
+
+/// Hash computation with trace: SHA-256 applied `HASH_ROUNDS` times,
+/// starting from the hex encoding of `input`.
+///
+/// Returns the final digest. Trace steps record each round's digest by its
+/// low 64 bits (see `digest_low_u64`); a round's input is the previous
+/// round's output, and the first round's is `input` itself.
 pub fn hash_computation_with_trace(input: u64) -> (String, ProofTrace) {
     let mut trace = ProofTrace::new();
     trace.set_input("input", input);
 
     use crate::crypto::hash_string;
 
-    let input_str = format!("{:x}", input);
-    let mut current = input_str.clone();
+    let mut current = format!("{:x}", input);
+    let mut round_input = input;
 
-    for i in 0..5 {
+    for i in 0..HASH_ROUNDS {
         let output = hash_string(&current);
+        let round_output = digest_low_u64(&output);
         trace.add_step(TraceStep {
             step: i,
             operation: format!("hash_round_{}", i),
-            input: input,
-            output: (output.len() as u64), // Store output length as u64
+            input: round_input,
+            output: round_output,
             depth: i,
         });
+        round_input = round_output;
         current = output;
     }
 
     (current, trace)
 }
 
+/// The low 64 bits of a hex digest (its last 16 hex characters); 0 if
+/// those are not valid hex
+pub fn digest_low_u64(digest: &str) -> u64 {
+    let start = digest.len().saturating_sub(16);
+    digest.get(start..).and_then(|low| u64::from_str_radix(low, 16).ok()).unwrap_or(0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_fibonacci() {
-        let (result, _trace) = fibonacci_with_trace(10);
+        let (result, _trace) = fibonacci_with_trace(10).unwrap();
         assert_eq!(result, 55);
     }
 
+    #[test]
+    fn test_fibonacci_overflow() {
+        let (largest, _) = fibonacci_with_trace(93).unwrap();
+        assert_eq!(largest, 12_200_160_415_121_876_738);
+
+        assert_eq!(
+            fibonacci_with_trace(100).unwrap_err(),
+            ComputationError::Overflow { index: 94 }
+        );
+    }
+
+    #[test]
+    fn test_fibonacci_input_limit() {
+        assert_eq!(
+            fibonacci_with_trace(101).unwrap_err(),
+            ComputationError::InputTooLarge { max: FIBONACCI.max_input }
+        );
+    }
+
+    #[test]
+    fn test_fibonacci_recursion_limit() {
+        // fibonacci(10) recurses down to fib(1) nine calls deep
+        assert_eq!(
+            fibonacci_with_trace_limited(10, 5).unwrap_err(),
+            ComputationError::RecursionTooDeep { max: 5 }
+        );
+        assert_eq!(fibonacci_with_trace_limited(10, 9).unwrap().0, 55);
+        assert_eq!(
+            fibonacci_with_trace_limited(10, 8).unwrap_err(),
+            ComputationError::RecursionTooDeep { max: 8 }
+        );
+    }
+
+    #[test]
+    fn test_fibonacci_naive_trace_explodes() {
+        let (result, trace) = fibonacci_naive_with_trace(10).unwrap();
+        assert_eq!(result, 55);
+        // One step per call: 2 * fib(11) - 1
+        assert_eq!(trace.steps.len(), 177);
+        assert_eq!(trace.steps_by_operation("fib_base").count(), 89);
+        assert_eq!(trace.steps.last().unwrap().depth, 0);
+
+        let (_, memoized) = fibonacci_with_trace(10).unwrap();
+        assert!(memoized.steps.len() < trace.steps.len() / 5);
+
+        assert_eq!(
+            fibonacci_naive_with_trace(FIBONACCI_NAIVE_MAX_INPUT + 1).unwrap_err(),
+            ComputationError::InputTooLarge { max: FIBONACCI_NAIVE_MAX_INPUT }
+        );
+    }
+
     #[test]
     fn test_fibonacci_trace() {
-        let (_result, trace) = fibonacci_with_trace(5);
+        let (_result, trace) = fibonacci_with_trace(5).unwrap();
         assert!(!trace.steps.is_empty());
         assert_eq!(*trace.outputs.get("result").unwrap(), 5);
     }
 
+    #[test]
+    fn test_factorial() {
+        let (result, trace) = factorial_with_trace(5);
+        assert_eq!(result, 120);
+        assert_eq!(trace.steps.len(), 5);
+
+        let (max, _) = factorial_with_trace(FACTORIAL.max_input);
+        assert_eq!(max, 2_432_902_008_176_640_000);
+    }
+
+    fn temp_input(name: &str, contents: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("stark_sum_{}_{}", std::process::id(), name));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_sum_file() {
+        let path = temp_input("values.txt", "1\n2\n\n39\n");
+        let (sum, trace) = sum_file_with_trace(&path).unwrap();
+        assert_eq!(sum, 42);
+        assert_eq!(trace.steps.len(), 3);
+        assert_eq!(trace.inputs.get("count"), Some(&3));
+        std::fs::remove_file(path).ok();
+
+        let empty = temp_input("empty.txt", "");
+        let (sum, trace) = sum_file_with_trace(&empty).unwrap();
+        assert_eq!(sum, 0);
+        assert_eq!(trace.steps.len(), 1);
+        assert_eq!(trace.steps[0].operation, "boundary");
+        std::fs::remove_file(empty).ok();
+    }
+
+    #[test]
+    fn test_sum_file_errors() {
+        let malformed = temp_input("malformed.txt", "1\ntwo\n3\n");
+        assert_eq!(
+            sum_file_with_trace(&malformed).unwrap_err(),
+            ComputationError::InvalidInput { line: 2, content: "two".to_string() }
+        );
+        std::fs::remove_file(malformed).ok();
+
+        let overflow = temp_input("overflow.txt", &format!("{}\n1\n", u64::MAX));
+        assert_eq!(sum_file_with_trace(&overflow).unwrap_err(), ComputationError::Overflow { index: 1 });
+        std::fs::remove_file(overflow).ok();
+
+        assert!(matches!(
+            sum_file_with_trace("/nonexistent/stark_sum_input.txt"),
+            Err(ComputationError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn test_nth_prime() {
+        let cancel = CancellationToken::new();
+        let (prime, trace) = nth_prime_with_trace(10, &cancel).unwrap();
+        assert_eq!(prime, 29);
+        assert_eq!(trace.steps.len(), 10);
+
+        cancel.cancel();
+        assert_eq!(nth_prime_with_trace(10, &cancel).unwrap_err(), ComputationError::Cancelled);
+    }
+
+    #[test]
+    fn test_validate_result() {
+        assert_eq!(FIBONACCI.validate_result(&ResultValue::U64(55)), Some(true));
+        assert_eq!(FIBONACCI.validate_result(&ResultValue::U64(0)), Some(true));
+        assert_eq!(FIBONACCI.validate_result(&ResultValue::U64(56)), Some(false));
+        assert_eq!(FIBONACCI.validate_result(&ResultValue::U128(354224848179261915075)), Some(true));
+        assert_eq!(FACTORIAL.validate_result(&ResultValue::U64(120)), Some(true));
+        assert_eq!(FACTORIAL.validate_result(&ResultValue::U64(121)), Some(false));
+        assert_eq!(NTH_PRIME.validate_result(&ResultValue::U64(541)), Some(true));
+        assert_eq!(NTH_PRIME.validate_result(&ResultValue::U64(18446744073709551557)), Some(true));
+        assert_eq!(NTH_PRIME.validate_result(&ResultValue::U64(3215031751)), Some(false));
+        assert_eq!(NTH_PRIME.validate_result(&ResultValue::U64(1)), Some(false));
+        assert_eq!(MODMUL_CHAIN.validate_result(&ResultValue::U64(56)), None);
+
+        // The trait method reads the same bound
+        assert_eq!(NthPrime.validate_result(&ResultValue::U64(27)), Some(false));
+        assert_eq!(Fibonacci.validate_result(&ResultValue::U64(55)), Some(true));
+    }
+
+    #[test]
+    fn test_replay_from_trace_inputs() {
+        let (result, trace) = linear_recurrence_with_trace(&[1, 1, 1], &[0, 0, 1], 12).unwrap();
+        let (replayed, replayed_trace) = replay("linear_recurrence", &trace.inputs).unwrap();
+        assert_eq!(replayed, result);
+        assert!(trace.compare(&replayed_trace).is_identical());
+
+        let (digest, trace) = hash_computation_with_trace(42);
+        let (replayed, _) = replay("hash_computation", &trace.inputs).unwrap();
+        assert_eq!(replayed, ResultValue::Digest(digest));
+
+        let inputs = BTreeMap::from([("count".to_string(), 2)]);
+        assert!(matches!(replay("sum_file", &inputs), Err(ComputationError::NotReplayable { .. })));
+        assert!(matches!(replay("fibonacci", &inputs), Err(ComputationError::NotReplayable { .. })));
+    }
+
+    #[test]
+    fn test_run_with_timeout() {
+        // Never finishes on its own; only cancellation stops it
+        let spin = |cancel: &CancellationToken| {
+            while !cancel.is_cancelled() {
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            Err::<(u64, ProofTrace), _>(ComputationError::Cancelled)
+        };
+        assert_eq!(
+            run_with_timeout(spin, Duration::from_millis(20)).unwrap_err(),
+            ComputationError::Timeout { limit_ms: 20 }
+        );
+
+        let (prime, _) = run_with_timeout(|cancel| nth_prime_with_trace(5, cancel), Duration::from_secs(30)).unwrap();
+        assert_eq!(prime, 11);
+    }
+
+    #[test]
+    fn test_fibonacci_as_linear_recurrence() {
+        for n in [0, 1, 2, 10, 50, 93] {
+            let (term, _) = linear_recurrence_with_trace(&[1, 1], &[0, 1], n).unwrap();
+            assert_eq!(term, fibonacci_with_trace(n).unwrap().0);
+        }
+        assert_eq!(
+            linear_recurrence_with_trace(&[1, 1], &[0, 1], 94).unwrap_err(),
+            ComputationError::Overflow { index: 94 }
+        );
+    }
+
+    #[test]
+    fn test_tribonacci() {
+        // 0, 0, 1, 1, 2, 4, 7, 13, 24, 44, 81
+        let (term, trace) = linear_recurrence_with_trace(&[1, 1, 1], &[0, 0, 1], 10).unwrap();
+        assert_eq!(term, 81);
+        assert_eq!(trace.steps.len(), 11);
+        assert_eq!(trace.steps[3].operation, "weighted_sum");
+        assert_eq!(trace.inputs.get("order"), Some(&3));
+    }
+
+    #[test]
+    fn test_computation_kind_names() {
+        for info in supported() {
+            let kind = ComputationKind::from_name(info.name);
+            assert!(!matches!(kind, ComputationKind::Other(_)), "{}", info.name);
+            assert_eq!(kind.name(), info.name);
+            assert_eq!(kind.info(), Some(info));
+        }
+        assert_eq!(ComputationKind::from_name("my_trace"), ComputationKind::Other("my_trace".to_string()));
+        assert_eq!(ComputationKind::from_name("my_trace").info(), None);
+    }
+
+    #[test]
+    fn test_supported_computations() {
+        let list = supported();
+        let arity = |name: &str| list.iter().find(|info| info.name == name).map(|info| info.arity);
+
+        assert_eq!(arity("fibonacci"), Some(1));
+        assert_eq!(arity("factorial"), Some(1));
+        assert_eq!(arity("modmul_chain"), Some(2));
+        assert_eq!(info("factorial").map(|info| info.max_input), Some(20));
+        assert!(info("unknown").is_none());
+    }
+
+    #[test]
+    fn test_modmul_chain() {
+        let modulus = 97;
+        let (result, trace) = modmul_chain_with_trace(&[10, 20, 30, 40], modulus);
+
+        assert_eq!(result, (10 * 20 * 30 * 40) % 97);
+        assert_eq!(trace.steps.len(), 4);
+        assert!(trace.steps.iter().all(|step| step.output < modulus));
+        assert_eq!(trace.inputs["modulus"], modulus);
+        assert_eq!(trace.inputs["value_2"], 30);
+    }
+
+    #[test]
+    fn test_modmul_chain_large_values() {
+        let modulus = (1u64 << 61) - 1;
+        let (result, _) = modmul_chain_with_trace(&[u64::MAX, u64::MAX], modulus);
+        let expected = ((u64::MAX as u128 % modulus as u128).pow(2) % modulus as u128) as u64;
+        assert_eq!(result, expected);
+    }
+
     #[test]
     fn test_hash_computation() {
-        let (hash, _trace) = hash_computation_with_trace(42);
-        assert!(!hash.is_empty());
+        let (hash, trace) = hash_computation_with_trace(42);
+        assert_eq!(hash.len(), 64);
+        assert_eq!(trace.steps.len(), HASH_ROUNDS);
+        assert_eq!(trace.steps[0].input, 42);
+        assert_eq!(trace.steps.last().unwrap().output, digest_low_u64(&hash));
+        assert_eq!(u64::from_str_radix(&hash[48..], 16).unwrap(), digest_low_u64(&hash));
+        for pair in trace.steps.windows(2) {
+            assert_eq!(pair[1].input, pair[0].output);
+        }
+        assert_eq!(HASH_COMPUTATION.validate_result(&ResultValue::Digest(hash)), Some(true));
+        assert_eq!(HASH_COMPUTATION.validate_result(&ResultValue::U64(42)), Some(false));
     }
 }
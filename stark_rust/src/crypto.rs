@@ -1,5 +1,11 @@
 // src/crypto.rs - Cryptographic utilities
+use rand_chacha::rand_core::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
 use sha2::{Sha256, Digest};
+use std::collections::BTreeMap;
+use crate::error::ParameterError;
+use crate::types::{ProofTrace, TraceStep};
+use crate::util::truncate_hash;
 
 /// Hash a value using SHA-256
 pub fn hash_bytes(data: &[u8]) -> String {
@@ -13,27 +19,593 @@ pub fn hash_string(data: &str) -> String {
     hash_bytes(data.as_bytes())
 }
 
-/// Hash a vector of integers
+/// Byte order used when hashing integers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ByteOrder {
+    /// Least significant byte first (the crate's historical encoding)
+    #[default]
+    LittleEndian,
+    /// Most significant byte first, for tooling that expects network order
+    BigEndian,
+}
+
+/// Hash algorithm configuration recorded in proofs.
+///
+/// The algorithm drives every commitment in a proof: the trace commitment,
+/// the hash chain, the Merkle trees and the FRI layer hashes. The byte
+/// order used for integer hashing is part of the algorithm's identity, so a
+/// proof's `hash_algorithm` name fully determines how it was hashed.
+/// Challenges come from the prover's `RandomOracle` and are not affected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HashAlgorithm {
+    /// SHA-256 with little-endian integers
+    #[default]
+    Sha256,
+    /// SHA-256 with big-endian integers
+    Sha256BigEndian,
+    /// BLAKE3 with little-endian integers
+    Blake3,
+    /// Keccak-256 (the pre-standard SHA-3 padding used by Ethereum) with
+    /// little-endian integers
+    Keccak256,
+}
+
+impl HashAlgorithm {
+    /// Every supported algorithm
+    pub const ALL: &'static [HashAlgorithm] = &[
+        HashAlgorithm::Sha256,
+        HashAlgorithm::Sha256BigEndian,
+        HashAlgorithm::Blake3,
+        HashAlgorithm::Keccak256,
+    ];
+
+    /// Name stored in `Proof::hash_algorithm`
+    pub fn name(&self) -> &'static str {
+        match self {
+            HashAlgorithm::Sha256 => "sha256",
+            HashAlgorithm::Sha256BigEndian => "sha256-be",
+            HashAlgorithm::Blake3 => "blake3",
+            HashAlgorithm::Keccak256 => "keccak256",
+        }
+    }
+
+    /// Parse a name stored in a proof
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "sha256" => Some(HashAlgorithm::Sha256),
+            "sha256-be" => Some(HashAlgorithm::Sha256BigEndian),
+            "blake3" => Some(HashAlgorithm::Blake3),
+            "keccak256" => Some(HashAlgorithm::Keccak256),
+            _ => None,
+        }
+    }
+
+    /// Byte order used for integers under this algorithm
+    pub fn byte_order(&self) -> ByteOrder {
+        match self {
+            HashAlgorithm::Sha256BigEndian => ByteOrder::BigEndian,
+            HashAlgorithm::Sha256 | HashAlgorithm::Blake3 | HashAlgorithm::Keccak256 => ByteOrder::LittleEndian,
+        }
+    }
+
+    /// Hash integers using this algorithm's byte order
+    pub fn hash_integers(&self, values: &[u64]) -> String {
+        self.hash_bytes(&encode_integers(values, self.byte_order()))
+    }
+
+    /// Hex digest of `data` under this algorithm
+    pub fn hash_bytes(&self, data: &[u8]) -> String {
+        match self {
+            HashAlgorithm::Sha256 | HashAlgorithm::Sha256BigEndian => hash_bytes(data),
+            HashAlgorithm::Blake3 => blake3::hash(data).to_hex().to_string(),
+            HashAlgorithm::Keccak256 => format!("{:x}", sha3::Keccak256::digest(data)),
+        }
+    }
+
+    /// Hex digest of a string under this algorithm
+    pub fn hash_string(&self, data: &str) -> String {
+        self.hash_bytes(data.as_bytes())
+    }
+
+    /// Commit to a trace by hashing its JSON serialization
+    pub fn commit_trace(&self, trace: &ProofTrace) -> String {
+        let trace_json = serde_json::to_string(trace).expect("Failed to serialize trace");
+        self.hash_string(&trace_json)
+    }
+
+    /// Hiding commitment to a trace: the hash of `salt` followed by the
+    /// trace JSON, so equal traces under different salts commit differently
+    pub fn commit_trace_salted(&self, trace: &ProofTrace, salt: &str) -> String {
+        let trace_json = serde_json::to_string(trace).expect("Failed to serialize trace");
+        self.hash_string(&format!("{}{}", salt, trace_json))
+    }
+
+    /// Running hash chain over trace steps: `h_i = hash(h_{i-1} || step_i)`.
+    ///
+    /// Returns every intermediate value; the last one commits to the whole
+    /// sequence, so reordering or omitting a step changes it.
+    pub fn hash_chain(&self, steps: &[TraceStep]) -> Vec<String> {
+        let mut links = Vec::with_capacity(steps.len());
+        let mut previous = String::new();
+        for step in steps {
+            let step_json = serde_json::to_string(step).expect("Failed to serialize trace step");
+            previous = self.hash_string(&format!("{}{}", previous, step_json));
+            links.push(previous.clone());
+        }
+        links
+    }
+
+    /// Merkle leaf for a trace step: the hash of its JSON serialization
+    pub fn merkle_leaf(&self, step: &TraceStep) -> String {
+        let step_json = serde_json::to_string(step).expect("Failed to serialize trace step");
+        self.hash_string(&step_json)
+    }
+
+    /// Merkle leaf for the constraint evaluation `value` at `index`
+    pub fn evaluation_leaf(&self, index: usize, value: u64) -> String {
+        self.hash_string(&format!("{}:{}", index, value))
+    }
+
+    /// Merkle root over every constraint evaluation, by position
+    pub fn evaluation_root(&self, evaluations: &[u64]) -> String {
+        let leaves: Vec<String> =
+            evaluations.iter().enumerate().map(|(i, &value)| self.evaluation_leaf(i, value)).collect();
+        self.merkle_root(&leaves)
+    }
+
+    /// Hash each level pairwise into the next, duplicating an odd last node
+    fn merkle_parent_level(&self, level: &[String]) -> Vec<String> {
+        level
+            .chunks(2)
+            .map(|pair| {
+                let right = pair.get(1).unwrap_or(&pair[0]);
+                self.hash_string(&format!("{}{}", pair[0], right))
+            })
+            .collect()
+    }
+
+    /// Merkle root over `leaves`, or an empty string if there are none
+    pub fn merkle_root(&self, leaves: &[String]) -> String {
+        if leaves.is_empty() {
+            return String::new();
+        }
+        let mut level = leaves.to_vec();
+        while level.len() > 1 {
+            level = self.merkle_parent_level(&level);
+        }
+        level.remove(0)
+    }
+
+    /// Authentication path for the leaf at `index`: its sibling at each level,
+    /// from the bottom of the tree up to (but excluding) the root
+    pub fn merkle_path(&self, leaves: &[String], mut index: usize) -> Vec<String> {
+        let mut path = Vec::new();
+        let mut level = leaves.to_vec();
+        while level.len() > 1 {
+            let sibling = if index.is_multiple_of(2) {
+                level.get(index + 1).unwrap_or(&level[index])
+            } else {
+                &level[index - 1]
+            };
+            path.push(sibling.clone());
+            level = self.merkle_parent_level(&level);
+            index /= 2;
+        }
+        path
+    }
+
+    /// Check that `leaf` sits at `index` under `root` given its authentication path
+    pub fn verify_merkle_path(&self, leaf: &str, mut index: usize, path: &[String], root: &str) -> bool {
+        let mut node = leaf.to_string();
+        for sibling in path {
+            node = if index.is_multiple_of(2) {
+                self.hash_string(&format!("{}{}", node, sibling))
+            } else {
+                self.hash_string(&format!("{}{}", sibling, node))
+            };
+            index /= 2;
+        }
+        node == root
+    }
+
+    /// Hash of one FRI layer: its leading values (at most five are used),
+    /// bound to the challenge and the layer's position
+    pub fn fri_layer_hash(&self, leading: &[u64], challenge: &str, layer_idx: usize) -> String {
+        let layer_data = format!(
+            "{}{}{}",
+            leading.iter().take(5).map(|e| e.to_string()).collect::<Vec<_>>().join(","),
+            challenge,
+            layer_idx
+        );
+        self.hash_string(&layer_data)
+    }
+
+    /// Run one FRI round on `evaluations`, the domain of layer `layer_idx`:
+    /// returns the layer's hash and the next domain, `fold_factor` times
+    /// shorter (every `fold_factor`-th value).
+    ///
+    /// The next domain is empty once a single value remains, so applying
+    /// this until the domain is empty yields exactly the proof's
+    /// `fri_layers`. Fold factors below 2 are raised to 2.
+    pub fn fold_layer(&self, evaluations: &[u64], challenge: &str, layer_idx: usize, fold_factor: usize) -> (String, Vec<u64>) {
+        let hash = self.fri_layer_hash(evaluations, challenge, layer_idx);
+        let next = if evaluations.len() > 1 {
+            evaluations.iter().step_by(fold_factor.max(2)).copied().collect()
+        } else {
+            Vec::new()
+        };
+        (hash, next)
+    }
+}
+
+/// Hash a vector of integers.
+///
+/// Each value is encoded as 8 little-endian bytes, so the hash is the same
+/// on every platform. Use `hash_integers_with_order` for big-endian.
 pub fn hash_integers(values: &[u64]) -> String {
-    let data = values.iter()
-        .map(|v| v.to_le_bytes().to_vec())
+    hash_integers_with_order(values, ByteOrder::LittleEndian)
+}
+
+/// Hash a vector of integers, encoding each as 8 bytes in the given order
+pub fn hash_integers_with_order(values: &[u64], order: ByteOrder) -> String {
+    hash_bytes(&encode_integers(values, order))
+}
+
+/// Concatenate `values` as 8-byte integers in the given order
+fn encode_integers(values: &[u64], order: ByteOrder) -> Vec<u8> {
+    values.iter()
+        .map(|v| match order {
+            ByteOrder::LittleEndian => v.to_le_bytes(),
+            ByteOrder::BigEndian => v.to_be_bytes(),
+        })
         .fold(Vec::new(), |mut acc, v| {
             acc.extend(v);
             acc
-        });
-    hash_bytes(&data)
+        })
+}
+
+/// Commit to a trace with SHA-256 (see `HashAlgorithm::commit_trace`)
+pub fn commit_trace(trace: &ProofTrace) -> String {
+    HashAlgorithm::Sha256.commit_trace(trace)
+}
+
+/// Salted SHA-256 trace commitment (see `HashAlgorithm::commit_trace_salted`)
+pub fn commit_trace_salted(trace: &ProofTrace, salt: &str) -> String {
+    HashAlgorithm::Sha256.commit_trace_salted(trace, salt)
+}
+
+/// SHA-256 hash chain over trace steps (see `HashAlgorithm::hash_chain`)
+pub fn hash_chain(steps: &[TraceStep]) -> Vec<String> {
+    HashAlgorithm::Sha256.hash_chain(steps)
+}
+
+/// SHA-256 Merkle leaf for a trace step (see `HashAlgorithm::merkle_leaf`)
+pub fn merkle_leaf(step: &TraceStep) -> String {
+    HashAlgorithm::Sha256.merkle_leaf(step)
+}
+
+/// SHA-256 Merkle leaf for an evaluation (see `HashAlgorithm::evaluation_leaf`)
+pub fn evaluation_leaf(index: usize, value: u64) -> String {
+    HashAlgorithm::Sha256.evaluation_leaf(index, value)
+}
+
+/// SHA-256 Merkle root over evaluations (see `HashAlgorithm::evaluation_root`)
+pub fn evaluation_root(evaluations: &[u64]) -> String {
+    HashAlgorithm::Sha256.evaluation_root(evaluations)
+}
+
+/// SHA-256 Merkle root over `leaves` (see `HashAlgorithm::merkle_root`)
+pub fn merkle_root(leaves: &[String]) -> String {
+    HashAlgorithm::Sha256.merkle_root(leaves)
+}
+
+/// SHA-256 authentication path (see `HashAlgorithm::merkle_path`)
+pub fn merkle_path(leaves: &[String], index: usize) -> Vec<String> {
+    HashAlgorithm::Sha256.merkle_path(leaves, index)
+}
+
+/// Check a SHA-256 authentication path (see `HashAlgorithm::verify_merkle_path`)
+pub fn verify_merkle_path(leaf: &str, index: usize, path: &[String], root: &str) -> bool {
+    HashAlgorithm::Sha256.verify_merkle_path(leaf, index, path, root)
+}
+
+/// Derive `count` distinct query positions in `0..domain_size` from a challenge.
+///
+/// The count is capped at the domain size, so every position is opened
+/// when more queries are requested than there are steps.
+pub fn query_indices(challenge: &str, count: usize, domain_size: usize) -> Vec<usize> {
+    let count = count.min(domain_size);
+    // `count` may come from an untrusted proof, so grow as positions are found
+    let mut indices = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    let mut counter = 0u64;
+    while indices.len() < count {
+        let digest = hash_string(&format!("{}{}", challenge, counter));
+        let value = u64::from_str_radix(&digest[..16], 16).expect("digest is hex");
+        let index = (value % domain_size as u64) as usize;
+        if seen.insert(index) {
+            indices.push(index);
+        }
+        counter += 1;
+    }
+    indices
+}
+
+/// Value the challenge is derived from: the trace commitment, followed by
+/// the step Merkle root when the proof opens queried steps, and the
+/// public inputs when the proof binds them
+pub fn challenge_seed(
+    trace_commitment: &str,
+    step_root: Option<&str>,
+    public_inputs: Option<&BTreeMap<String, u64>>,
+) -> String {
+    let mut seed = trace_commitment.to_string();
+    if let Some(root) = step_root {
+        seed.push_str(root);
+    }
+    if let Some(inputs) = public_inputs {
+        seed.push_str(&encode_public_inputs(inputs));
+    }
+    seed
+}
+
+/// Canonical encoding of public inputs for hashing: `name=value;` pairs
+/// in name order, e.g. `n=10;`
+pub fn encode_public_inputs(inputs: &BTreeMap<String, u64>) -> String {
+    inputs.iter().map(|(name, value)| format!("{}={};", name, value)).collect()
+}
+
+/// Default FRI folding factor (each layer halves the domain)
+pub const DEFAULT_FOLD_FACTOR: usize = 2;
+
+/// Number of FRI layers produced when folding `num_evaluations` values by
+/// `fold_factor` until a single value remains (one layer per domain size).
+pub fn fri_layer_count(num_evaluations: usize, fold_factor: usize) -> usize {
+    if num_evaluations == 0 || fold_factor < 2 {
+        return 0;
+    }
+    let mut len = num_evaluations;
+    let mut layers = 1;
+    while len > 1 {
+        len = len.div_ceil(fold_factor);
+        layers += 1;
+    }
+    layers
+}
+
+/// Lengths of the successive FRI layers over a domain of `domain_size`
+/// points: each layer is `fold_factor` times shorter (rounding up) until a
+/// single value remains. Empty when no folding is possible.
+pub fn fri_fold_schedule(domain_size: usize, fold_factor: usize) -> Vec<usize> {
+    let mut schedule = Vec::with_capacity(fri_layer_count(domain_size, fold_factor));
+    if domain_size == 0 || fold_factor < 2 {
+        return schedule;
+    }
+    let mut len = domain_size;
+    schedule.push(len);
+    while len > 1 {
+        len = len.div_ceil(fold_factor);
+        schedule.push(len);
+    }
+    schedule
+}
+
+/// SHA-256 hash of one FRI layer (see `HashAlgorithm::fri_layer_hash`)
+pub fn fri_layer_hash(leading: &[u64], challenge: &str, layer_idx: usize) -> String {
+    HashAlgorithm::Sha256.fri_layer_hash(leading, challenge, layer_idx)
+}
+
+/// One FRI round at `DEFAULT_FOLD_FACTOR`, hashed with SHA-256: the layer's
+/// hash and the next folded domain (see `HashAlgorithm::fold_layer`)
+pub fn fold_layer(evals: &[u64], challenge: &str, layer_idx: usize) -> (String, Vec<u64>) {
+    HashAlgorithm::Sha256.fold_layer(evals, challenge, layer_idx, DEFAULT_FOLD_FACTOR)
+}
+
+/// Fold evaluations in place, keeping every `fold_factor`-th value.
+///
+/// Equivalent to `iter().step_by(fold_factor).copied().collect()` but
+/// compacts the existing buffer instead of allocating a new one per layer.
+pub fn fold_in_place(evaluations: &mut Vec<u64>, fold_factor: usize) {
+    let folded_len = evaluations.len().div_ceil(fold_factor);
+    for i in 1..folded_len {
+        evaluations[i] = evaluations[i * fold_factor];
+    }
+    evaluations.truncate(folded_len);
+}
+
+/// FRI layers needed to fold a degree-`constraint_degree` constraint over
+/// `num_evaluations` points down to a constant
+pub fn fri_layers_for_degree(num_evaluations: usize, constraint_degree: usize, fold_factor: usize) -> usize {
+    // Saturate rather than overflow on an absurd degree read from a proof
+    fri_layer_count(num_evaluations.saturating_mul(constraint_degree.max(1)), fold_factor)
+}
+
+/// Security levels (in bits) accepted by the CLI
+pub const SUPPORTED_SECURITY_LEVELS: &[u32] = &[80, 96, 128, 192, 256];
+
+/// Check whether a security level is in the supported set
+pub fn is_supported_security_level(security_bits: u32) -> bool {
+    SUPPORTED_SECURITY_LEVELS.contains(&security_bits)
+}
+
+/// Number of hex characters in a challenge for the given security level.
+///
+/// Each hex character carries 4 bits, clamped between 16 characters and
+/// the full 64-character SHA-256 digest.
+pub fn challenge_hex_len(security_bits: u32) -> usize {
+    (security_bits as usize / 4).clamp(16, 64)
+}
+
+/// Source of Fiat-Shamir randomness used to derive challenges
+pub trait RandomOracle: Send + Sync {
+    /// Map an input string to a hex digest
+    fn query(&self, input: &str) -> String;
+}
+
+/// The default oracle: SHA-256
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sha256Oracle;
+
+impl RandomOracle for Sha256Oracle {
+    fn query(&self, input: &str) -> String {
+        hash_string(input)
+    }
+}
+
+/// An oracle returning a canned digest regardless of input, for tests
+/// that need to force a known challenge. Only built for tests, since a
+/// fixed challenge voids soundness.
+#[cfg(test)]
+#[derive(Debug, Clone)]
+pub struct FixedOracle {
+    output: String,
+}
+
+#[cfg(test)]
+impl FixedOracle {
+    /// Create an oracle that always answers with `output`
+    pub fn new(output: impl Into<String>) -> Self {
+        FixedOracle { output: output.into() }
+    }
+}
+
+#[cfg(test)]
+impl RandomOracle for FixedOracle {
+    fn query(&self, _input: &str) -> String {
+        self.output.clone()
+    }
 }
 
 /// Generate a challenge from commitment and security parameter
 pub fn generate_challenge(commitment: &str, security_bits: u32) -> String {
+    generate_challenge_with(&Sha256Oracle, commitment, security_bits)
+}
+
+/// Generate a challenge using the given random oracle
+pub fn generate_challenge_with(oracle: &dyn RandomOracle, commitment: &str, security_bits: u32) -> String {
+    generate_challenge_len_with(oracle, commitment, security_bits, challenge_hex_len(security_bits))
+        .expect("challenge_hex_len is always within bounds")
+}
+
+/// Shortest challenge accepted, in hex characters (32 bits)
+pub const MIN_CHALLENGE_HEX_LEN: usize = 8;
+
+/// Longest challenge possible: a full SHA-256 digest in hex
+pub const MAX_CHALLENGE_HEX_LEN: usize = 64;
+
+/// Generate a challenge truncated to an explicit number of hex characters.
+///
+/// Lengths below `MIN_CHALLENGE_HEX_LEN` are rejected as insecure and
+/// lengths beyond the digest are rejected as impossible.
+pub fn generate_challenge_len(commitment: &str, security_bits: u32, hex_len: usize) -> Result<String, ParameterError> {
+    generate_challenge_len_with(&Sha256Oracle, commitment, security_bits, hex_len)
+}
+
+/// Generate a challenge of explicit length using the given random oracle
+pub fn generate_challenge_len_with(
+    oracle: &dyn RandomOracle,
+    commitment: &str,
+    security_bits: u32,
+    hex_len: usize,
+) -> Result<String, ParameterError> {
+    if hex_len < MIN_CHALLENGE_HEX_LEN {
+        return Err(ParameterError::ChallengeTooShort { len: hex_len, min: MIN_CHALLENGE_HEX_LEN });
+    }
+    if hex_len > MAX_CHALLENGE_HEX_LEN {
+        return Err(ParameterError::ChallengeTooLong { len: hex_len, max: MAX_CHALLENGE_HEX_LEN });
+    }
     let challenge_input = format!("{}{}", commitment, security_bits);
-    let hash = hash_string(&challenge_input);
-    hash[..16].to_string()
+    let hash = oracle.query(&challenge_input);
+    Ok(truncate_hash(&hash, hex_len).to_string())
+}
+
+/// Challenge drawn by a simulated interactive verifier from a
+/// `ChaCha20Rng` seeded with `seed`, with the same length as the
+/// Fiat-Shamir challenge for `security_bits`.
+///
+/// It does not depend on the trace commitment at all: in an interactive
+/// protocol the verifier sends it only after receiving the commitment,
+/// but in a non-interactive proof whoever picks the seed picks the
+/// challenge. For demonstrations only.
+pub fn interactive_challenge(seed: u64, security_bits: u32) -> String {
+    let hex_len = challenge_hex_len(security_bits);
+    let mut bytes = vec![0u8; hex_len.div_ceil(2)];
+    ChaCha20Rng::seed_from_u64(seed).fill_bytes(&mut bytes);
+    truncate_hash(&hex::encode(bytes), hex_len).to_string()
+}
+
+/// Fiat-Shamir transcript: an ordered record of every value absorbed and
+/// every challenge squeezed out.
+///
+/// Squeezing queries the oracle on the concatenation of everything absorbed
+/// so far, which reproduces `generate_challenge_with` when the trace
+/// commitment (and step root) followed by the security level are absorbed.
+#[derive(Debug, Clone, Default)]
+pub struct Transcript {
+    state: String,
+    entries: Vec<(String, String)>,
+}
+
+impl Transcript {
+    /// Create an empty transcript
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rebuild the transcript the prover followed for `proof`.
+    ///
+    /// Proofs with an `interactive_seed` did not use Fiat-Shamir, so their
+    /// recorded challenge will not match the one squeezed here.
+    pub fn for_proof(proof: &crate::types::Proof, oracle: &dyn RandomOracle) -> Self {
+        let mut transcript = Transcript::new();
+        transcript.absorb("trace_commitment", &proof.trace_commitment);
+        if let Some(root) = &proof.step_root {
+            transcript.absorb("step_root", root);
+        }
+        if proof.inputs_bound {
+            transcript.absorb("public_inputs", &encode_public_inputs(&proof.public_inputs));
+        }
+        transcript.absorb("security_bits", &proof.security_bits.to_string());
+        transcript.squeeze("challenge", oracle, challenge_hex_len(proof.security_bits));
+        for (i, layer) in proof.fri_layers.iter().enumerate() {
+            transcript.absorb(&format!("fri_layer_{}", i), layer);
+        }
+        transcript
+    }
+
+    /// Feed a labelled value into the transcript
+    pub fn absorb(&mut self, label: &str, value: &str) {
+        self.state.push_str(value);
+        self.entries.push((label.to_string(), value.to_string()));
+    }
+
+    /// Derive a challenge of `hex_len` characters from everything absorbed so
+    /// far. The challenge is itself absorbed, so later squeezes differ.
+    pub fn squeeze(&mut self, label: &str, oracle: &dyn RandomOracle, hex_len: usize) -> String {
+        let challenge = truncate_hash(&oracle.query(&self.state), hex_len).to_string();
+        self.absorb(label, &challenge);
+        challenge
+    }
+
+    /// Every absorbed and squeezed `(label, value)` pair, in order
+    pub fn export(&self) -> Vec<(String, String)> {
+        self.entries.clone()
+    }
 }
 
 /// Verify challenge consistency
 pub fn verify_challenge(commitment: &str, security_bits: u32, provided_challenge: &str) -> bool {
-    let expected_challenge = generate_challenge(commitment, security_bits);
+    verify_challenge_with(&Sha256Oracle, commitment, security_bits, provided_challenge)
+}
+
+/// Verify challenge consistency using the given random oracle
+pub fn verify_challenge_with(
+    oracle: &dyn RandomOracle,
+    commitment: &str,
+    security_bits: u32,
+    provided_challenge: &str,
+) -> bool {
+    let expected_challenge = generate_challenge_with(oracle, commitment, security_bits);
     expected_challenge == provided_challenge
 }
 
@@ -41,17 +613,196 @@ pub fn verify_challenge(commitment: &str, security_bits: u32, provided_challenge
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_interactive_challenge_is_reproducible() {
+        let challenge = interactive_challenge(42, 128);
+        assert_eq!(challenge, interactive_challenge(42, 128));
+        assert_ne!(challenge, interactive_challenge(43, 128));
+        assert_eq!(challenge.len(), challenge_hex_len(128));
+
+        let mut rng = ChaCha20Rng::seed_from_u64(42);
+        let first = rng.next_u64();
+        assert_ne!(first, rng.next_u64());
+        assert_eq!(ChaCha20Rng::seed_from_u64(42).next_u64(), first);
+    }
+
+    #[test]
+    fn test_fri_fold_schedule() {
+        assert_eq!(fri_fold_schedule(21, 2), [21, 11, 6, 3, 2, 1]);
+        assert_eq!(fri_fold_schedule(9, 3), [9, 3, 1]);
+        assert_eq!(fri_fold_schedule(1, 2), [1]);
+        assert!(fri_fold_schedule(0, 2).is_empty());
+        assert!(fri_fold_schedule(8, 1).is_empty());
+        assert_eq!(fri_fold_schedule(21, 2).len(), fri_layer_count(21, 2));
+    }
+
     #[test]
     fn test_hash_string() {
         let hash = hash_string("test");
         assert_eq!(hash.len(), 64); // SHA-256 produces 64 hex characters
     }
 
+    #[test]
+    fn test_hash_integers_byte_order() {
+        let values = [1u64, 2, 0x0102030405060708];
+        let le = hash_integers_with_order(&values, ByteOrder::LittleEndian);
+        let be = hash_integers_with_order(&values, ByteOrder::BigEndian);
+
+        assert_ne!(le, be);
+        assert_eq!(le, hash_integers(&values));
+        assert_eq!(be, HashAlgorithm::Sha256BigEndian.hash_integers(&values));
+        // Stable encodings: a single 1 is 01 00.. (LE) versus 00..01 (BE)
+        assert_eq!(hash_integers_with_order(&[1], ByteOrder::LittleEndian), hash_bytes(&[1, 0, 0, 0, 0, 0, 0, 0]));
+        assert_eq!(hash_integers_with_order(&[1], ByteOrder::BigEndian), hash_bytes(&[0, 0, 0, 0, 0, 0, 0, 1]));
+    }
+
+    #[test]
+    fn test_hash_algorithm_names() {
+        for &algorithm in HashAlgorithm::ALL {
+            assert_eq!(HashAlgorithm::from_name(algorithm.name()), Some(algorithm));
+        }
+        assert_eq!(HashAlgorithm::from_name("md5"), None);
+    }
+
+    #[test]
+    fn test_hash_algorithm_digests() {
+        assert_eq!(
+            HashAlgorithm::Blake3.hash_string(""),
+            "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"
+        );
+        assert_eq!(
+            HashAlgorithm::Keccak256.hash_string(""),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+        assert_eq!(HashAlgorithm::Sha256.hash_string("abc"), hash_string("abc"));
+    }
+
     #[test]
     fn test_generate_challenge() {
         let commitment = "abc123";
-        let challenge = generate_challenge(commitment, 128);
-        assert_eq!(challenge.len(), 16);
+        assert_eq!(generate_challenge(commitment, 64).len(), 16);
+        assert_eq!(generate_challenge(commitment, 80).len(), 20);
+        assert_eq!(generate_challenge(commitment, 128).len(), 32);
+        assert_eq!(generate_challenge(commitment, 256).len(), 64);
+    }
+
+    #[test]
+    fn test_fixed_oracle_forces_challenge() {
+        let oracle = FixedOracle::new("00112233445566778899aabbccddeeff00112233445566778899aabbccddeeff");
+        let challenge = generate_challenge_with(&oracle, "anything", 128);
+        assert_eq!(challenge, "00112233445566778899aabbccddeeff");
+        assert!(verify_challenge_with(&oracle, "something else", 128, &challenge));
+        assert!(!verify_challenge("anything", 128, &challenge));
+    }
+
+    #[test]
+    fn test_hash_chain_detects_reordering() {
+        let step = |i: usize| TraceStep {
+            step: 0,
+            operation: "op".to_string(),
+            input: i as u64,
+            output: i as u64,
+            depth: 0,
+        };
+        let steps = vec![step(1), step(2), step(3)];
+        let mut swapped = steps.clone();
+        swapped.swap(0, 1);
+
+        let chain = hash_chain(&steps);
+        assert_eq!(chain.len(), 3);
+        assert_ne!(chain.last(), hash_chain(&swapped).last());
+        assert_eq!(chain, hash_chain(&steps));
+    }
+
+    #[test]
+    fn test_merkle_paths() {
+        let leaves: Vec<String> = (0..5).map(|i| hash_string(&i.to_string())).collect();
+        let root = merkle_root(&leaves);
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let path = merkle_path(&leaves, index);
+            assert_eq!(path.len(), 3);
+            assert!(verify_merkle_path(leaf, index, &path, &root));
+            assert!(!verify_merkle_path(leaf, (index + 1) % 5, &path, &root));
+        }
+        assert!(merkle_path(&leaves[..1], 0).is_empty());
+        assert_eq!(merkle_root(&leaves[..1]), leaves[0]);
+    }
+
+    #[test]
+    fn test_query_indices() {
+        let indices = query_indices("abcdef", 4, 10);
+        assert_eq!(indices.len(), 4);
+        assert!(indices.iter().all(|&i| i < 10));
+        assert_eq!(indices, query_indices("abcdef", 4, 10));
+
+        let mut all = query_indices("abcdef", 20, 3);
+        all.sort();
+        assert_eq!(all, vec![0, 1, 2]);
+        assert!(query_indices("abcdef", 3, 0).is_empty());
+    }
+
+    #[test]
+    fn test_fri_layer_count() {
+        assert_eq!(fri_layer_count(0, 2), 0);
+        assert_eq!(fri_layer_count(1, 2), 1);
+        assert_eq!(fri_layer_count(19, 2), 6); // 19, 10, 5, 3, 2, 1
+        assert_eq!(fri_layer_count(16, 4), 3); // 16, 4, 1
+        assert_eq!(fri_layer_count(16, 1), 0);
+    }
+
+    #[test]
+    fn test_fold_in_place_matches_step_by() {
+        for len in [0, 1, 2, 7, 16, 19] {
+            for fold_factor in [2, 3, 4] {
+                let values: Vec<u64> = (0..len).map(|v| v * 10 + 1).collect();
+                let expected: Vec<u64> = values.iter().step_by(fold_factor).copied().collect();
+                let mut folded = values.clone();
+                fold_in_place(&mut folded, fold_factor);
+                assert_eq!(folded, expected, "len {} fold {}", len, fold_factor);
+            }
+        }
+    }
+
+    #[test]
+    fn test_supported_security_levels() {
+        assert!(is_supported_security_level(128));
+        assert!(!is_supported_security_level(100));
+    }
+
+    #[test]
+    fn test_generate_challenge_len() {
+        let commitment = "abc123";
+        let full = generate_challenge_len(commitment, 128, 64).unwrap();
+        let short = generate_challenge_len(commitment, 128, 8).unwrap();
+
+        assert_eq!(short, full[..8]);
+        assert_eq!(generate_challenge_len(commitment, 128, 32).unwrap(), generate_challenge(commitment, 128));
+        assert_eq!(
+            generate_challenge_len(commitment, 128, 7),
+            Err(ParameterError::ChallengeTooShort { len: 7, min: 8 })
+        );
+        assert_eq!(
+            generate_challenge_len(commitment, 128, 65),
+            Err(ParameterError::ChallengeTooLong { len: 65, max: 64 })
+        );
+    }
+
+    #[test]
+    fn test_transcript_export_matches_proof() {
+        use crate::computation::fibonacci_with_trace;
+        use crate::prover::STARKProver;
+
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let proof = STARKProver::new(128).prove("fibonacci", result, &trace).unwrap();
+        let export = Transcript::for_proof(&proof, &Sha256Oracle).export();
+
+        assert_eq!(export[0], ("trace_commitment".to_string(), proof.trace_commitment.clone()));
+        assert_eq!(export[1], ("public_inputs".to_string(), "n=10;".to_string()));
+        assert_eq!(export[3], ("challenge".to_string(), proof.challenge.clone()));
+        let layers: Vec<&String> = export[4..].iter().map(|(_, value)| value).collect();
+        assert_eq!(layers, proof.fri_layers.iter().collect::<Vec<_>>());
+        assert_eq!(export[4].0, "fri_layer_0");
     }
 
     #[test]
@@ -0,0 +1,157 @@
+// src/error.rs - Error types
+use crate::types::VerificationResult;
+use thiserror::Error;
+
+/// Errors raised while running a computation or proving it
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ComputationError {
+    /// The trace produced no constraint evaluations, so no FRI layers can be built
+    #[error("no constraint evaluations to prove")]
+    NoConstraints,
+
+    /// An input exceeds the computation's supported maximum
+    #[error("input too large (maximum is {max})")]
+    InputTooLarge { max: u64 },
+
+    /// An intermediate value overflowed u64 while computing the given index
+    #[error("arithmetic overflow at index {index}")]
+    Overflow { index: u64 },
+
+    /// External input could not be read (the message of the underlying IO error)
+    #[error("could not read input: {0}")]
+    Io(String),
+
+    /// The computation noticed a cancellation request and stopped early
+    #[error("computation cancelled")]
+    Cancelled,
+
+    /// The computation did not finish within its time budget
+    #[error("computation exceeded the {limit_ms} ms timeout")]
+    Timeout { limit_ms: u64 },
+
+    /// A recursive computation went deeper than its maximum recursion depth
+    #[error("recursion exceeded the maximum depth of {max}")]
+    RecursionTooDeep { max: usize },
+
+    /// A computation set the same trace input twice
+    #[error("duplicate trace input {key:?}")]
+    DuplicateInput { key: String },
+
+    /// Merging traces would set the same trace output twice
+    #[error("duplicate trace output {key:?}")]
+    DuplicateOutput { key: String },
+
+    /// A computation cannot be re-executed from the given trace inputs
+    /// (unknown computation, or inputs missing or out of range)
+    #[error("cannot replay {computation} from its trace inputs")]
+    NotReplayable { computation: String },
+
+    /// A line of external input is not a valid u64
+    #[error("line {line} is not a u64: {content:?}")]
+    InvalidInput { line: usize, content: String },
+
+    /// A trace has neither a `result` output nor any steps, so there is
+    /// no result to claim
+    #[error("trace records no result (no `result` output and no steps)")]
+    MissingResult,
+}
+
+/// Errors raised while loading a proof (or a trace) from JSON
+#[derive(Debug, Error)]
+pub enum LoadError {
+    /// The file or stream could not be read
+    #[error("could not read input: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The path names a directory rather than a proof file
+    #[error("expected a file, found a directory: {path}")]
+    IsDirectory { path: String },
+
+    /// The input contained no JSON at all
+    #[error("input is empty")]
+    Empty,
+
+    /// The JSON could not be parsed as a proof (or trace)
+    #[error("malformed JSON: {0}")]
+    Malformed(#[from] serde_json::Error),
+
+    /// A proof file, stream or download exceeded the size accepted for
+    /// untrusted proofs; reading stopped just past the limit
+    #[error("proof too large: more than the limit of {max} bytes")]
+    TooLarge { max: usize },
+
+    /// A complete proof was followed by more content (e.g. a second object)
+    #[error("unexpected trailing data at byte {offset}")]
+    TrailingData { offset: usize },
+
+    /// A proof could not be downloaded (connection failure or HTTP error)
+    #[error("network error: {0}")]
+    Network(String),
+
+    /// A URL was given to a build without the `network` feature
+    #[error("cannot download {url}: built without the `network` feature")]
+    NetworkDisabled { url: String },
+}
+
+impl LoadError {
+    /// True for failures that may clear up on their own, such as a file
+    /// that another process has not finished creating yet. Malformed
+    /// content is never transient.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            LoadError::Io(e) => matches!(
+                e.kind(),
+                std::io::ErrorKind::NotFound
+                    | std::io::ErrorKind::Interrupted
+                    | std::io::ErrorKind::WouldBlock
+                    | std::io::ErrorKind::TimedOut
+            ),
+            LoadError::Empty | LoadError::Network(_) => true,
+            LoadError::IsDirectory { .. }
+            | LoadError::Malformed(_)
+            | LoadError::TooLarge { .. }
+            | LoadError::TrailingData { .. }
+            | LoadError::NetworkDisabled { .. } => false,
+        }
+    }
+}
+
+/// Errors raised for insecure or impossible protocol parameters
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParameterError {
+    /// The requested challenge is too short to be secure
+    #[error("challenge length {len} is below the minimum of {min} hex characters")]
+    ChallengeTooShort { len: usize, min: usize },
+
+    /// The requested challenge is longer than the hash digest
+    #[error("challenge length {len} exceeds the digest length of {max} hex characters")]
+    ChallengeTooLong { len: usize, max: usize },
+
+    /// A file naming template is malformed or could produce clashing names
+    #[error("invalid name template {template:?}: {reason}")]
+    InvalidTemplate { template: String, reason: String },
+}
+
+/// Any error the library can produce, for callers that just want `?`
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Computation(#[from] ComputationError),
+
+    #[error(transparent)]
+    Load(#[from] LoadError),
+
+    #[error(transparent)]
+    Parameter(#[from] ParameterError),
+
+    /// A proof was produced or loaded but did not verify (boxed, since the
+    /// report is far larger than every other variant)
+    #[error("proof failed verification: {}", .0.checks_failed.join("; "))]
+    Verification(Box<VerificationResult>),
+}
+
+impl From<VerificationResult> for Error {
+    fn from(result: VerificationResult) -> Self {
+        Error::Verification(Box::new(result))
+    }
+}
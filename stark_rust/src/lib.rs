@@ -1,10 +1,21 @@
 // src/lib.rs - Library root file
 pub mod prover;
 pub mod verifier;
+pub mod checks;
 pub mod types;
 pub mod crypto;
 pub mod computation;
+pub mod error;
+pub mod util;
+#[cfg(feature = "network")]
+pub mod net;
 
-pub use prover::STARKProver;
+pub use prover::{CancellationToken, Format, HashChainMode, ProvePhase, ProveProgress, STARKProver};
 pub use verifier::STARKVerifier;
-pub use types::{Proof, ProofTrace, VerificationResult};
+pub use checks::{CheckOutcome, VerificationCheck};
+pub use computation::{Computation, ComputationKind};
+pub use types::{
+    Claim, EvaluationOpening, Lint, PaddingMode, Proof, ProofArchive, ProofBuilder, ProofStats, ProofTrace, ReportVerbosity,
+    ResultValue, StepOpening, TraceBundle, TraceDiff, TraceLengthHistogram, VerificationDiff, VerificationResult,
+};
+pub use error::{ComputationError, Error, LoadError, ParameterError};
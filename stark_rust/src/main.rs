@@ -1,7 +1,11 @@
 // src/main.rs - Command-line interface
-use clap::{Parser, Subcommand};
-use stark_prover_verifier::{STARKProver, STARKVerifier, computation};
-use std::path::PathBuf;
+use clap::{Parser, Subcommand, ValueEnum};
+use stark_prover_verifier::{CancellationToken, ComputationError, Error, LoadError, Proof, ProofArchive, ProofStats, ProofTrace, ReportVerbosity, ResultValue, STARKProver, STARKVerifier, TraceBundle, VerificationResult, computation, crypto};
+use stark_prover_verifier::types::write_junit_report;
+use stark_prover_verifier::verifier::verify_same_trace;
+use stark_prover_verifier::util::{truncate_hash, ProofNameTemplate};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 #[derive(Parser)]
 #[command(name = "STARK Prover & Verifier")]
@@ -17,23 +21,287 @@ enum Commands {
     /// Run a complete demonstration
     Demo,
 
-    /// Generate a STARK proof for fibonacci(n)
+    /// Generate a STARK proof for fibonacci(n) or another computation
     Prove {
-        /// The fibonacci index
-        #[arg(value_name = "N")]
-        n: u64,
+        /// The fibonacci (or nth-prime) index, or the hash-computation input
+        #[arg(value_name = "N", required_unless_present = "input")]
+        n: Option<u64>,
+
+        /// Computation to prove
+        #[arg(long, value_enum, default_value_t = ProveComputation::Fibonacci)]
+        computation: ProveComputation,
+
+        /// Input file for `sum-file` (newline-separated u64 values)
+        #[arg(long, value_name = "FILE")]
+        input: Option<PathBuf>,
 
         /// Output file (optional)
         #[arg(short, long)]
         output: Option<PathBuf>,
+
+        /// Also save the trace bundle (trace + proof) to this file
+        #[arg(long)]
+        bundle: Option<PathBuf>,
+
+        /// Security level in bits
+        #[arg(long, default_value_t = 128, value_parser = parse_security)]
+        security: u32,
+
+        /// DEMONSTRATION ONLY: claim this result instead of the computed one,
+        /// producing a proof that must fail verification
+        #[arg(long, hide = true, value_name = "VALUE")]
+        force_result: Option<u64>,
+
+        /// Give up if the computation takes longer than this many seconds
+        #[arg(long, value_name = "SECONDS")]
+        timeout: Option<u64>,
+
+        /// Write compact single-line JSON instead of indented JSON
+        #[arg(long)]
+        compact: bool,
+    },
+
+    /// Generate a STARK proof for a trace JSON file produced elsewhere
+    ProveTrace {
+        /// Trace JSON file, or `-` to read it from stdin
+        #[arg(value_name = "FILE")]
+        trace_file: String,
+
+        /// Name of the computation the trace belongs to
+        #[arg(long)]
+        computation: String,
+
+        /// Output file (defaults to <computation>_proof.json)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Security level in bits
+        #[arg(long, default_value_t = 128, value_parser = parse_security)]
+        security: u32,
+
+        /// Write compact single-line JSON instead of indented JSON
+        #[arg(long)]
+        compact: bool,
     },
 
     /// Verify a STARK proof from a JSON file
     Verify {
+        /// Path to the proof JSON file, or an http:// URL to download it from
+        #[arg(value_name = "FILE")]
+        proof_file: PathBuf,
+
+        /// Minimum security level in bits
+        #[arg(long, default_value_t = 128, value_parser = parse_security)]
+        security: u32,
+
+        /// Print nothing; report validity only through the exit status
+        #[arg(short, long, conflicts_with = "verbose")]
+        quiet: bool,
+
+        /// Print the full report with every check
+        #[arg(short, long)]
+        verbose: bool,
+    },
+
+    /// Prove fibonacci(n) for several n and write all proofs to one archive
+    ProveBatch {
+        /// The fibonacci indices
+        #[arg(value_name = "N", required = true)]
+        ns: Vec<u64>,
+
+        /// Archive file
+        #[arg(short, long, default_value = "proofs_archive.json")]
+        output: PathBuf,
+
+        /// Write one proof file per index into this directory instead of an archive
+        #[arg(long, value_name = "DIR")]
+        output_dir: Option<PathBuf>,
+
+        /// File name for each proof in --output-dir; placeholders are {computation}, {n} and {security}
+        #[arg(long, default_value = "{computation}_{n}_{security}.json", requires = "output_dir")]
+        name_template: String,
+
+        /// Security level in bits
+        #[arg(long, default_value_t = 128, value_parser = parse_security)]
+        security: u32,
+
+        /// Write the archive, or each proof in --output-dir, as compact single-line JSON
+        #[arg(long)]
+        compact: bool,
+    },
+
+    /// Verify every proof in an archive written by `prove-batch`
+    VerifyBatchFile {
+        /// Path to the archive JSON file
+        #[arg(value_name = "FILE")]
+        archive_file: PathBuf,
+
+        /// Minimum security level in bits
+        #[arg(long, default_value_t = 128, value_parser = parse_security)]
+        security: u32,
+
+        /// Also write a JUnit XML report, one test case per proof
+        #[arg(long, value_name = "FILE")]
+        junit: Option<PathBuf>,
+    },
+
+    /// Check that several proofs commit to the same trace
+    VerifyConsistent {
+        /// Proof JSON files
+        #[arg(value_name = "FILE", required = true)]
+        proof_files: Vec<PathBuf>,
+    },
+
+    /// Warn about weak parameters in a proof (advisory, never fails)
+    Lint {
+        /// Path to the proof JSON file
+        #[arg(value_name = "FILE")]
+        proof_file: PathBuf,
+    },
+
+    /// Print the Fiat-Shamir transcript reconstructed from a proof
+    Transcript {
         /// Path to the proof JSON file
         #[arg(value_name = "FILE")]
         proof_file: PathBuf,
     },
+
+    /// Print one field of a proof, selected by a JSON Pointer
+    Query {
+        /// Path to the proof JSON file
+        #[arg(value_name = "FILE")]
+        proof_file: PathBuf,
+
+        /// JSON Pointer such as /fri_layers/0 or /public_inputs/n
+        #[arg(value_name = "POINTER")]
+        pointer: String,
+    },
+
+    /// Summarize every proof JSON file in a directory
+    Stats {
+        /// Directory holding the proof files
+        #[arg(value_name = "DIR")]
+        dir: PathBuf,
+    },
+
+    /// Print a histogram of trace lengths across a proof archive
+    Histogram {
+        /// Path to the archive JSON file
+        #[arg(value_name = "FILE")]
+        archive_file: PathBuf,
+    },
+
+    /// List the computations that can be proved
+    ListComputations,
+
+    /// Time committing and proving fibonacci(N) under every hash algorithm
+    BenchHash {
+        /// The fibonacci index
+        #[arg(value_name = "N")]
+        n: u64,
+
+        /// Proofs generated per algorithm
+        #[arg(long, default_value_t = 20)]
+        iterations: u32,
+    },
+
+    /// Re-execute a bundle's computation from its trace inputs and check
+    /// that every step matches the stored trace
+    Replay {
+        /// Trace bundle written by `prove --bundle`
+        #[arg(value_name = "BUNDLE")]
+        bundle: PathBuf,
+    },
+
+    /// Regenerate a current-format proof from a bundle's stored trace
+    Reprove {
+        /// Trace bundle written by `prove --bundle`
+        #[arg(value_name = "BUNDLE")]
+        bundle: PathBuf,
+
+        /// Output file for the proof (default: <computation>_proof.json)
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+
+    /// Compare the traces stored in two bundle files
+    TraceDiff {
+        /// First trace bundle
+        #[arg(value_name = "A")]
+        a: PathBuf,
+
+        /// Second trace bundle
+        #[arg(value_name = "B")]
+        b: PathBuf,
+    },
+}
+
+/// Parse a `--security` value, accepting only supported levels
+fn parse_security(value: &str) -> Result<u32, String> {
+    let bits: u32 = value.parse().map_err(|_| format!("'{}' is not a number", value))?;
+    if crypto::is_supported_security_level(bits) {
+        Ok(bits)
+    } else {
+        Err(format!(
+            "unsupported security level {} (supported: {:?})",
+            bits,
+            crypto::SUPPORTED_SECURITY_LEVELS
+        ))
+    }
+}
+
+/// Computations selectable with `prove --computation`
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ProveComputation {
+    /// fibonacci(N)
+    Fibonacci,
+    /// Sum of the values in the `--input` file
+    SumFile,
+    /// The N-th prime
+    NthPrime,
+    /// SHA-256 applied five times to N
+    HashComputation,
+}
+
+/// Exit status for a proof that loaded but failed verification, and for
+/// any failure not covered below
+const EXIT_INVALID: i32 = 1;
+/// Exit status when a file could not be read or written
+const EXIT_IO: i32 = 2;
+/// Exit status when a proof or trace is not valid JSON of the right shape
+const EXIT_MALFORMED: i32 = 3;
+/// Exit status for a proof whose version or hash algorithm this build
+/// does not support, or a URL given to a build without `network`
+const EXIT_UNSUPPORTED: i32 = 4;
+
+/// Exit status for a proof or trace that failed to load
+fn load_error_exit_code(error: &LoadError) -> i32 {
+    match error {
+        LoadError::Io(_) | LoadError::IsDirectory { .. } | LoadError::Network(_) => EXIT_IO,
+        LoadError::Empty
+        | LoadError::Malformed(_)
+        | LoadError::TooLarge { .. }
+        | LoadError::TrailingData { .. } => EXIT_MALFORMED,
+        LoadError::NetworkDisabled { .. } => EXIT_UNSUPPORTED,
+    }
+}
+
+/// Exit status for a computation that could not be run
+fn computation_error_exit_code(error: &ComputationError) -> i32 {
+    match error {
+        ComputationError::Io(_) => EXIT_IO,
+        ComputationError::InvalidInput { .. } | ComputationError::MissingResult => EXIT_MALFORMED,
+        _ => EXIT_INVALID,
+    }
+}
+
+/// Exit status for a library error
+fn error_exit_code(error: &Error) -> i32 {
+    match error {
+        Error::Load(e) => load_error_exit_code(e),
+        Error::Computation(e) => computation_error_exit_code(e),
+        _ => EXIT_INVALID,
+    }
 }
 
 fn main() {
@@ -41,13 +309,76 @@ fn main() {
 
     match cli.command {
         Commands::Demo => run_demo(),
-        Commands::Prove { n, output } => prove_fibonacci(n, output),
-        Commands::Verify { proof_file } => verify_proof(proof_file),
+        Commands::Prove { n, computation, input, output, bundle, security, force_result, timeout, compact } => {
+            let job = match (computation, n, input) {
+                (ProveComputation::Fibonacci, Some(n), _) => ProveJob::Fibonacci(n),
+                (ProveComputation::SumFile, _, Some(input)) => ProveJob::SumFile(input),
+                (ProveComputation::NthPrime, Some(n), _) => ProveJob::NthPrime(n),
+                (ProveComputation::HashComputation, Some(n), _) => ProveJob::HashComputation(n),
+                (ProveComputation::Fibonacci | ProveComputation::NthPrime | ProveComputation::HashComputation, None, _) => {
+                    eprintln!("Error: this computation requires N");
+                    std::process::exit(EXIT_INVALID);
+                }
+                (ProveComputation::SumFile, _, None) => {
+                    eprintln!("Error: sum-file requires --input FILE");
+                    std::process::exit(EXIT_INVALID);
+                }
+            };
+            prove_computation(job, output, bundle, security, force_result, timeout.map(Duration::from_secs), compact)
+        }
+        Commands::ProveTrace { trace_file, computation, output, security, compact } => {
+            prove_trace(trace_file, computation, output, security, compact)
+        }
+        Commands::Verify { proof_file, security, quiet, verbose } => {
+            let verbosity = if quiet {
+                ReportVerbosity::Silent
+            } else if verbose {
+                ReportVerbosity::Full
+            } else {
+                ReportVerbosity::Summary
+            };
+            verify_proof(proof_file, security, verbosity)
+        }
+        Commands::ProveBatch { ns, output, output_dir, name_template, security, compact } => {
+            prove_batch(ns, output, output_dir, name_template, security, compact)
+        }
+        Commands::VerifyBatchFile { archive_file, security, junit } => verify_batch_file(archive_file, security, junit),
+        Commands::VerifyConsistent { proof_files } => verify_consistent(proof_files),
+        Commands::Lint { proof_file } => lint_proof(proof_file),
+        Commands::Transcript { proof_file } => print_transcript(proof_file),
+        Commands::Query { proof_file, pointer } => query_proof(proof_file, pointer),
+        Commands::Stats { dir } => print_stats(dir),
+        Commands::Histogram { archive_file } => print_histogram(archive_file),
+        Commands::ListComputations => list_computations(),
+        Commands::BenchHash { n, iterations } => bench_hash(n, iterations),
+        Commands::Replay { bundle } => replay_bundle(bundle),
+        Commands::Reprove { bundle, output } => reprove_bundle(bundle, output),
+        Commands::TraceDiff { a, b } => trace_diff(a, b),
     }
 }
 
+/// Cancel the returned token on the first Ctrl-C; a second Ctrl-C exits
+/// immediately.
+fn install_interrupt_handler() -> CancellationToken {
+    let cancel = CancellationToken::new();
+    let handler_token = cancel.clone();
+    let installed = ctrlc::set_handler(move || {
+        if handler_token.is_cancelled() {
+            std::process::exit(130);
+        }
+        eprintln!("\nInterrupt received, stopping after the current proof...");
+        handler_token.cancel();
+    });
+    if let Err(e) = installed {
+        eprintln!("Warning: could not install Ctrl-C handler: {}", e);
+    }
+    cancel
+}
+
 /// Run a complete demonstration
 fn run_demo() {
+    let cancel = install_interrupt_handler();
+
     println!("\n{}", "=".repeat(60));
     println!("STARK PROVER & VERIFIER - DEMONSTRATION (RUST)");
     println!("{}", "=".repeat(60));
@@ -57,14 +388,16 @@ fn run_demo() {
     println!("{}", "-".repeat(60));
 
     let prover = STARKProver::new(128);
-    let (result, trace) = computation::fibonacci_with_trace(10);
+    let (result, trace) = computation::fibonacci_with_trace(10).expect("fibonacci(10) fits in a u64");
 
     println!("Computing fibonacci(10)...");
     println!("✓ Result: fibonacci(10) = {}", result);
     println!("✓ Trace steps: {}", trace.steps.len());
 
     println!("\nGenerating STARK proof...");
-    let proof = prover.prove("fibonacci", result, &trace);
+    let proof = prover
+        .prove("fibonacci", result, &trace)
+        .expect("fibonacci trace always has constraints");
     println!("✓ STARK proof generated");
 
     println!(
@@ -73,7 +406,7 @@ fn run_demo() {
         proof.computation,
         proof.result,
         proof.security_bits,
-        &proof.trace_commitment[..16],
+        truncate_hash(&proof.trace_commitment, 16),
         proof.challenge,
         proof.fri_layers.len()
     );
@@ -93,9 +426,16 @@ fn run_demo() {
     println!("{}", "-".repeat(60));
 
     let test_cases = vec![5, 8, 15];
-    for n in test_cases {
-        let (result, trace) = computation::fibonacci_with_trace(n);
-        let proof = prover.prove("fibonacci", result, &trace);
+    let total = test_cases.len();
+    for (completed, n) in test_cases.into_iter().enumerate() {
+        if cancel.is_cancelled() {
+            println!("\nInterrupted: {} of {} computations completed", completed, total);
+            std::process::exit(130);
+        }
+        let (result, trace) = computation::fibonacci_with_trace(n).expect("small inputs fit in a u64");
+        let proof = prover
+            .prove("fibonacci", result, &trace)
+            .expect("fibonacci trace always has constraints");
         let verification = verifier.verify(&proof);
 
         let status = if verification.valid { "✓ VALID" } else { "✗ INVALID" };
@@ -108,76 +448,616 @@ fn run_demo() {
     println!();
 }
 
-/// Generate a proof for fibonacci(n)
-fn prove_fibonacci(n: u64, output: Option<PathBuf>) {
+/// A fully specified `prove` invocation
+enum ProveJob {
+    Fibonacci(u64),
+    SumFile(PathBuf),
+    NthPrime(u64),
+    HashComputation(u64),
+}
+
+/// Wrap a numeric computation's result for `ComputeFn`
+fn numeric((result, trace): (u64, ProofTrace)) -> (ResultValue, ProofTrace) {
+    (ResultValue::U64(result), trace)
+}
+
+/// A computation runnable on a worker thread by `run_with_timeout`
+type ComputeFn = Box<dyn FnOnce(&CancellationToken) -> Result<(ResultValue, ProofTrace), ComputationError> + Send>;
+
+impl ProveJob {
+    /// Name recorded in the proof
+    fn name(&self) -> &'static str {
+        match self {
+            ProveJob::Fibonacci(_) => computation::FIBONACCI.name,
+            ProveJob::SumFile(_) => computation::SUM_FILE.name,
+            ProveJob::NthPrime(_) => computation::NTH_PRIME.name,
+            ProveJob::HashComputation(_) => computation::HASH_COMPUTATION.name,
+        }
+    }
+
+    /// The computation to run for this job
+    fn compute_fn(&self) -> ComputeFn {
+        match self {
+            ProveJob::Fibonacci(n) => {
+                let n = *n;
+                Box::new(move |_: &CancellationToken| computation::fibonacci_with_trace(n).map(numeric))
+            }
+            ProveJob::SumFile(input) => {
+                let input = input.clone();
+                Box::new(move |_: &CancellationToken| computation::sum_file_with_trace(input).map(numeric))
+            }
+            ProveJob::NthPrime(n) => {
+                let n = *n;
+                Box::new(move |cancel: &CancellationToken| computation::nth_prime_with_trace(n, cancel).map(numeric))
+            }
+            ProveJob::HashComputation(input) => {
+                let input = *input;
+                Box::new(move |_: &CancellationToken| {
+                    let (digest, trace) = computation::hash_computation_with_trace(input);
+                    Ok((ResultValue::Digest(digest), trace))
+                })
+            }
+        }
+    }
+
+    /// Output file used when `--output` is not given
+    fn default_output(&self) -> PathBuf {
+        match self {
+            ProveJob::Fibonacci(n) => PathBuf::from(format!("proof_fib_{}.json", n)),
+            ProveJob::SumFile(_) => PathBuf::from("proof_sum_file.json"),
+            ProveJob::NthPrime(n) => PathBuf::from(format!("proof_prime_{}.json", n)),
+            ProveJob::HashComputation(input) => PathBuf::from(format!("proof_hash_{}.json", input)),
+        }
+    }
+}
+
+/// Generate a proof for the requested computation
+fn prove_computation(
+    job: ProveJob,
+    output: Option<PathBuf>,
+    bundle: Option<PathBuf>,
+    security: u32,
+    force_result: Option<u64>,
+    timeout: Option<Duration>,
+    compact: bool,
+) {
     println!("\n{}", "=".repeat(60));
     println!("STARK PROOF GENERATION (RUST)");
     println!("{}", "=".repeat(60));
     println!();
 
-    if n > 100 {
-        eprintln!("Error: n must be <= 100 for performance reasons");
-        std::process::exit(1);
+    let prover = STARKProver::new(security).with_pretty(!compact);
+    match &job {
+        ProveJob::Fibonacci(n) => {
+            let max_n = computation::FIBONACCI.max_input;
+            if *n > max_n {
+                eprintln!("Error: n must be <= {} for performance reasons", max_n);
+                std::process::exit(EXIT_INVALID);
+            }
+            println!("Computing Fibonacci({})...", n);
+        }
+        ProveJob::SumFile(input) => println!("Summing values from {}...", input.display()),
+        ProveJob::NthPrime(n) => println!("Searching for prime #{}...", n),
+        ProveJob::HashComputation(input) => println!("Hashing {} ({} rounds)...", input, computation::HASH_ROUNDS),
     }
+    let computed = match timeout {
+        Some(timeout) => computation::run_with_timeout(job.compute_fn(), timeout),
+        None => job.compute_fn()(&CancellationToken::new()),
+    };
+    let (result, trace) = match computed {
+        Ok(computed) => computed,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(computation_error_exit_code(&e));
+        }
+    };
 
-    println!("Computing Fibonacci({})...", n);
-    let prover = STARKProver::new(128);
-    let (result, trace) = computation::fibonacci_with_trace(n);
-
-    println!("✓ Computation completed: fibonacci({}) = {}", n, result);
+    match &job {
+        ProveJob::Fibonacci(n) => println!("✓ Computation completed: fibonacci({}) = {}", n, result),
+        ProveJob::SumFile(_) => println!("✓ Computation completed: sum = {}", result),
+        ProveJob::NthPrime(n) => println!("✓ Computation completed: prime #{} = {}", n, result),
+        ProveJob::HashComputation(input) => println!("✓ Computation completed: hash({}) = {}", input, result),
+    }
     println!("✓ Computation trace generated with {} steps", trace.steps.len());
 
     println!("\nGenerating STARK proof...");
-    let proof = prover.prove("fibonacci", result, &trace);
+    let mut proof = match prover.prove(job.name(), result.clone(), &trace) {
+        Ok(proof) => proof,
+        Err(e) => {
+            eprintln!("Error generating proof: {}", e);
+            std::process::exit(computation_error_exit_code(&e));
+        }
+    };
     println!("✓ STARK proof generated successfully");
 
+    if let Some(forced) = force_result {
+        println!("\n⚠ DEMONSTRATION ONLY: claiming result {} instead of {}", forced, result);
+        println!("⚠ This proof is intentionally unsound and must fail verification");
+        proof.result = forced.into();
+    }
+
     println!(
         "\nProof Details:\n  - Version: {}\n  - Computation: {}\n  - Result: {}\n  - Security Level: {} bits\n  - Trace Commitment: {}...\n  - Challenge: {}\n  - FRI Layers: {}",
         proof.version,
         proof.computation,
         proof.result,
         proof.security_bits,
-        &proof.trace_commitment[..16],
+        truncate_hash(&proof.trace_commitment, 16),
         proof.challenge,
         proof.fri_layers.len()
     );
 
-    let output_file = output.unwrap_or_else(|| {
-        PathBuf::from(format!("proof_fib_{}.json", n))
-    });
+    let output_file = output.unwrap_or_else(|| job.default_output());
 
-    match prover.save_proof(&proof, output_file.to_str().unwrap()) {
+    match prover.save_proof(&proof, &output_file) {
         Ok(_) => println!("\n✓ Proof saved to: {}", output_file.display()),
         Err(e) => {
             eprintln!("Error saving proof: {}", e);
-            std::process::exit(1);
+            std::process::exit(EXIT_IO);
+        }
+    }
+
+    if let Some(bundle_file) = bundle {
+        let bundle = TraceBundle::new(job.name(), result, trace, Some(proof));
+        match bundle.save(&bundle_file) {
+            Ok(_) => println!("✓ Trace bundle saved to: {}", bundle_file.display()),
+            Err(e) => {
+                eprintln!("Error saving trace bundle: {}", e);
+                std::process::exit(EXIT_IO);
+            }
         }
     }
 
     println!();
 }
 
-/// Verify a proof from a file
-fn verify_proof(proof_file: PathBuf) {
-    println!("\n{}", "=".repeat(60));
-    println!("STARK PROOF VERIFICATION (RUST)");
-    println!("{}", "=".repeat(60));
+/// Prove a trace read from a file, or from stdin when `trace_file` is `-`
+fn prove_trace(trace_file: String, computation: String, output: Option<PathBuf>, security: u32, compact: bool) {
+    let prover = STARKProver::new(security).with_pretty(!compact);
+    let proved = if trace_file == "-" {
+        prover.prove_from_reader(computation.as_str(), std::io::stdin().lock())
+    } else {
+        match std::fs::File::open(&trace_file) {
+            Ok(file) => prover.prove_from_reader(computation.as_str(), std::io::BufReader::new(file)),
+            Err(e) => {
+                eprintln!("Error reading trace {}: {}", trace_file, e);
+                std::process::exit(EXIT_IO);
+            }
+        }
+    };
+    let proof = match proved {
+        Ok(proof) => proof,
+        Err(e) => {
+            eprintln!("Error proving trace: {}", e);
+            std::process::exit(error_exit_code(&e));
+        }
+    };
+
+    let output_file = output.unwrap_or_else(|| PathBuf::from(format!("{}_proof.json", computation)));
+    match prover.save_proof(&proof, &output_file) {
+        Ok(_) => println!("✓ Proof for {} = {} saved to: {}", computation, proof.result, output_file.display()),
+        Err(e) => {
+            eprintln!("Error saving proof: {}", e);
+            std::process::exit(EXIT_IO);
+        }
+    }
+}
+
+/// Load a proof from a file, or download it when given an http(s) URL,
+/// refusing anything larger than `max_bytes`
+#[cfg(feature = "network")]
+fn load_proof(location: &Path, max_bytes: usize) -> Result<Proof, LoadError> {
+    if let Some(url) = location.to_str().filter(|location| stark_prover_verifier::net::is_url(location)) {
+        let body = stark_prover_verifier::net::fetch(url, max_bytes)?;
+        return Proof::from_json_limited(&body, max_bytes);
+    }
+    Proof::from_file_limited(location, max_bytes)
+}
+
+/// Load a proof from a file, refusing anything larger than `max_bytes`;
+/// URLs need the `network` feature
+#[cfg(not(feature = "network"))]
+fn load_proof(location: &Path, max_bytes: usize) -> Result<Proof, LoadError> {
+    let is_url = |location: &&str| location.starts_with("http://") || location.starts_with("https://");
+    if let Some(url) = location.to_str().filter(is_url) {
+        return Err(LoadError::NetworkDisabled { url: url.to_string() });
+    }
+    Proof::from_file_limited(location, max_bytes)
+}
+
+fn verify_proof(proof_file: PathBuf, security: u32, verbosity: ReportVerbosity) {
+    if verbosity == ReportVerbosity::Full {
+        println!("\n{}", "=".repeat(60));
+        println!("STARK PROOF VERIFICATION (RUST)");
+        println!("{}", "=".repeat(60));
+        println!();
+
+        println!("Loading proof from: {}", proof_file.display());
+    }
+
+    let verifier = STARKVerifier::new(security);
+    let proof = match load_proof(&proof_file, verifier.max_file_bytes()) {
+        Ok(proof) => proof,
+        Err(e) => {
+            eprintln!("Error loading proof: {}", e);
+            std::process::exit(load_error_exit_code(&e));
+        }
+    };
+    let result = verifier.verify(&proof);
+    result.print_report_with(verbosity);
+    if !result.valid {
+        let supported = STARKVerifier::supported_versions().contains(&proof.version.as_str())
+            && crypto::HashAlgorithm::from_name(&proof.hash_algorithm).is_some();
+        std::process::exit(if supported { EXIT_INVALID } else { EXIT_UNSUPPORTED });
+    }
+}
+
+/// Print the proof field at a JSON Pointer; strings are printed bare so
+/// the output can be used directly in scripts
+fn query_proof(proof_file: PathBuf, pointer: String) {
+    let proof = match Proof::from_file(&proof_file) {
+        Ok(proof) => proof,
+        Err(e) => {
+            eprintln!("Error loading proof: {}", e);
+            std::process::exit(1);
+        }
+    };
+    match proof.get_field(&pointer) {
+        Some(serde_json::Value::String(value)) => println!("{}", value),
+        Some(value) => println!("{}", serde_json::to_string_pretty(&value).expect("JSON values always serialize")),
+        None => {
+            eprintln!("Error: no field at {:?}", pointer);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Print aggregate statistics for the proofs in a directory
+fn print_stats(dir: PathBuf) {
+    match ProofStats::from_dir(&dir) {
+        Ok(stats) => println!("{}", stats),
+        Err(e) => {
+            eprintln!("Error reading {}: {}", dir.display(), e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Print the trace length histogram of an archive
+fn print_histogram(archive_file: PathBuf) {
+    match ProofArchive::load(&archive_file) {
+        Ok(archive) => println!("{}", archive.trace_length_histogram()),
+        Err(e) => {
+            eprintln!("Error loading archive: {}", e);
+            std::process::exit(load_error_exit_code(&e));
+        }
+    }
+}
+
+/// Print advisory lints for a proof file
+fn lint_proof(proof_file: PathBuf) {
+    let proof = match Proof::from_file(&proof_file) {
+        Ok(proof) => proof,
+        Err(e) => {
+            eprintln!("Error loading proof: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let lints = proof.lint();
+    if lints.is_empty() {
+        println!("No lints for {}", proof_file.display());
+    } else {
+        println!("{} lint(s) for {}:", lints.len(), proof_file.display());
+        for lint in &lints {
+            println!("  ⚠ {}", lint);
+        }
+    }
+}
+
+/// Print every value absorbed into, and challenge squeezed from, a proof's transcript
+fn print_transcript(proof_file: PathBuf) {
+    let proof = match Proof::from_file(&proof_file) {
+        Ok(proof) => proof,
+        Err(e) => {
+            eprintln!("Error loading proof: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let transcript = crypto::Transcript::for_proof(&proof, &crypto::Sha256Oracle);
+    println!("\nTranscript for {}:", proof_file.display());
+    for (label, value) in transcript.export() {
+        println!("  {:<18} {}", label, value);
+    }
     println!();
+}
+
+/// Prove fibonacci(n) for each n and save the proofs as one archive
+fn prove_batch(
+    ns: Vec<u64>,
+    output: PathBuf,
+    output_dir: Option<PathBuf>,
+    name_template: String,
+    security: u32,
+    compact: bool,
+) {
+    let template = match ProofNameTemplate::parse(&name_template) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let cancel = install_interrupt_handler();
+    let prover = STARKProver::new(security).with_pretty(!compact);
+    let mut proofs = Vec::with_capacity(ns.len());
+    for &n in &ns {
+        // Stop between proofs and keep the ones already done
+        if cancel.is_cancelled() {
+            break;
+        }
+        let proved = computation::fibonacci_with_trace(n)
+            .and_then(|(result, trace)| prover.prove("fibonacci", result, &trace));
+        match proved {
+            Ok(proof) => {
+                println!("✓ fibonacci({}) = {}", n, proof.result);
+                proofs.push(proof);
+            }
+            Err(e) => {
+                eprintln!("Error proving fibonacci({}): {}", n, e);
+                std::process::exit(1);
+            }
+        }
+    }
 
-    println!("Loading proof from: {}", proof_file.display());
+    let count = proofs.len();
+    let interrupted = count < ns.len();
+    if let Some(dir) = output_dir {
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            eprintln!("Error creating {}: {}", dir.display(), e);
+            std::process::exit(1);
+        }
+        for (&n, proof) in ns.iter().zip(&proofs) {
+            let path = dir.join(template.render(&proof.computation, n, security));
+            if let Err(e) = prover.save_proof(proof, &path) {
+                eprintln!("Error saving proof to {}: {}", path.display(), e);
+                std::process::exit(1);
+            }
+        }
+        println!("\n✓ {} proofs saved to: {}", count, dir.display());
+    } else {
+        match prover.save_archive(&ProofArchive::new(proofs), &output) {
+            Ok(_) => println!("\n✓ {} proofs saved to: {}", count, output.display()),
+            Err(e) => {
+                eprintln!("Error saving archive: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
 
-    let verifier = STARKVerifier::new(128);
-    match verifier.verify_from_file(proof_file.to_str().unwrap()) {
-        Ok(result) => {
-            if !result.valid {
-                eprintln!("Proof file not found or invalid");
+    if interrupted {
+        println!("Interrupted: {} of {} proofs completed", count, ns.len());
+        std::process::exit(130);
+    }
+}
+
+/// Verify every proof in an archive, exiting with 1 if any is invalid
+fn verify_batch_file(archive_file: PathBuf, security: u32, junit: Option<PathBuf>) {
+    let archive = match ProofArchive::load(&archive_file) {
+        Ok(archive) => archive,
+        Err(e) => {
+            eprintln!("Error loading archive: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let results = STARKVerifier::new(security).verify_archive(&archive);
+    for (i, (proof, result)) in archive.proofs.iter().zip(&results).enumerate() {
+        let status = if result.valid { "✓ VALID" } else { "✗ INVALID" };
+        println!("[{}] {} = {} - {}", i, proof.computation, proof.result, status);
+    }
+
+    if let Some(junit) = junit {
+        let cases: Vec<(String, &VerificationResult)> = archive
+            .proofs
+            .iter()
+            .zip(&results)
+            .enumerate()
+            .map(|(i, (proof, result))| (format!("[{}] {}", i, proof.statement()), result))
+            .collect();
+        let written = std::fs::File::create(&junit).and_then(|mut file| {
+            write_junit_report(&mut file, &archive_file.display().to_string(), &cases)
+        });
+        if let Err(e) = written {
+            eprintln!("Error writing JUnit report {}: {}", junit.display(), e);
+            std::process::exit(EXIT_IO);
+        }
+    }
+
+    let invalid = results.iter().filter(|result| !result.valid).count();
+    println!("\n{} of {} proofs valid", results.len() - invalid, results.len());
+    if invalid > 0 {
+        std::process::exit(1);
+    }
+}
+
+/// Check that proofs from several files share a trace commitment
+fn verify_consistent(proof_files: Vec<PathBuf>) {
+    let mut proofs = Vec::new();
+    for file in &proof_files {
+        match Proof::from_file(file) {
+            Ok(proof) => proofs.push(proof),
+            Err(e) => {
+                eprintln!("Error loading proof {}: {}", file.display(), e);
                 std::process::exit(1);
             }
-            result.print_report();
         }
+    }
+
+    println!();
+    for (file, proof) in proof_files.iter().zip(&proofs) {
+        println!("{}  {}", truncate_hash(&proof.trace_commitment, 16), file.display());
+    }
+
+    if verify_same_trace(&proofs) {
+        println!("\n✓ All {} proofs commit to the same trace\n", proofs.len());
+    } else {
+        eprintln!("\n✗ Proofs commit to different traces\n");
+        std::process::exit(1);
+    }
+}
+
+/// Print the supported computations
+fn list_computations() {
+    println!("\n{:<14} {:>5} {:>10}  DESCRIPTION", "NAME", "ARITY", "MAX INPUT");
+    for info in computation::supported() {
+        println!(
+            "{:<14} {:>5} {:>10}  {}",
+            info.name, info.arity, info.max_input, info.description
+        );
+    }
+    println!();
+}
+
+/// Time every hash algorithm on fibonacci(n) and check that each one's
+/// proof verifies, exiting with 1 if any does not
+fn bench_hash(n: u64, iterations: u32) {
+    let (result, trace) = match computation::fibonacci_with_trace(n) {
+        Ok(computed) => computed,
         Err(e) => {
-            eprintln!("Error loading proof: {}", e);
+            eprintln!("Error computing fibonacci({}): {}", n, e);
             std::process::exit(1);
         }
+    };
+    let iterations = iterations.max(1);
+
+    let mut rows = Vec::new();
+    for &algorithm in crypto::HashAlgorithm::ALL {
+        let prover = STARKProver::new(128).with_hash_algorithm(algorithm);
+        let mut proof = None;
+        let start = Instant::now();
+        for _ in 0..iterations {
+            match prover.prove("fibonacci", result, &trace) {
+                Ok(proved) => proof = Some(proved),
+                Err(e) => {
+                    eprintln!("Error proving with {}: {}", algorithm.name(), e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        let prove_time = start.elapsed() / iterations;
+        let proof = proof.expect("at least one iteration");
+
+        let start = Instant::now();
+        for _ in 0..iterations {
+            std::hint::black_box(algorithm.commit_trace(&trace));
+        }
+        let commit_time = start.elapsed() / iterations;
+
+        let verifies = STARKVerifier::new(128).is_valid(&proof);
+        rows.push((algorithm, commit_time, prove_time, verifies));
     }
+
+    let fastest = rows.iter().map(|row| row.2).min().unwrap_or_default().max(Duration::from_nanos(1));
+    println!("\nfibonacci({}), {} iterations per algorithm\n", n, iterations);
+    println!("{:<12} {:>12} {:>12} {:>10}  VERIFIES", "ALGORITHM", "COMMIT", "PROVE", "RELATIVE");
+    for (algorithm, commit_time, prove_time, verifies) in &rows {
+        println!(
+            "{:<12} {:>12} {:>12} {:>9.2}x  {}",
+            algorithm.name(),
+            format!("{:.1?}", commit_time),
+            format!("{:.1?}", prove_time),
+            fastest.as_secs_f64() / prove_time.as_secs_f64().max(f64::MIN_POSITIVE),
+            if *verifies { "✓" } else { "✗" }
+        );
+    }
+    println!();
+    if rows.iter().any(|row| !row.3) {
+        std::process::exit(1);
+    }
+}
+
+/// Replay a bundle's computation, exiting with 1 at the first divergence
+fn replay_bundle(bundle_file: PathBuf) {
+    let bundle = match TraceBundle::load(&bundle_file) {
+        Ok(bundle) => bundle,
+        Err(e) => {
+            eprintln!("Error loading trace bundle {}: {}", bundle_file.display(), e);
+            std::process::exit(load_error_exit_code(&e));
+        }
+    };
+    let (result, trace) = match computation::replay(&bundle.computation, &bundle.trace.inputs) {
+        Ok(replayed) => replayed,
+        Err(e) => {
+            eprintln!("Error replaying {}: {}", bundle.computation, e);
+            std::process::exit(computation_error_exit_code(&e));
+        }
+    };
+
+    let diff = bundle.trace.compare(&trace);
+    if let Some(index) = diff.first_differing_step {
+        println!("✗ Replay diverges from the stored trace at step {}", index);
+        match bundle.trace.steps.get(index) {
+            Some(step) => println!("  stored:   {:?}", step),
+            None => println!("  stored:   (trace ends)"),
+        }
+        match trace.steps.get(index) {
+            Some(step) => println!("  replayed: {:?}", step),
+            None => println!("  replayed: (trace ends)"),
+        }
+        std::process::exit(1);
+    }
+    if !diff.is_identical() || result != bundle.result {
+        println!("✗ Replay matches every step, but not the stored outputs or result");
+        println!("{}", diff);
+        std::process::exit(1);
+    }
+    println!("✓ Replay of {} matches all {} stored steps", bundle.computation, trace.steps.len());
+}
+
+/// Regenerate a proof from a bundle's trace, warning when the bundle's
+/// embedded proof claimed a different result
+fn reprove_bundle(bundle_file: PathBuf, output: Option<PathBuf>) {
+    let prover = STARKProver::new(128);
+    let (proof, warning) = match prover.reprove_from_bundle(&bundle_file) {
+        Ok(reproved) => reproved,
+        Err(e) => {
+            eprintln!("Error re-proving {}: {}", bundle_file.display(), e);
+            std::process::exit(error_exit_code(&e));
+        }
+    };
+    if let Some(warning) = warning {
+        eprintln!("Warning: {}", warning);
+    }
+    let output_file = output.unwrap_or_else(|| PathBuf::from(format!("{}_proof.json", proof.computation)));
+    match prover.save_proof(&proof, &output_file) {
+        Ok(_) => println!("✓ Re-proved {} = {} saved to: {}", proof.computation, proof.result, output_file.display()),
+        Err(e) => {
+            eprintln!("Error saving proof: {}", e);
+            std::process::exit(EXIT_IO);
+        }
+    }
+}
+
+/// Compare the traces of two bundle files
+fn trace_diff(a: PathBuf, b: PathBuf) {
+    let load = |path: &PathBuf| match TraceBundle::load(path) {
+        Ok(bundle) => bundle,
+        Err(e) => {
+            eprintln!("Error loading trace bundle {}: {}", path.display(), e);
+            std::process::exit(load_error_exit_code(&e));
+        }
+    };
+    let bundle_a = load(&a);
+    let bundle_b = load(&b);
+
+    println!("\n{}", "=".repeat(60));
+    println!("TRACE DIFF");
+    println!("{}", "=".repeat(60));
+    println!("A: {} ({}, {} steps)", a.display(), bundle_a.computation, bundle_a.trace.steps.len());
+    println!("B: {} ({}, {} steps)", b.display(), bundle_b.computation, bundle_b.trace.steps.len());
+    println!();
+    println!("{}", bundle_a.trace.compare(&bundle_b.trace));
+    println!();
 }
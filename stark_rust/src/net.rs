@@ -0,0 +1,100 @@
+// src/net.rs - Fetching proofs over HTTP
+use crate::error::LoadError;
+use std::io::Read;
+use std::time::Duration;
+
+/// How long to wait for the server before giving up
+pub const NETWORK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Whether `location` names a proof to download rather than a local file
+pub fn is_url(location: &str) -> bool {
+    location.starts_with("http://") || location.starts_with("https://")
+}
+
+/// Download the body of `url` with a blocking HTTP(S) GET.
+///
+/// Redirects are followed. Connection failures and non-2xx responses
+/// fail with `LoadError::Network`, bodies longer than `max_bytes` with
+/// `LoadError::TooLarge`, never with a verification failure.
+pub fn fetch(url: &str, max_bytes: usize) -> Result<Vec<u8>, LoadError> {
+    let network = |message: String| LoadError::Network(format!("{}: {}", url, message));
+    let client = reqwest::blocking::Client::builder()
+        .timeout(NETWORK_TIMEOUT)
+        .build()
+        .map_err(|e| network(e.to_string()))?;
+    let response = client
+        .get(url)
+        .header(reqwest::header::ACCEPT, "application/json")
+        .send()
+        .map_err(|e| network(e.to_string()))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(network(format!("server answered {}", status)));
+    }
+    if response.content_length().is_some_and(|len| len > max_bytes as u64) {
+        return Err(LoadError::TooLarge { max: max_bytes });
+    }
+
+    // Read one byte past the limit so an oversized body is detected
+    // without buffering all of it
+    let mut body = Vec::new();
+    response
+        .take(max_bytes as u64 + 1)
+        .read_to_end(&mut body)
+        .map_err(|e| network(e.to_string()))?;
+    if body.len() > max_bytes {
+        return Err(LoadError::TooLarge { max: max_bytes });
+    }
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::net::TcpListener;
+
+    /// Serve `response` to a single connection on a local port
+    fn serve_once(response: String) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0u8; 1024];
+            let _ = stream.read(&mut request).unwrap();
+            stream.write_all(response.as_bytes()).unwrap();
+        });
+        format!("http://{}/proof.json", address)
+    }
+
+    #[test]
+    fn test_verify_from_url() {
+        use crate::computation::fibonacci_with_trace;
+        use crate::{STARKProver, STARKVerifier};
+
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let proof = STARKProver::new(128).prove("fibonacci", result, &trace).unwrap();
+        let json = serde_json::to_string(&proof).unwrap();
+        let url = serve_once(format!("HTTP/1.0 200 OK\r\nContent-Length: {}\r\n\r\n{}", json.len(), json));
+        assert!(is_url(&url));
+        let report = STARKVerifier::new(128).verify_from_url(&url).unwrap();
+        assert!(report.valid);
+
+        let url = serve_once("HTTP/1.0 404 Not Found\r\n\r\n".to_string());
+        let error = STARKVerifier::new(128).verify_from_url(&url).unwrap_err();
+        assert!(matches!(error, LoadError::Network(ref message) if message.contains("404")), "{}", error);
+
+        let url = serve_once(format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}", json.len(), json));
+        assert!(matches!(fetch(&url, 16), Err(LoadError::TooLarge { max: 16, .. })));
+    }
+
+    #[test]
+    fn test_fetch_errors() {
+        assert!(!is_url("proof.json"));
+        assert!(matches!(fetch("ftp://example.com/proof.json", 1024), Err(LoadError::Network(_))));
+
+        // Nothing listens on a port we just released
+        let address = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+        assert!(matches!(fetch(&format!("http://{}/", address), 1024), Err(LoadError::Network(_))));
+    }
+}
@@ -1,41 +1,594 @@
 // src/prover.rs - STARK Proof Generation
-use crate::types::{Proof, ProofTrace};
-use crate::crypto::{hash_integers, hash_string, generate_challenge};
+use crate::types::{
+    ConstraintAnnotation, EvaluationOpening, PaddingMode, Proof, ProofArchive, ProofTrace, ResultValue, StepOpening, TraceStep,
+    VerificationResult,
+};
+use crate::verifier::STARKVerifier;
+use crate::error::{ComputationError, Error, LoadError};
+use crate::computation;
+use crate::crypto::{
+    challenge_seed, fri_layers_for_degree, generate_challenge_with, interactive_challenge, query_indices, HashAlgorithm,
+    RandomOracle, Sha256Oracle, DEFAULT_FOLD_FACTOR,
+};
+use serde::Deserialize;
+use std::io::Write;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Serialization format for proofs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// JSON document, indented unless the prover is set to compact output
+    /// (see `STARKProver::with_pretty`)
+    Json,
+    /// Single-line JSON followed by a newline, for newline-delimited streams
+    Ndjson,
+}
+
+/// The parts of a `TraceBundle` that `reprove_from_bundle` needs. The
+/// embedded proof stays raw JSON, since it may predate the current format.
+#[derive(Deserialize)]
+struct LegacyBundle {
+    computation: String,
+    #[serde(default)]
+    result: Option<ResultValue>,
+    trace: ProofTrace,
+    #[serde(default)]
+    proof: Option<serde_json::Value>,
+}
+
+/// Cooperative stop flag for long-running batch loops.
+///
+/// Clones share the same flag, so one can be handed to a signal handler
+/// while the loop polls another between jobs.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Create a token that is not yet cancelled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request that loops polling this token stop
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /// True once `cancel` has been called on any clone
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// How much of the trace hash chain to include in a proof
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HashChainMode {
+    /// No hash chain (default)
+    #[default]
+    Off,
+    /// Only the final chain value
+    Final,
+    /// The final value plus every intermediate link
+    Full,
+}
+
+/// Phase of proof generation reported by `STARKProver::prove_with_progress`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProvePhase {
+    /// Hashing the trace into its commitment
+    Committing,
+    /// Evaluating the constraints on the trace
+    EvaluatingConstraints,
+    /// Building FRI layer `layer` (counting from 0)
+    Folding { layer: usize },
+    /// The proof is complete
+    Done,
+}
+
+/// A progress report: the phase about to run and how much of the work
+/// is already complete
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProveProgress {
+    pub phase: ProvePhase,
+    /// Percent complete, from 0 to 100
+    pub percent: u8,
+}
 
 /// STARK Prover
 pub struct STARKProver {
     security_level: u32,
+    include_constraint_annotations: bool,
+    constraint_target: Option<u64>,
+    oracle: Arc<dyn RandomOracle>,
+    fold_factor: usize,
+    hash_chain: HashChainMode,
+    hash_algorithm: HashAlgorithm,
+    padding: PaddingMode,
+    num_queries: usize,
+    evaluation_openings: usize,
+    constraint_degree: usize,
+    constraints_per_step: usize,
+    sampled_steps: usize,
+    salt_commitment: bool,
+    interactive_seed: Option<u64>,
+    omit_timestamp: bool,
+    pretty: bool,
 }
 
 impl STARKProver {
-    /// Create a new prover
+    /// The recommended prover: `RECOMMENDED_SECURITY_BITS` (128) bits and
+    /// `RECOMMENDED_SECURITY_BITS / BITS_PER_QUERY` (128) step openings,
+    /// the fewest for which `Proof::effective_security_bits` reaches that
+    /// level, with every other option at its default. Start here unless you
+    /// need something else.
+    ///
+    /// Openings are distinct steps, so a trace shorter than 128 steps is
+    /// opened in full and its estimate stops at `BITS_PER_QUERY` per step.
+    /// Proofs are larger than with `new` because of the openings: each one
+    /// carries a step and its Merkle path.
+    pub fn secure() -> Self {
+        let bits = crate::types::RECOMMENDED_SECURITY_BITS;
+        STARKProver::new(bits).with_num_queries(bits.div_ceil(crate::types::BITS_PER_QUERY) as usize)
+    }
+
+    /// Create a new prover with no step openings.
+    ///
+    /// `security_level` is taken as given; see `secure` for the
+    /// recommended configuration.
     pub fn new(security_level: u32) -> Self {
-        STARKProver { security_level }
+        STARKProver {
+            security_level,
+            include_constraint_annotations: false,
+            constraint_target: None,
+            oracle: Arc::new(Sha256Oracle),
+            fold_factor: DEFAULT_FOLD_FACTOR,
+            hash_chain: HashChainMode::Off,
+            hash_algorithm: HashAlgorithm::default(),
+            padding: PaddingMode::None,
+            num_queries: 0,
+            evaluation_openings: 0,
+            constraint_degree: 1,
+            constraints_per_step: 1,
+            sampled_steps: 0,
+            salt_commitment: false,
+            interactive_seed: None,
+            omit_timestamp: false,
+            pretty: true,
+        }
+    }
+
+    /// Mix a fresh random salt into every trace commitment (recorded as
+    /// `Proof::commitment_salt`), so the commitment hides the trace and two
+    /// proofs of the same computation commit differently.
+    ///
+    /// Off by default, keeping commitments deterministic. Salted proofs
+    /// never match in `verify_same_trace`.
+    pub fn with_commitment_salt(mut self, enabled: bool) -> Self {
+        self.salt_commitment = enabled;
+        self
+    }
+
+    /// Leave `Proof::timestamp` out of proofs, so they reveal nothing about
+    /// when they were made and proving the same trace twice gives identical
+    /// proofs. Verifiers skip their timestamp checks for such proofs.
+    pub fn with_timestamp_omitted(mut self, omit: bool) -> Self {
+        self.omit_timestamp = omit;
+        self
+    }
+
+    /// Indent `Format::Json` output (the default). Compact JSON is smaller,
+    /// which adds up when storing many proofs, and loads the same way.
+    pub fn with_pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+
+    /// Draw the challenge from a `ChaCha20Rng` seeded with `seed`, playing
+    /// the verifier of an interactive protocol, instead of deriving it
+    /// from the commitment (Fiat-Shamir).
+    ///
+    /// For teaching only: the proof records the seed, but the challenge no
+    /// longer depends on the commitment, so a prover who chooses the seed
+    /// can choose the challenge. Such proofs only verify with
+    /// `STARKVerifier::with_interactive_challenges_allowed(true)`.
+    pub fn with_interactive_challenge(mut self, seed: u64) -> Self {
+        self.interactive_seed = Some(seed);
+        self
+    }
+
+    /// Evaluate constraints on only `count` challenge-selected steps instead
+    /// of all of them (0 disables).
+    ///
+    /// NOT SOUND: steps outside the sample are never checked, so a prover
+    /// can cheat on them undetected. Intended only for demos on traces too
+    /// large to evaluate in full. The trace commitment still covers every
+    /// step, and the sampled indices are recorded in `Proof::sampled_steps`.
+    pub fn with_trace_sampling(mut self, count: usize) -> Self {
+        self.sampled_steps = count;
+        self
+    }
+
+    /// Set the (conceptual) degree of the constraint polynomial.
+    ///
+    /// Higher degrees enlarge the domain FRI has to fold, so proofs get
+    /// more layers. Values below 1 are raised to 1.
+    pub fn with_constraint_degree(mut self, degree: usize) -> Self {
+        self.constraint_degree = degree.max(1);
+        self
+    }
+
+    /// Degree of the constraint polynomial this prover works with
+    pub fn constraint_degree(&self) -> usize {
+        self.constraint_degree
+    }
+
+    /// Evaluate `k` constraints on every trace step instead of one.
+    ///
+    /// Proofs carry `k` evaluations per step, grouped per constraint (see
+    /// `TraceStep::constraint_value`). Values below 1 are raised to 1.
+    pub fn with_constraints_per_step(mut self, k: usize) -> Self {
+        self.constraints_per_step = k.max(1);
+        self
+    }
+
+    /// Number of constraints this prover evaluates per trace step
+    pub fn constraints_per_step(&self) -> usize {
+        self.constraints_per_step
+    }
+
+    /// Open `count` trace steps, chosen by the challenge, with Merkle paths
+    /// so the proof can be spot-checked without the full trace (0 disables).
+    ///
+    /// Each query adds `BITS_PER_QUERY` bits to
+    /// `Proof::effective_security_bits`, at the cost of one step and its
+    /// path in the proof. The count is recorded as `Proof::num_queries`.
+    pub fn with_num_queries(mut self, count: usize) -> Self {
+        self.num_queries = count;
+        self
+    }
+
+    /// Number of trace steps this prover opens
+    pub fn num_queries(&self) -> usize {
+        self.num_queries
+    }
+
+    /// Open `count` constraint evaluations with Merkle paths against an
+    /// evaluation root, at positions derived from the challenge and that
+    /// root (0 disables). The constraint-side counterpart of
+    /// `with_num_queries`.
+    pub fn with_evaluation_openings(mut self, count: usize) -> Self {
+        self.evaluation_openings = count;
+        self
+    }
+
+    /// Pad constraint evaluations to a power-of-two domain before FRI folding
+    pub fn with_padding(mut self, padding: PaddingMode) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Select the hash algorithm used for every commitment in the proof
+    /// (and with it the integer byte order)
+    pub fn with_hash_algorithm(mut self, algorithm: HashAlgorithm) -> Self {
+        self.hash_algorithm = algorithm;
+        self
+    }
+
+    /// Include a running hash chain over the trace steps for auditability
+    pub fn with_hash_chain(mut self, mode: HashChainMode) -> Self {
+        self.hash_chain = mode;
+        self
+    }
+
+    /// Set the FRI folding factor (values below 2 are raised to 2)
+    pub fn with_fold_factor(mut self, fold_factor: usize) -> Self {
+        self.fold_factor = fold_factor.max(2);
+        self
+    }
+
+    /// Derive challenges with a custom random oracle instead of SHA-256
+    pub fn with_oracle(mut self, oracle: impl RandomOracle + 'static) -> Self {
+        self.oracle = Arc::new(oracle);
+        self
+    }
+
+    /// Include the evaluated constraint value of every trace step in the proof.
+    /// Off by default to keep proofs small.
+    pub fn with_constraint_annotations(mut self, include: bool) -> Self {
+        self.include_constraint_annotations = include;
+        self
+    }
+
+    /// Mark an annotated step as satisfied only when its evaluation equals
+    /// `target`, matching `STARKVerifier::with_constraint_target`.
+    ///
+    /// Without a target, annotations only record the range check.
+    pub fn with_constraint_target(mut self, target: u64) -> Self {
+        self.constraint_target = Some(target);
+        self
     }
 
     /// Generate a STARK proof
+    ///
+    /// Fails with `ComputationError::NoConstraints` rather than producing a
+    /// proof without FRI layers when the trace yields no evaluations, and
+    /// with `ComputationError::InputTooLarge` when a known computation's
+    /// input exceeds its `ComputationInfo::max_input`.
     pub fn prove(
         &self,
         computation_name: impl Into<String>,
-        result: u64,
+        result: impl Into<ResultValue>,
         trace: &ProofTrace,
-    ) -> Proof {
-        let computation = computation_name.into();
+    ) -> Result<Proof, ComputationError> {
+        self.prove_with_progress(computation_name, result, trace, |_| {})
+    }
+
+    /// Run `computation` on `input` and prove it, returning the trace along
+    /// with the proof so the caller can keep it (e.g. in a `TraceBundle`)
+    pub fn prove_computation(
+        &self,
+        computation: &dyn computation::Computation,
+        input: u64,
+    ) -> Result<(Proof, ProofTrace), ComputationError> {
+        let (result, trace) = computation.run(input)?;
+        let proof = self.prove(computation.info().name, result, &trace)?;
+        Ok((proof, trace))
+    }
+
+    /// Like `prove`, but call `progress` at every phase boundary and once
+    /// per FRI layer, e.g. to drive a progress bar for large traces.
+    ///
+    /// The percentage counts committing, evaluating and each FRI layer as
+    /// one unit of work; the last report is `ProvePhase::Done` at 100%.
+    pub fn prove_with_progress(
+        &self,
+        computation_name: impl Into<String>,
+        result: impl Into<ResultValue>,
+        trace: &ProofTrace,
+        mut progress: impl FnMut(ProveProgress),
+    ) -> Result<Proof, ComputationError> {
+        self.prove_inner(computation_name.into(), result.into(), trace, true, &mut Vec::new(), &mut progress)
+    }
+
+    /// Prove only the trace commitment and result, skipping FRI.
+    ///
+    /// Much faster for large traces, but clearly weaker: the proof has no
+    /// FRI layers, is marked `commitment_only`, and only verifies under a
+    /// verifier built with `with_commitment_only_allowed(true)`.
+    pub fn prove_commitment(
+        &self,
+        computation_name: impl Into<String>,
+        result: impl Into<ResultValue>,
+        trace: &ProofTrace,
+    ) -> Result<Proof, ComputationError> {
+        self.prove_inner(computation_name.into(), result.into(), trace, false, &mut Vec::new(), &mut |_| {})
+    }
+
+    /// Commit to the trace (salted if configured), then prove the rest,
+    /// evaluating constraints into `scratch` (see `prove_committed`)
+    fn prove_inner(
+        &self,
+        computation: String,
+        result: ResultValue,
+        trace: &ProofTrace,
+        with_fri: bool,
+        scratch: &mut Vec<u64>,
+        progress: &mut dyn FnMut(ProveProgress),
+    ) -> Result<Proof, ComputationError> {
+        progress(ProveProgress { phase: ProvePhase::Committing, percent: 0 });
 
         // Step 1: Commit to the trace
+        if self.salt_commitment {
+            let salt = crate::util::random_salt().map_err(|e| ComputationError::Io(e.to_string()))?;
+            let trace_commitment = self.hash_algorithm.commit_trace_salted(trace, &salt);
+            let mut proof =
+                self.prove_committed(computation, result, trace, trace_commitment, with_fri, scratch, progress)?;
+            proof.commitment_salt = Some(salt);
+            return Ok(proof);
+        }
         let trace_commitment = self.commit_to_trace(trace);
+        self.prove_committed(computation, result, trace, trace_commitment, with_fri, scratch, progress)
+    }
+
+    /// Prove a trace read as JSON from `reader`, claiming the result the
+    /// trace records (see `ProofTrace::claimed_result`).
+    ///
+    /// Empty and malformed input are reported as distinct `LoadError`s; a
+    /// trace with no result to claim fails with
+    /// `ComputationError::MissingResult`.
+    pub fn prove_from_reader(
+        &self,
+        computation_name: impl Into<String>,
+        reader: impl std::io::Read,
+    ) -> Result<Proof, Error> {
+        let trace = ProofTrace::from_reader(reader)?;
+        let result = trace.claimed_result().ok_or(ComputationError::MissingResult)?;
+        Ok(self.prove(computation_name, result, &trace)?)
+    }
+
+    /// Prove a batch of `(computation, result, trace)` jobs in order.
+    ///
+    /// One evaluation buffer is shared by every job, so it only grows to
+    /// the largest job's FRI domain instead of being allocated per proof.
+    pub fn prove_many(&self, jobs: &[(&str, u64, &ProofTrace)]) -> Vec<Result<Proof, ComputationError>> {
+        let mut scratch = Vec::new();
+        jobs.iter()
+            .map(|&(computation, result, trace)| {
+                self.prove_inner(computation.to_string(), result.into(), trace, true, &mut scratch, &mut |_| {})
+            })
+            .collect()
+    }
 
-        // Step 2: Evaluate constraints
-        let constraint_evaluations = self.evaluate_constraints(trace);
+    /// Like `prove_many`, but checks `cancel` before each job and stops early
+    /// once it is set. Results for the jobs already proved are returned.
+    pub fn prove_many_until(
+        &self,
+        jobs: &[(&str, u64, &ProofTrace)],
+        cancel: &CancellationToken,
+    ) -> Vec<Result<Proof, ComputationError>> {
+        let mut scratch = Vec::new();
+        jobs.iter()
+            .take_while(|_| !cancel.is_cancelled())
+            .map(|&(computation, result, trace)| {
+                self.prove_inner(computation.to_string(), result.into(), trace, true, &mut scratch, &mut |_| {})
+            })
+            .collect()
+    }
 
-        // Step 3: Generate challenge
-        let challenge = generate_challenge(&trace_commitment, self.security_level);
+    /// Run `compute` with a time budget via `computation::run_with_timeout`,
+    /// then prove its result. Fails with `ComputationError::Timeout` if the
+    /// computation does not finish in time.
+    pub fn prove_with_timeout<F>(
+        &self,
+        computation_name: impl Into<String>,
+        compute: F,
+        timeout: std::time::Duration,
+    ) -> Result<Proof, ComputationError>
+    where
+        F: FnOnce(&CancellationToken) -> Result<(u64, ProofTrace), ComputationError> + Send + 'static,
+    {
+        let (result, trace) = computation::run_with_timeout(compute, timeout)?;
+        self.prove(computation_name, result, &trace)
+    }
+
+    /// Generate a proof and immediately check it with `verifier`.
+    ///
+    /// Returns the proof only if it verifies; otherwise returns the failing
+    /// verification result. Proving errors are reported as a failed result.
+    // The failing result is the whole point of the error path; boxing it
+    // would only make callers unwrap another layer
+    #[allow(clippy::result_large_err)]
+    pub fn prove_verified(
+        &self,
+        computation_name: impl Into<String>,
+        result: impl Into<ResultValue>,
+        trace: &ProofTrace,
+        verifier: &STARKVerifier,
+    ) -> Result<Proof, VerificationResult> {
+        let proof = self.prove(computation_name, result, trace).map_err(|e| {
+            VerificationResult::invalid("Proof generation failed", vec![e.to_string()])
+        })?;
+        let verification = verifier.verify(&proof);
+        if verification.valid {
+            Ok(proof)
+        } else {
+            Err(verification)
+        }
+    }
 
-        // Step 4: Create FRI layers
-        let fri_layers = self.create_fri_layers(&constraint_evaluations, &challenge);
+    /// Generate a STARK proof reusing a commitment from `commit_to_trace`.
+    ///
+    /// The commitment depends only on the trace, so it can be computed once
+    /// and shared across provers running at different security levels
+    /// (`benches/commitment.rs` measures the saving).
+    ///
+    /// A salted commitment must be fresh for every proof, so a prover with
+    /// `with_commitment_salt(true)` ignores `trace_commitment` and commits
+    /// under a new salt, exactly like `prove`.
+    pub fn prove_with_commitment(
+        &self,
+        computation_name: impl Into<String>,
+        result: impl Into<ResultValue>,
+        trace: &ProofTrace,
+        trace_commitment: String,
+    ) -> Result<Proof, ComputationError> {
+        if self.salt_commitment {
+            return self.prove(computation_name, result, trace);
+        }
+        self.prove_committed(
+            computation_name.into(),
+            result.into(),
+            trace,
+            trace_commitment,
+            true,
+            &mut Vec::new(),
+            &mut |_| {},
+        )
+    }
+
+    /// Steps 2 onwards of proving, reporting progress from evaluation on.
+    /// Without FRI the proof is marked commitment-only.
+    ///
+    /// Constraints are evaluated into `scratch`, which then serves as the
+    /// FRI folding domain, so a caller proving many traces can pass the
+    /// same buffer to each call.
+    // Internal plumbing shared by every public entry point
+    #[allow(clippy::too_many_arguments)]
+    fn prove_committed(
+        &self,
+        computation: String,
+        result: ResultValue,
+        trace: &ProofTrace,
+        trace_commitment: String,
+        with_fri: bool,
+        scratch: &mut Vec<u64>,
+        progress: &mut dyn FnMut(ProveProgress),
+    ) -> Result<Proof, ComputationError> {
+
+        if let Some(info) = computation::info(&computation) {
+            if trace.inputs.get(info.input).is_some_and(|&value| value > info.max_input) {
+                return Err(ComputationError::InputTooLarge { max: info.max_input });
+            }
+        }
+
+        // Step 2: Generate challenge, binding the public inputs and, when
+        // steps will be opened, the step Merkle root so the prover cannot
+        // pick either afterwards
+        let step_leaves: Vec<String> = if self.num_queries > 0 {
+            trace.steps.iter().map(|step| self.hash_algorithm.merkle_leaf(step)).collect()
+        } else {
+            Vec::new()
+        };
+        let step_root = (!step_leaves.is_empty()).then(|| self.hash_algorithm.merkle_root(&step_leaves));
+        let seed = challenge_seed(&trace_commitment, step_root.as_deref(), Some(&trace.inputs));
+        let challenge = match self.interactive_seed {
+            Some(rng_seed) => interactive_challenge(rng_seed, self.security_level),
+            None => generate_challenge_with(self.oracle.as_ref(), &seed, self.security_level),
+        };
+
+        // Step 3: Evaluate constraints, on every step or only on the
+        // challenge-selected sample
+        let sampled_steps = if self.sampled_steps > 0 {
+            query_indices(&challenge, self.sampled_steps, trace.steps.len())
+        } else {
+            Vec::new()
+        };
+        // Padding can still add a FRI layer, so this share is an estimate
+        let evaluated_steps = if sampled_steps.is_empty() { trace.steps.len() } else { sampled_steps.len() };
+        let estimated_layers = fri_layers_for_degree(
+            evaluated_steps.saturating_mul(self.constraints_per_step),
+            self.constraint_degree,
+            self.fold_factor,
+        );
+        let percent = (100 / (2 + estimated_layers)) as u8;
+        progress(ProveProgress { phase: ProvePhase::EvaluatingConstraints, percent });
+        if sampled_steps.is_empty() {
+            self.evaluate_constraints_into(trace, scratch);
+        } else {
+            let sampled: Vec<&TraceStep> = sampled_steps.iter().map(|&i| &trace.steps[i]).collect();
+            self.evaluate_steps_into(&sampled, scratch);
+        }
+        if scratch.is_empty() {
+            return Err(ComputationError::NoConstraints);
+        }
+        let padding = self.padding.apply(scratch);
+        let constraint_evaluations = scratch.clone();
+
+        // Step 4: Create FRI layers, folding the scratch buffer in place
+        let fri_layers = if with_fri {
+            let units = 2 + fri_layers_for_degree(constraint_evaluations.len(), self.constraint_degree, self.fold_factor);
+            self.create_fri_layers(scratch, &challenge, &mut |layer| {
+                let percent = ((2 + layer) * 100 / units) as u8;
+                progress(ProveProgress { phase: ProvePhase::Folding { layer }, percent });
+            })
+        } else {
+            Vec::new()
+        };
 
         // Create the proof
-        Proof::new(
+        let mut proof = Proof::new(
             computation,
             result,
             trace_commitment,
@@ -43,69 +596,236 @@ impl STARKProver {
             challenge,
             fri_layers,
             self.security_level,
-        )
+        );
+        proof.public_inputs = trace.inputs.clone();
+        proof.inputs_bound = true;
+        proof.num_steps = trace.steps.len();
+        proof.padding = padding;
+        proof.sampled_steps = sampled_steps;
+        proof.fri_fold_factor = self.fold_factor;
+        proof.constraint_degree = self.constraint_degree;
+        proof.constraints_per_step = self.constraints_per_step;
+        proof.commitment_only = !with_fri;
+        proof.interactive_seed = self.interactive_seed;
+        if self.omit_timestamp {
+            proof.timestamp = None;
+        }
+        proof.hash_algorithm = self.hash_algorithm.name().to_string();
+        proof.constraint_commitment = self.hash_algorithm.hash_integers(&proof.constraint_evaluations);
+        if self.hash_chain != HashChainMode::Off {
+            let links = self.hash_algorithm.hash_chain(&trace.steps);
+            proof.trace_chain = links.last().cloned();
+            if self.hash_chain == HashChainMode::Full {
+                proof.trace_chain_links = links;
+            }
+        }
+        if step_root.is_some() {
+            proof.num_queries = self.num_queries;
+            proof.fri_queries = query_indices(&proof.challenge, self.num_queries, step_leaves.len())
+                .into_iter()
+                .map(|index| StepOpening {
+                    index,
+                    step: trace.steps[index].clone(),
+                    path: self.hash_algorithm.merkle_path(&step_leaves, index),
+                })
+                .collect();
+            proof.step_root = step_root;
+        }
+        if self.evaluation_openings > 0 {
+            let leaves: Vec<String> = proof
+                .constraint_evaluations
+                .iter()
+                .enumerate()
+                .map(|(i, &value)| self.hash_algorithm.evaluation_leaf(i, value))
+                .collect();
+            let root = self.hash_algorithm.merkle_root(&leaves);
+            proof.evaluation_queries =
+                query_indices(&challenge_seed(&proof.challenge, Some(&root), None), self.evaluation_openings, leaves.len())
+                    .into_iter()
+                    .map(|index| EvaluationOpening {
+                        index,
+                        value: proof.constraint_evaluations[index],
+                        path: self.hash_algorithm.merkle_path(&leaves, index),
+                    })
+                    .collect();
+            proof.evaluation_root = Some(root);
+        }
+        if self.include_constraint_annotations {
+            let annotated: Vec<&TraceStep> = if proof.sampled_steps.is_empty() {
+                trace.steps.iter().collect()
+            } else {
+                proof.sampled_steps.iter().map(|&i| &trace.steps[i]).collect()
+            };
+            proof.constraint_annotations = self.annotate_constraints(&annotated, &proof.constraint_evaluations);
+        }
+        progress(ProveProgress { phase: ProvePhase::Done, percent: 100 });
+        Ok(proof)
     }
 
     /// Commit to the trace by hashing it
-    fn commit_to_trace(&self, trace: &ProofTrace) -> String {
-        let trace_json = serde_json::to_string(&trace)
-            .expect("Failed to serialize trace");
-        hash_string(&trace_json)
+    pub fn commit_to_trace(&self, trace: &ProofTrace) -> String {
+        self.hash_algorithm.commit_trace(trace)
     }
 
     /// Evaluate constraint polynomials on the trace
-    fn evaluate_constraints(&self, trace: &ProofTrace) -> Vec<u64> {
-        trace
-            .steps
+    pub fn evaluate_constraints(&self, trace: &ProofTrace) -> Vec<u64> {
+        let mut evaluations = Vec::with_capacity(trace.steps.len() * self.constraints_per_step);
+        self.evaluate_constraints_into(trace, &mut evaluations);
+        evaluations
+    }
+
+    /// Evaluate constraints into a caller-provided buffer.
+    ///
+    /// The buffer is cleared and refilled, so reusing it across similarly
+    /// sized traces avoids allocating on every call.
+    pub fn evaluate_constraints_into(&self, trace: &ProofTrace, evaluations: &mut Vec<u64>) {
+        let modulus = self.constraint_modulus();
+        evaluations.clear();
+        // Grouped per constraint: all of constraint 0, then constraint 1, ...
+        for constraint in 0..self.constraints_per_step {
+            evaluations.extend(trace.steps.iter().map(|step| step.constraint_value(constraint, modulus)));
+        }
+    }
+
+    /// Evaluate every constraint on a subset of steps into `evaluations`,
+    /// grouped per constraint
+    fn evaluate_steps_into(&self, steps: &[&TraceStep], evaluations: &mut Vec<u64>) {
+        let modulus = self.constraint_modulus();
+        evaluations.clear();
+        for constraint in 0..self.constraints_per_step {
+            evaluations.extend(steps.iter().map(|step| step.constraint_value(constraint, modulus)));
+        }
+    }
+
+    /// Modulus that constraint evaluations are reduced into
+    fn constraint_modulus(&self) -> u64 {
+        1u64 << self.security_level.min(32)
+    }
+
+    /// Pair each trace step with its evaluated value for constraint 0
+    fn annotate_constraints(&self, steps: &[&TraceStep], evaluations: &[u64]) -> Vec<ConstraintAnnotation> {
+        let modulus = self.constraint_modulus();
+        steps
             .iter()
-            .map(|step| {
-                // Simple constraint: output should be consistent
-                step.output % (1u64 << self.security_level.min(32))
+            .zip(evaluations)
+            .map(|(step, &value)| ConstraintAnnotation {
+                step: step.step,
+                operation: step.operation.clone(),
+                value,
+                satisfied: crate::checks::constraint_satisfied(value, modulus, self.constraint_target),
             })
             .collect()
     }
 
-    /// Create FRI (Fast Reed-Solomon Interactive) proof layers
-    fn create_fri_layers(&self, evaluations: &[u64], challenge: &str) -> Vec<String> {
+    /// Create FRI (Fast Reed-Solomon Interactive) proof layers from the
+    /// evaluations in `current_evals`, calling `on_layer` with each layer's
+    /// index before building it. The buffer is used as the folding domain
+    /// and is left empty.
+    fn create_fri_layers(&self, current_evals: &mut Vec<u64>, challenge: &str, on_layer: &mut dyn FnMut(usize)) -> Vec<String> {
         let mut layers = Vec::new();
-        let mut current_evals = evaluations.to_vec();
+        // A degree-d constraint over n points needs a domain of d * n
+        // points: the evaluations repeated d times
+        let n = current_evals.len();
+        let domain_size = n * self.constraint_degree;
+        current_evals.truncate(domain_size);
+        while current_evals.len() < domain_size {
+            current_evals.extend_from_within(..n.min(domain_size - current_evals.len()));
+        }
 
-        // Generate 3 FRI layers
-        for layer_idx in 0..3 {
-            if current_evals.is_empty() {
-                break;
-            }
+        // Generate one FRI layer per domain size until a single value remains
+        while !current_evals.is_empty() {
+            let layer_idx = layers.len();
+            on_layer(layer_idx);
+            layers.push(self.fold_layer_in_place(current_evals, challenge, layer_idx));
+        }
 
-            // Hash the current evaluations with the challenge
-            let layer_data = format!("{}{}{}", 
-                current_evals.iter()
-                    .take(5)
-                    .map(|e| e.to_string())
-                    .collect::<Vec<_>>()
-                    .join(","),
-                challenge,
-                layer_idx
-            );
+        layers
+    }
 
-            let layer_hash = hash_string(&layer_data);
-            layers.push(layer_hash);
+    /// `HashAlgorithm::fold_layer` without allocating: hash the layer, then
+    /// fold `evaluations` into the next domain in place (emptying it once a
+    /// single value remains)
+    fn fold_layer_in_place(&self, evaluations: &mut Vec<u64>, challenge: &str, layer_idx: usize) -> String {
+        let hash = self.hash_algorithm.fri_layer_hash(evaluations, challenge, layer_idx);
+        if evaluations.len() > 1 {
+            crate::crypto::fold_in_place(evaluations, self.fold_factor.max(2));
+        } else {
+            evaluations.clear();
+        }
+        hash
+    }
 
-            // Halve the evaluations for the next layer (simulating folding)
-            current_evals = current_evals
-                .iter()
-                .step_by(2)
-                .copied()
-                .collect();
+    /// Serialize a proof into any writer (file, socket, buffer, stdout)
+    pub fn write_proof(&self, proof: &Proof, mut writer: impl Write, format: Format) -> std::io::Result<()> {
+        match format {
+            Format::Json if self.pretty => serde_json::to_writer_pretty(&mut writer, proof)?,
+            Format::Json => serde_json::to_writer(&mut writer, proof)?,
+            Format::Ndjson => {
+                serde_json::to_writer(&mut writer, proof)?;
+                writer.write_all(b"\n")?;
+            }
         }
+        writer.flush()
+    }
 
-        layers
+    /// Regenerate a current-format proof from a saved trace bundle.
+    ///
+    /// Only the computation name, result and trace are read strictly, so
+    /// bundles whose embedded proof predates the current format still load.
+    /// The result is the bundle's `result` (falling back to the trace's
+    /// claimed result for bundles without one); a disagreeing embedded
+    /// proof result is returned as a warning alongside the proof.
+    pub fn reprove_from_bundle(&self, filename: impl AsRef<Path>) -> Result<(Proof, Option<String>), Error> {
+        let json = std::fs::read_to_string(filename).map_err(LoadError::Io)?;
+        if json.trim().is_empty() {
+            return Err(LoadError::Empty.into());
+        }
+        let bundle: LegacyBundle = serde_json::from_str(&json).map_err(LoadError::Malformed)?;
+        let result = match bundle.result {
+            Some(result) => result,
+            None => bundle.trace.claimed_result().ok_or(ComputationError::MissingResult)?.into(),
+        };
+
+        let warning = bundle
+            .proof
+            .as_ref()
+            .and_then(|proof| proof.get("result"))
+            .and_then(|old_result| match serde_json::from_value::<ResultValue>(old_result.clone()) {
+                Ok(old_result) if old_result == result => None,
+                Ok(old_result) => Some(old_result.to_string()),
+                Err(_) => Some(old_result.to_string()),
+            })
+            .map(|old_result| format!("embedded proof claims result {} but the trace computes {}", old_result, result));
+
+        let proof = self.prove(bundle.computation, result, &bundle.trace)?;
+        Ok((proof, warning))
     }
 
-    /// Save proof to a JSON file
-    pub fn save_proof(&self, proof: &Proof, filename: &str) -> std::io::Result<()> {
-        let json = serde_json::to_string_pretty(&proof)?;
-        std::fs::write(filename, json)?;
-        Ok(())
+    /// Save proof to a JSON file, indented unless `with_pretty(false)`
+    ///
+    /// A directory path fails with `ErrorKind::IsADirectory` on every
+    /// platform instead of whatever the OS reports for it.
+    pub fn save_proof(&self, proof: &Proof, filename: impl AsRef<Path>) -> std::io::Result<()> {
+        let filename = filename.as_ref();
+        if filename.is_dir() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::IsADirectory,
+                format!("expected a file, found a directory: {}", filename.display()),
+            ));
+        }
+        let file = std::fs::File::create(filename)?;
+        self.write_proof(proof, std::io::BufWriter::new(file), Format::Json)
+    }
+
+    /// Save an archive to a JSON file, indented unless `with_pretty(false)`
+    pub fn save_archive(&self, archive: &ProofArchive, filename: impl AsRef<Path>) -> std::io::Result<()> {
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(filename)?);
+        if self.pretty {
+            serde_json::to_writer_pretty(&mut writer, archive)?;
+        } else {
+            serde_json::to_writer(&mut writer, archive)?;
+        }
+        writer.flush()
     }
 }
 
@@ -114,11 +834,146 @@ mod tests {
     use super::*;
     use crate::computation::fibonacci_with_trace;
 
+    #[test]
+    fn test_hash_algorithm_drives_commitments() {
+        let (result, trace) = fibonacci_with_trace(12).unwrap();
+        let verifier = STARKVerifier::new(128);
+        for &algorithm in HashAlgorithm::ALL {
+            let prover = STARKProver::new(128)
+                .with_hash_algorithm(algorithm)
+                .with_num_queries(4)
+                .with_evaluation_openings(4)
+                .with_hash_chain(HashChainMode::Full);
+            let proof = prover.prove("fibonacci", result, &trace).unwrap();
+            assert_eq!(proof.trace_commitment, algorithm.commit_trace(&trace));
+            assert_eq!(proof.trace_chain_links, algorithm.hash_chain(&trace.steps));
+            assert_eq!(proof.fri_layers[0], algorithm.fri_layer_hash(&proof.constraint_evaluations, &proof.challenge, 0));
+            assert!(verifier.verify(&proof).valid, "{} proof should verify", algorithm.name());
+            assert!(verifier.verify_trace_binding(&proof, &trace));
+            assert!(verifier.verify_hash_chain(&proof, &trace));
+        }
+    }
+
+    #[test]
+    fn test_fold_layer_reproduces_fri_layers() {
+        let (result, trace) = fibonacci_with_trace(20).unwrap();
+        for (degree, fold_factor) in [(1, 2), (2, 3)] {
+            let prover = STARKProver::new(128).with_constraint_degree(degree).with_fold_factor(fold_factor);
+            let proof = prover.prove("fibonacci", result, &trace).unwrap();
+
+            let evaluations = &proof.constraint_evaluations;
+            let mut domain: Vec<u64> = evaluations.iter().copied().cycle().take(evaluations.len() * degree).collect();
+            let mut layers = Vec::new();
+            while !domain.is_empty() {
+                let (layer, next) = HashAlgorithm::Sha256.fold_layer(&domain, &proof.challenge, layers.len(), fold_factor);
+                layers.push(layer);
+                domain = next;
+            }
+            assert_eq!(layers, proof.fri_layers);
+        }
+
+        // The free function folds at the default factor
+        let proof = STARKProver::new(128).prove("fibonacci", result, &trace).unwrap();
+        let mut domain = proof.constraint_evaluations.clone();
+        let mut layers = Vec::new();
+        while !domain.is_empty() {
+            let (layer, next) = crate::crypto::fold_layer(&domain, &proof.challenge, layers.len());
+            assert_eq!(next.len(), domain.len().div_ceil(DEFAULT_FOLD_FACTOR) * usize::from(domain.len() > 1));
+            layers.push(layer);
+            domain = next;
+        }
+        assert_eq!(layers, proof.fri_layers);
+    }
+
+    #[test]
+    fn test_secure_prover_meets_recommended_level() {
+        use crate::types::{BITS_PER_QUERY, RECOMMENDED_SECURITY_BITS};
+
+        let verifier = STARKVerifier::new(RECOMMENDED_SECURITY_BITS);
+        assert_eq!(STARKProver::secure().num_queries() as u32 * BITS_PER_QUERY, RECOMMENDED_SECURITY_BITS);
+
+        // Traces shorter than the query count are opened in full and
+        // credited one query per step
+        for n in [10, 80] {
+            let (result, trace) = fibonacci_with_trace(n).unwrap();
+            let proof = STARKProver::secure().prove("fibonacci", result, &trace).unwrap();
+            let report = verifier.verify(&proof);
+            assert!(report.valid, "{:?}", report.checks_failed);
+            assert_eq!(proof.fri_queries.len(), proof.num_steps.min(STARKProver::secure().num_queries()));
+            let expected = (proof.num_steps as u32 * BITS_PER_QUERY).min(RECOMMENDED_SECURITY_BITS);
+            assert_eq!(proof.effective_security_bits(), expected, "fibonacci({})", n);
+        }
+
+        let (result, trace) = fibonacci_with_trace(80).unwrap();
+        let proof = STARKProver::secure().prove("fibonacci", result, &trace).unwrap();
+        assert_eq!(proof.effective_security_bits(), RECOMMENDED_SECURITY_BITS);
+
+        // The openings are what earn the level: the same proof without them earns nothing
+        let proof = STARKProver::new(RECOMMENDED_SECURITY_BITS).prove("fibonacci", result, &trace).unwrap();
+        assert_eq!(proof.effective_security_bits(), 0);
+    }
+
+    #[test]
+    fn test_prove_computation_returns_trace() {
+        let prover = STARKProver::new(128);
+        for computation in [&computation::Fibonacci as &dyn computation::Computation, &computation::Factorial] {
+            let (proof, trace) = prover.prove_computation(computation, 10).unwrap();
+            assert_eq!(proof.computation, computation.info().name);
+            assert_eq!(crate::crypto::commit_trace(&trace), proof.trace_commitment);
+            assert!(STARKVerifier::new(128).verify(&proof).valid);
+        }
+        assert!(matches!(
+            prover.prove_computation(&computation::Factorial, 21),
+            Err(ComputationError::InputTooLarge { max: 20 })
+        ));
+    }
+
+    #[test]
+    fn test_save_proof_rejects_directory() {
+        let (result, trace) = fibonacci_with_trace(5).unwrap();
+        let prover = STARKProver::new(128);
+        let proof = prover.prove("fibonacci", result, &trace).unwrap();
+        let dir = std::env::temp_dir();
+
+        let err = prover.save_proof(&proof, dir.to_str().unwrap()).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::IsADirectory);
+        assert!(err.to_string().contains("expected a file, found a directory"));
+    }
+
+    #[test]
+    fn test_prove_from_reader() {
+        let (result, trace) = fibonacci_with_trace(15).unwrap();
+        let json = serde_json::to_vec(&trace).unwrap();
+
+        let proof = STARKProver::new(128).prove_from_reader("fibonacci", json.as_slice()).unwrap();
+        assert_eq!(proof.result, result);
+        assert!(STARKVerifier::new(128).is_valid(&proof));
+    }
+
+    #[test]
+    fn test_prove_from_reader_distinguishes_empty_and_malformed() {
+        use crate::error::LoadError;
+
+        let prover = STARKProver::new(128);
+        assert!(matches!(
+            prover.prove_from_reader("fibonacci", " \n".as_bytes()),
+            Err(Error::Load(LoadError::Empty))
+        ));
+        assert!(matches!(
+            prover.prove_from_reader("fibonacci", "{\"steps\": [".as_bytes()),
+            Err(Error::Load(LoadError::Malformed(_)))
+        ));
+        assert!(matches!(
+            prover.prove_from_reader("fibonacci", r#"{"steps": [], "inputs": {}, "outputs": {}}"#.as_bytes()),
+            Err(Error::Computation(ComputationError::MissingResult))
+        ));
+    }
+
     #[test]
     fn test_prove_fibonacci() {
         let prover = STARKProver::new(128);
-        let (result, trace) = fibonacci_with_trace(10);
-        let proof = prover.prove("fibonacci", result, &trace);
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let proof = prover.prove("fibonacci", result, &trace).unwrap();
 
         assert_eq!(proof.result, 55);
         assert_eq!(proof.computation, "fibonacci");
@@ -128,10 +983,471 @@ mod tests {
         assert!(!proof.fri_layers.is_empty());
     }
 
+    #[test]
+    fn test_prove_without_constraints_fails() {
+        let prover = STARKProver::new(128);
+        let mut trace = ProofTrace::new();
+        trace.set_input("n", 0);
+
+        let err = prover.prove("fibonacci", 0u64, &trace).unwrap_err();
+        assert_eq!(err, ComputationError::NoConstraints);
+    }
+
+    #[test]
+    fn test_constraint_annotations_line_up_with_steps() {
+        let prover = STARKProver::new(128).with_constraint_annotations(true);
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let proof = prover.prove("fibonacci", result, &trace).unwrap();
+
+        assert_eq!(proof.constraint_annotations.len(), trace.steps.len());
+        for ((annotation, step), &value) in proof
+            .constraint_annotations
+            .iter()
+            .zip(&trace.steps)
+            .zip(&proof.constraint_evaluations)
+        {
+            assert_eq!(annotation.step, step.step);
+            assert_eq!(annotation.operation, step.operation);
+            assert_eq!(annotation.value, value);
+            assert!(annotation.satisfied);
+        }
+
+        let plain = STARKProver::new(128).prove("fibonacci", result, &trace).unwrap();
+        assert!(plain.constraint_annotations.is_empty());
+    }
+
+    #[test]
+    fn test_constraint_annotations_use_target() {
+        let prover = STARKProver::new(128).with_constraint_annotations(true).with_constraint_target(1);
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let proof = prover.prove("fibonacci", result, &trace).unwrap();
+
+        for annotation in &proof.constraint_annotations {
+            assert_eq!(annotation.satisfied, annotation.value == 1);
+        }
+        let violated = proof.constraint_annotations.iter().find(|a| !a.satisfied).unwrap();
+        assert!(violated.to_string().ends_with("(violated)"), "{}", violated);
+        assert!(proof.constraint_annotations.iter().any(|a| a.satisfied));
+    }
+
+    #[test]
+    fn test_cached_commitment_matches_fresh() {
+        let (result, trace) = fibonacci_with_trace(20).unwrap();
+        let commitment = STARKProver::new(128).commit_to_trace(&trace);
+
+        for security in [80, 128, 256] {
+            let prover = STARKProver::new(security);
+            let fresh = prover.prove("fibonacci", result, &trace).unwrap();
+            let cached = prover
+                .prove_with_commitment("fibonacci", result, &trace, commitment.clone())
+                .unwrap();
+
+            assert_eq!(prover.commit_to_trace(&trace), commitment);
+            assert_eq!(cached.trace_commitment, fresh.trace_commitment);
+            assert_eq!(cached.challenge, fresh.challenge);
+            assert_eq!(cached.fri_layers, fresh.fri_layers);
+        }
+
+        // A salted prover never reuses the unsalted commitment
+        let salted = STARKProver::new(128)
+            .with_commitment_salt(true)
+            .prove_with_commitment("fibonacci", result, &trace, commitment.clone())
+            .unwrap();
+        assert!(salted.commitment_salt.is_some());
+        assert_ne!(salted.trace_commitment, commitment);
+        assert!(STARKVerifier::new(128).verify(&salted).valid);
+    }
+
+    #[test]
+    fn test_write_proof_round_trip() {
+        let prover = STARKProver::new(128);
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let proof = prover.prove("fibonacci", result, &trace).unwrap();
+
+        for format in [Format::Json, Format::Ndjson] {
+            let mut buffer = Vec::new();
+            prover.write_proof(&proof, &mut buffer, format).unwrap();
+
+            let decoded: Proof = serde_json::from_slice(&buffer).unwrap();
+            assert_eq!(decoded.trace_commitment, proof.trace_commitment);
+            assert_eq!(decoded.constraint_evaluations, proof.constraint_evaluations);
+            assert_eq!(decoded.fri_layers, proof.fri_layers);
+            assert_eq!(decoded.result, proof.result);
+        }
+
+        let mut line = Vec::new();
+        prover.write_proof(&proof, &mut line, Format::Ndjson).unwrap();
+        assert_eq!(line.iter().filter(|&&b| b == b'\n').count(), 1);
+        assert_eq!(line.last(), Some(&b'\n'));
+    }
+
+    #[test]
+    fn test_compact_json_round_trip() {
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let proof = STARKProver::new(128).prove("fibonacci", result, &trace).unwrap();
+
+        let mut pretty = Vec::new();
+        STARKProver::new(128).write_proof(&proof, &mut pretty, Format::Json).unwrap();
+        let mut compact = Vec::new();
+        STARKProver::new(128).with_pretty(false).write_proof(&proof, &mut compact, Format::Json).unwrap();
+        assert!(compact.len() < pretty.len());
+        assert!(!compact.contains(&b'\n'));
+
+        let from_pretty = Proof::from_json(std::str::from_utf8(&pretty).unwrap()).unwrap();
+        let from_compact = Proof::from_json(std::str::from_utf8(&compact).unwrap()).unwrap();
+        assert_eq!(serde_json::to_value(&from_pretty).unwrap(), serde_json::to_value(&from_compact).unwrap());
+        assert_eq!(serde_json::to_value(&from_compact).unwrap(), serde_json::to_value(&proof).unwrap());
+        assert!(crate::STARKVerifier::new(128).verify(&from_compact).valid);
+    }
+
+    #[test]
+    fn test_fixed_oracle_challenge() {
+        use crate::crypto::FixedOracle;
+
+        let digest = "f".repeat(64);
+        let prover = STARKProver::new(128).with_oracle(FixedOracle::new(digest.clone()));
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let proof = prover.prove("fibonacci", result, &trace).unwrap();
+
+        assert_eq!(proof.challenge, "f".repeat(32));
+    }
+
+    #[test]
+    fn test_prove_rejects_oversized_input() {
+        let prover = STARKProver::new(128);
+        let (result, mut trace) = fibonacci_with_trace(10).unwrap();
+        trace.set_input("n", 150);
+
+        let err = prover.prove("fibonacci", result, &trace).unwrap_err();
+        assert_eq!(err, ComputationError::InputTooLarge { max: 100 });
+    }
+
+    #[test]
+    fn test_fri_layers_follow_fold_schedule() {
+        use crate::crypto::fri_layer_count;
+
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        for fold_factor in [2, 3, 4] {
+            let prover = STARKProver::new(128).with_fold_factor(fold_factor);
+            let proof = prover.prove("fibonacci", result, &trace).unwrap();
+
+            assert_eq!(proof.fri_fold_factor, fold_factor);
+            assert_eq!(
+                proof.fri_layers.len(),
+                fri_layer_count(proof.constraint_evaluations.len(), fold_factor)
+            );
+        }
+    }
+
+    #[test]
+    fn test_reprove_from_bundle() {
+        use crate::types::TraceBundle;
+
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let bundle = TraceBundle::new("fibonacci", result, trace, None);
+        let mut json = serde_json::to_value(&bundle).unwrap();
+        // An embedded proof in some older, incompatible format
+        json["proof"] = serde_json::json!({ "version": "0.1", "result": 54, "layers": 2 });
+
+        let path = std::env::temp_dir().join(format!("stark_reprove_{}.json", std::process::id()));
+        std::fs::write(&path, json.to_string()).unwrap();
+
+        let (proof, warning) = STARKProver::new(128).reprove_from_bundle(path.to_str().unwrap()).unwrap();
+        assert_eq!(warning.as_deref(), Some("embedded proof claims result 54 but the trace computes 55"));
+
+        json["proof"]["result"] = serde_json::json!(55);
+        std::fs::write(&path, json.to_string()).unwrap();
+        let (_, warning) = STARKProver::new(128).reprove_from_bundle(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(warning, None);
+
+        assert_eq!(proof.result, 55);
+        assert_eq!(proof.version, "1.0");
+        assert!(crate::verifier::STARKVerifier::new(128).verify(&proof).valid);
+    }
+
+    #[test]
+    fn test_reprove_from_bundle_non_u64_results() {
+        use crate::types::TraceBundle;
+
+        let prover = STARKProver::new(128);
+        let (digest, trace) = computation::hash_computation_with_trace(7);
+        let digest_proof = prover.prove("hash_computation", ResultValue::Digest(digest.clone()), &trace).unwrap();
+        let digest_bundle = TraceBundle::new("hash_computation", ResultValue::Digest(digest), trace, Some(digest_proof));
+        let (negative, trace) = computation::signed_linear_recurrence_with_trace(&[-2], &[3], 3).unwrap();
+        assert_eq!(negative, -24);
+        let negative_proof = prover.prove("signed_linear_recurrence", negative, &trace).unwrap();
+        let negative_bundle = TraceBundle::new("signed_linear_recurrence", negative, trace, Some(negative_proof));
+
+        let path = std::env::temp_dir().join(format!("stark_reprove_results_{}.json", std::process::id()));
+        for bundle in [digest_bundle, negative_bundle] {
+            bundle.save(&path).unwrap();
+            let (proof, warning) = prover.reprove_from_bundle(&path).unwrap();
+            assert_eq!(warning, None, "{}", bundle.computation);
+            assert_eq!(proof.result, bundle.result);
+            let report = crate::verifier::STARKVerifier::new(128).verify(&proof);
+            assert!(report.valid, "{}: {:?}", bundle.computation, report.checks_failed);
+        }
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_prove_verified() {
+        let prover = STARKProver::new(128);
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+
+        let proof = prover
+            .prove_verified("fibonacci", result, &trace, &STARKVerifier::new(128))
+            .unwrap();
+        assert_eq!(proof.result, 55);
+
+        // A verifier demanding more security than the prover provides
+        let failure = prover
+            .prove_verified("fibonacci", result, &trace, &STARKVerifier::new(256))
+            .unwrap_err();
+        assert!(!failure.valid);
+        assert!(failure.checks_failed.iter().any(|c| c.contains("Insufficient security")));
+
+        let failure = prover
+            .prove_verified("fibonacci", 0u64, &ProofTrace::new(), &STARKVerifier::new(128))
+            .unwrap_err();
+        assert_eq!(failure.message, "Proof generation failed");
+    }
+
+    #[test]
+    fn test_padding_to_power_of_two() {
+        use crate::crypto::fri_layer_count;
+
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        assert!(!trace.steps.len().is_power_of_two());
+
+        let prover = STARKProver::new(128).with_padding(PaddingMode::Zero);
+        let proof = prover.prove("fibonacci", result, &trace).unwrap();
+        let padding = proof.padding.unwrap();
+
+        assert_eq!(padding.original_len, trace.steps.len());
+        assert_eq!(padding.padded_len, trace.steps.len().next_power_of_two());
+        assert_eq!(proof.constraint_evaluations.len(), padding.padded_len);
+        assert_eq!(proof.unpadded_evaluations().len(), trace.steps.len());
+        assert_eq!(proof.fri_layers.len(), fri_layer_count(padding.padded_len, 2));
+        assert!(STARKVerifier::new(128).verify(&proof).valid);
+    }
+
+    #[test]
+    fn test_buffered_constraint_evaluation() {
+        let prover = STARKProver::new(128);
+        let (_, trace) = fibonacci_with_trace(20).unwrap();
+        let (_, smaller) = fibonacci_with_trace(15).unwrap();
+
+        let mut buffer = Vec::new();
+        prover.evaluate_constraints_into(&trace, &mut buffer);
+        assert_eq!(buffer, prover.evaluate_constraints(&trace));
+
+        // Refilling with a trace that fits reuses the same allocation
+        let ptr = buffer.as_ptr();
+        prover.evaluate_constraints_into(&smaller, &mut buffer);
+        assert_eq!(buffer, prover.evaluate_constraints(&smaller));
+        assert_eq!(buffer.as_ptr(), ptr);
+    }
+
+    #[test]
+    fn test_prove_many() {
+        let prover = STARKProver::new(128);
+        let (r5, t5) = fibonacci_with_trace(5).unwrap();
+        let (r8, t8) = fibonacci_with_trace(8).unwrap();
+
+        let empty = ProofTrace::new();
+        // Larger trace first, so a stale shared buffer would show up
+        let proofs = prover.prove_many(&[
+            ("fibonacci", r8, &t8),
+            ("fibonacci", r5, &t5),
+            ("fibonacci", 0, &empty),
+        ]);
+        assert_eq!(proofs.len(), 3);
+        assert_eq!(proofs[0].as_ref().unwrap().result, 21);
+        assert_eq!(proofs[1].as_ref().unwrap().result, 5);
+        assert_eq!(proofs[2].as_ref().unwrap_err(), &ComputationError::NoConstraints);
+
+        let single = prover.prove("fibonacci", r5, &t5).unwrap();
+        assert_eq!(proofs[1].as_ref().unwrap().constraint_evaluations, single.constraint_evaluations);
+        assert_eq!(proofs[1].as_ref().unwrap().fri_layers, single.fri_layers);
+    }
+
+    #[test]
+    fn test_prove_many_until_stops_when_cancelled() {
+        // An oracle that requests cancellation while the first job is proved
+        struct CancellingOracle(CancellationToken);
+        impl RandomOracle for CancellingOracle {
+            fn query(&self, input: &str) -> String {
+                self.0.cancel();
+                Sha256Oracle.query(input)
+            }
+        }
+
+        let (r5, t5) = fibonacci_with_trace(5).unwrap();
+        let jobs = [("fibonacci", r5, &t5), ("fibonacci", r5, &t5), ("fibonacci", r5, &t5)];
+
+        let cancel = CancellationToken::new();
+        let prover = STARKProver::new(128).with_oracle(CancellingOracle(cancel.clone()));
+        let proofs = prover.prove_many_until(&jobs, &cancel);
+        assert_eq!(proofs.len(), 1);
+        assert!(proofs[0].is_ok());
+
+        assert!(STARKProver::new(128).prove_many_until(&jobs, &cancel).is_empty());
+        assert_eq!(STARKProver::new(128).prove_many_until(&jobs, &CancellationToken::new()).len(), 3);
+    }
+
+    #[test]
+    fn test_prove_with_progress_reports_every_phase() {
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let prover = STARKProver::new(128);
+        let mut events = Vec::new();
+        let proof = prover.prove_with_progress("fibonacci", result, &trace, |event| events.push(event)).unwrap();
+
+        let phases: Vec<ProvePhase> = events.iter().map(|event| event.phase).collect();
+        let mut expected = vec![ProvePhase::Committing, ProvePhase::EvaluatingConstraints];
+        expected.extend((0..proof.fri_layers.len()).map(|layer| ProvePhase::Folding { layer }));
+        expected.push(ProvePhase::Done);
+        assert_eq!(phases, expected);
+
+        let percents: Vec<u8> = events.iter().map(|event| event.percent).collect();
+        assert!(percents.windows(2).all(|pair| pair[0] < pair[1]), "{:?}", percents);
+        assert_eq!((percents[0], percents[percents.len() - 1]), (0, 100));
+
+        // Progress reporting does not change the proof
+        let plain = prover.prove("fibonacci", result, &trace).unwrap();
+        assert_eq!(plain.fri_layers, proof.fri_layers);
+    }
+
+    #[test]
+    fn test_commitment_only_proof() {
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let proof = STARKProver::new(128).prove_commitment("fibonacci", result, &trace).unwrap();
+        assert!(proof.commitment_only && proof.fri_layers.is_empty());
+
+        let lenient = STARKVerifier::new(128).with_commitment_only_allowed(true);
+        let accepted = lenient.verify(&proof);
+        assert!(accepted.valid, "{:?}", accepted.checks_failed);
+        assert!(accepted.warnings.iter().any(|w| w.contains("FRI was skipped")));
+        assert!(lenient.verify_trace_binding(&proof, &trace));
+
+        // The commitment and result are still checked
+        let mut wrong_result = proof.clone();
+        wrong_result.result = ResultValue::U64(result + 1);
+        assert!(!lenient.is_valid(&wrong_result));
+
+        let strict = STARKVerifier::new(128);
+        let rejected = strict.verify(&proof);
+        assert!(!rejected.valid && !strict.is_valid(&proof));
+        assert_eq!(rejected.checks_failed, vec!["Commitment-only proof has no FRI layers".to_string()]);
+    }
+
+    #[test]
+    fn test_interactive_challenge_mode() {
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let interactive = STARKProver::new(128).with_interactive_challenge(7);
+        let first = interactive.prove("fibonacci", result, &trace).unwrap();
+        let second = interactive.prove("fibonacci", result, &trace).unwrap();
+        let fiat_shamir = STARKProver::new(128).prove("fibonacci", result, &trace).unwrap();
+
+        assert_eq!(first.interactive_seed, Some(7));
+        assert_eq!(first.challenge, second.challenge);
+        assert_ne!(first.challenge, fiat_shamir.challenge);
+        assert_eq!(fiat_shamir.interactive_seed, None);
+
+        // The default verifier does not accept a prover-chosen seed
+        let strict = STARKVerifier::new(128);
+        let report = strict.verify(&first);
+        assert!(!report.valid);
+        assert!(report.checks_failed.iter().any(|c| c.contains("interactive challenges not allowed")));
+        assert!(!strict.is_valid(&first));
+
+        let verifier = STARKVerifier::new(128).with_interactive_challenges_allowed(true);
+        let report = verifier.verify(&first);
+        assert!(report.valid, "{:?}", report.checks_failed);
+        assert!(report.checks_passed.iter().any(|c| c.contains("not Fiat-Shamir")));
+
+        // The recorded seed must be the one that drew the challenge
+        let mut reseeded = first.clone();
+        reseeded.interactive_seed = Some(8);
+        assert!(!verifier.is_valid(&reseeded));
+    }
+
+    #[test]
+    fn test_constraint_degree_adds_fri_layers() {
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let linear = STARKProver::new(128).prove("fibonacci", result, &trace).unwrap();
+        let quartic_prover = STARKProver::new(128).with_constraint_degree(4);
+        let quartic = quartic_prover.prove("fibonacci", result, &trace).unwrap();
+
+        assert_eq!(quartic_prover.constraint_degree(), 4);
+        assert_eq!(quartic.constraint_degree, 4);
+        assert_eq!(quartic.fri_layers.len(), linear.fri_layers.len() + 2);
+
+        let verifier = STARKVerifier::new(128);
+        assert!(verifier.verify(&quartic).valid);
+
+        // Claiming a higher degree without the extra layers is flagged
+        let mut understated = linear.clone();
+        understated.constraint_degree = 4;
+        let report = verifier.verify(&understated);
+        assert!(!report.valid);
+        assert!(report.checks_failed.iter().any(|c| c.contains("Too few FRI layers")));
+    }
+
+    #[test]
+    fn test_prove_with_timeout() {
+        use crate::computation::nth_prime_with_trace;
+        use std::time::Duration;
+
+        let prover = STARKProver::new(128);
+        let proof = prover
+            .prove_with_timeout("nth_prime", |cancel| nth_prime_with_trace(10, cancel), Duration::from_secs(30))
+            .unwrap();
+        assert_eq!(proof.result, 29);
+        assert!(STARKVerifier::new(128).verify(&proof).valid);
+
+        let slow = |cancel: &CancellationToken| {
+            std::thread::sleep(Duration::from_millis(200));
+            nth_prime_with_trace(10, cancel)
+        };
+        assert_eq!(
+            prover.prove_with_timeout("nth_prime", slow, Duration::from_millis(1)).unwrap_err(),
+            ComputationError::Timeout { limit_ms: 1 }
+        );
+    }
+
+    #[test]
+    fn test_in_place_folding_preserves_layers() {
+        // The layers as computed before folding was done in place
+        fn allocating_layers(evaluations: &[u64], challenge: &str, fold_factor: usize) -> Vec<String> {
+            let mut layers = Vec::new();
+            let mut current: Vec<u64> = evaluations.to_vec();
+            for layer_idx in 0.. {
+                let head: Vec<String> = current.iter().take(5).map(|e| e.to_string()).collect();
+                layers.push(crate::crypto::hash_string(&format!("{}{}{}", head.join(","), challenge, layer_idx)));
+                if current.len() == 1 {
+                    break;
+                }
+                current = current.iter().step_by(fold_factor).copied().collect();
+            }
+            layers
+        }
+
+        let evaluations: Vec<u64> = (0..1000).map(|i| i * 7919 % 65_536).collect();
+        for fold_factor in [2, 3, 4] {
+            let prover = STARKProver::new(128).with_fold_factor(fold_factor);
+            assert_eq!(
+                prover.create_fri_layers(&mut evaluations.clone(), "challenge", &mut |_| {}),
+                allocating_layers(&evaluations, "challenge", fold_factor)
+            );
+        }
+    }
+
     #[test]
     fn test_trace_commitment() {
         let prover = STARKProver::new(128);
-        let (_, trace) = fibonacci_with_trace(5);
+        let (_, trace) = fibonacci_with_trace(5).unwrap();
         let commitment = prover.commit_to_trace(&trace);
 
         // Should be a valid SHA-256 hash (64 hex characters)
@@ -1,9 +1,12 @@
 // src/types.rs - Data structures for proofs and verification
+use crate::error::{ComputationError, LoadError};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::time::Duration;
 
 /// A single step in the computation trace
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TraceStep {
     pub step: usize,
     pub operation: String,
@@ -12,12 +15,53 @@ pub struct TraceStep {
     pub depth: usize,
 }
 
+impl TraceStep {
+    /// A step of a computation over signed integers.
+    ///
+    /// Negative values are stored in two's complement, so `input` and
+    /// `output` hold `value as u64`; read them back with `signed_input` and
+    /// `signed_output`. Constraint moduli are powers of two, which makes
+    /// reducing the stored value the same as reducing the signed one.
+    pub fn signed(step: usize, operation: impl Into<String>, input: i64, output: i64, depth: usize) -> Self {
+        TraceStep {
+            step,
+            operation: operation.into(),
+            input: input as u64,
+            output: output as u64,
+            depth,
+        }
+    }
+
+    /// The input of a step created with `TraceStep::signed`
+    pub fn signed_input(&self) -> i64 {
+        self.input as i64
+    }
+
+    /// The output of a step created with `TraceStep::signed`
+    pub fn signed_output(&self) -> i64 {
+        self.output as i64
+    }
+
+    /// Value of constraint `constraint` at this step, reduced into `modulus`.
+    ///
+    /// Constraint 0 is the step's output; constraint `j` adds `j` times the
+    /// step's input, so each constraint binds a different combination of
+    /// the step's columns.
+    pub fn constraint_value(&self, constraint: usize, modulus: u64) -> u64 {
+        let weight = constraint as u64;
+        self.output.wrapping_add(weight.wrapping_mul(self.input)) % modulus
+    }
+}
+
 /// The complete computation trace
+///
+/// Inputs and outputs are kept sorted so the trace always serializes (and
+/// therefore commits) identically.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProofTrace {
     pub steps: Vec<TraceStep>,
-    pub inputs: HashMap<String, u64>,
-    pub outputs: HashMap<String, u64>,
+    pub inputs: BTreeMap<String, u64>,
+    pub outputs: BTreeMap<String, u64>,
 }
 
 impl ProofTrace {
@@ -25,8 +69,8 @@ impl ProofTrace {
     pub fn new() -> Self {
         ProofTrace {
             steps: Vec::new(),
-            inputs: HashMap::new(),
-            outputs: HashMap::new(),
+            inputs: BTreeMap::new(),
+            outputs: BTreeMap::new(),
         }
     }
 
@@ -35,15 +79,182 @@ impl ProofTrace {
         self.steps.push(step);
     }
 
-    /// Set an input value
+    /// Set an input value, overwriting any previous value for the key
     pub fn set_input(&mut self, name: impl Into<String>, value: u64) {
         self.inputs.insert(name.into(), value);
     }
 
+    /// Set an input value, refusing to overwrite an existing key
+    pub fn try_set_input(&mut self, name: impl Into<String>, value: u64) -> Result<(), ComputationError> {
+        match self.inputs.entry(name.into()) {
+            std::collections::btree_map::Entry::Occupied(entry) => {
+                Err(ComputationError::DuplicateInput { key: entry.key().clone() })
+            }
+            std::collections::btree_map::Entry::Vacant(entry) => {
+                entry.insert(value);
+                Ok(())
+            }
+        }
+    }
+
+    /// Set a signed input value (stored in two's complement), refusing to
+    /// overwrite an existing key
+    pub fn try_set_signed_input(&mut self, name: impl Into<String>, value: i64) -> Result<(), ComputationError> {
+        self.try_set_input(name, value as u64)
+    }
+
+    /// A signed input value set with `try_set_signed_input`
+    pub fn signed_input(&self, name: &str) -> Option<i64> {
+        self.inputs.get(name).map(|&value| value as i64)
+    }
+
     /// Set an output value
     pub fn set_output(&mut self, name: impl Into<String>, value: u64) {
         self.outputs.insert(name.into(), value);
     }
+
+    /// Set a signed output value, stored in two's complement
+    pub fn set_signed_output(&mut self, name: impl Into<String>, value: i64) {
+        self.set_output(name, value as u64);
+    }
+
+    /// Append the steps of `other`, e.g. a sub-computation's trace.
+    ///
+    /// Appended steps are renumbered to follow on from this trace and their
+    /// depth is increased by `offset_depth`. Inputs and outputs are merged;
+    /// a key present in both traces is an error and leaves `self` unchanged.
+    pub fn append(&mut self, other: ProofTrace, offset_depth: usize) -> Result<(), ComputationError> {
+        if let Some(key) = other.inputs.keys().find(|key| self.inputs.contains_key(*key)) {
+            return Err(ComputationError::DuplicateInput { key: key.clone() });
+        }
+        if let Some(key) = other.outputs.keys().find(|key| self.outputs.contains_key(*key)) {
+            return Err(ComputationError::DuplicateOutput { key: key.clone() });
+        }
+
+        let base = self.steps.len();
+        self.steps.extend(other.steps.into_iter().enumerate().map(|(i, step)| TraceStep {
+            step: base + i,
+            depth: step.depth + offset_depth,
+            ..step
+        }));
+        self.inputs.extend(other.inputs);
+        self.outputs.extend(other.outputs);
+        Ok(())
+    }
+
+    /// The steps whose operation is `op`, in trace order
+    pub fn steps_by_operation<'a>(&'a self, op: &'a str) -> impl Iterator<Item = &'a TraceStep> + 'a {
+        self.steps.iter().filter(move |step| step.operation == op)
+    }
+
+    /// Index of the first `memo_lookup` step whose output differs from the
+    /// earliest earlier `fib_compute` step with the same input, or that
+    /// looks up an input no earlier step computed. `None` when every
+    /// lookup returns the memoized value.
+    pub fn first_inconsistent_memo_lookup(&self) -> Option<usize> {
+        let mut memo: HashMap<u64, u64> = HashMap::new();
+        for (index, step) in self.steps.iter().enumerate() {
+            match step.operation.as_str() {
+                "fib_compute" => {
+                    memo.entry(step.input).or_insert(step.output);
+                }
+                "memo_lookup" if memo.get(&step.input) != Some(&step.output) => return Some(index),
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Read a trace serialized as JSON, e.g. piped in from another tool.
+    ///
+    /// Input that is empty (or only whitespace) fails with
+    /// `LoadError::Empty`, anything else that does not parse with
+    /// `LoadError::Malformed`.
+    pub fn from_reader(mut reader: impl std::io::Read) -> Result<ProofTrace, LoadError> {
+        let mut json = String::new();
+        reader.read_to_string(&mut json)?;
+        if json.trim().is_empty() {
+            return Err(LoadError::Empty);
+        }
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    /// The result recorded by the computation: the `result` output if set,
+    /// otherwise the output of the last step
+    pub fn claimed_result(&self) -> Option<u64> {
+        self.outputs
+            .get("result")
+            .copied()
+            .or_else(|| self.steps.last().map(|step| step.output))
+    }
+
+    /// Compare this trace against another one
+    pub fn compare(&self, other: &ProofTrace) -> TraceDiff {
+        let first_differing_step = self
+            .steps
+            .iter()
+            .zip(&other.steps)
+            .position(|(a, b)| a != b)
+            .or_else(|| {
+                (self.steps.len() != other.steps.len())
+                    .then(|| self.steps.len().min(other.steps.len()))
+            });
+
+        TraceDiff {
+            first_differing_step,
+            step_count_delta: other.steps.len() as i64 - self.steps.len() as i64,
+            differing_inputs: differing_keys(&self.inputs, &other.inputs),
+            differing_outputs: differing_keys(&self.outputs, &other.outputs),
+        }
+    }
+}
+
+/// Keys whose values differ (or which exist on only one side), sorted
+fn differing_keys(a: &BTreeMap<String, u64>, b: &BTreeMap<String, u64>) -> Vec<String> {
+    let mut keys: Vec<String> = a
+        .keys()
+        .chain(b.keys())
+        .filter(|key| a.get(*key) != b.get(*key))
+        .cloned()
+        .collect();
+    keys.sort();
+    keys.dedup();
+    keys
+}
+
+/// Differences between two computation traces
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceDiff {
+    /// Index of the first step that differs, or where the shorter trace ends
+    pub first_differing_step: Option<usize>,
+    /// Step count of the other trace minus this one
+    pub step_count_delta: i64,
+    pub differing_inputs: Vec<String>,
+    pub differing_outputs: Vec<String>,
+}
+
+impl TraceDiff {
+    /// True when the traces have identical steps, inputs and outputs
+    pub fn is_identical(&self) -> bool {
+        self.first_differing_step.is_none()
+            && self.differing_inputs.is_empty()
+            && self.differing_outputs.is_empty()
+    }
+}
+
+impl std::fmt::Display for TraceDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_identical() {
+            return write!(f, "Traces are identical");
+        }
+        match self.first_differing_step {
+            Some(step) => writeln!(f, "First differing step: {}", step)?,
+            None => writeln!(f, "All steps match")?,
+        }
+        writeln!(f, "Step count delta: {:+}", self.step_count_delta)?;
+        writeln!(f, "Differing inputs: [{}]", self.differing_inputs.join(", "))?;
+        write!(f, "Differing outputs: [{}]", self.differing_outputs.join(", "))
+    }
 }
 
 impl Default for ProofTrace {
@@ -52,25 +263,297 @@ impl Default for ProofTrace {
     }
 }
 
+/// A computation result, wide enough for values beyond `u64`.
+///
+/// `U64` serializes as a plain JSON number so existing proofs keep their
+/// format; wider values serialize as `{"u128": "..."}` or `{"big": "..."}`
+/// with decimal strings, since JSON numbers lose precision past 2^53 in
+/// many readers. Negative results are `I64` and serialize as plain
+/// (negative) JSON numbers. Equality compares numeric values, so `U64(5)`
+/// equals `U128(5)` and `Big("5")`. Computations whose result is a hash
+/// use `Digest`, serialized as `{"digest": "..."}`, which only equals
+/// another `Digest`.
+#[derive(Debug, Clone)]
+pub enum ResultValue {
+    U64(u64),
+    U128(u128),
+    /// Arbitrary-size decimal digits
+    Big(String),
+    /// A result of a computation over signed integers
+    I64(i64),
+    /// Lowercase hex digest, e.g. of `hash_computation`
+    Digest(String),
+}
+
+impl ResultValue {
+    /// The narrowest variant holding `value`
+    pub fn from_u128(value: u128) -> Self {
+        match u64::try_from(value) {
+            Ok(small) => ResultValue::U64(small),
+            Err(_) => ResultValue::U128(value),
+        }
+    }
+
+    /// The value as a `u64`, if it is numeric and fits
+    pub fn as_u64(&self) -> Option<u64> {
+        self.numeric()?.parse().ok()
+    }
+
+    /// The value as a `u128`, if it is numeric and fits
+    pub fn as_u128(&self) -> Option<u128> {
+        self.numeric()?.parse().ok()
+    }
+
+    /// The value as an `i64`, if it is numeric and fits
+    pub fn as_i64(&self) -> Option<i64> {
+        self.numeric()?.parse().ok()
+    }
+
+    /// The hex digest, if the result is one
+    pub fn as_digest(&self) -> Option<&str> {
+        match self {
+            ResultValue::Digest(hex) => Some(hex),
+            _ => None,
+        }
+    }
+
+    /// Canonical decimal digits, or `None` for a digest
+    fn numeric(&self) -> Option<String> {
+        match self {
+            ResultValue::Digest(_) => None,
+            _ => Some(self.canonical()),
+        }
+    }
+
+    /// Decimal digits without leading zeros; a digest's hex as is
+    fn canonical(&self) -> String {
+        match self {
+            ResultValue::U64(value) => value.to_string(),
+            ResultValue::U128(value) => value.to_string(),
+            ResultValue::I64(value) => value.to_string(),
+            ResultValue::Digest(hex) => hex.clone(),
+            ResultValue::Big(digits) => {
+                let trimmed = digits.trim_start_matches('0');
+                if trimmed.is_empty() { "0".to_string() } else { trimmed.to_string() }
+            }
+        }
+    }
+}
+
+impl Default for ResultValue {
+    fn default() -> Self {
+        ResultValue::U64(0)
+    }
+}
+
+impl From<u64> for ResultValue {
+    fn from(value: u64) -> Self {
+        ResultValue::U64(value)
+    }
+}
+
+impl From<u128> for ResultValue {
+    fn from(value: u128) -> Self {
+        ResultValue::U128(value)
+    }
+}
+
+impl From<i64> for ResultValue {
+    /// Non-negative values become `U64`, so they compare and serialize
+    /// exactly like unsigned results
+    fn from(value: i64) -> Self {
+        match u64::try_from(value) {
+            Ok(unsigned) => ResultValue::U64(unsigned),
+            Err(_) => ResultValue::I64(value),
+        }
+    }
+}
+
+impl PartialEq for ResultValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (ResultValue::Digest(a), ResultValue::Digest(b)) => a == b,
+            (ResultValue::Digest(_), _) | (_, ResultValue::Digest(_)) => false,
+            _ => self.canonical() == other.canonical(),
+        }
+    }
+}
+
+impl Eq for ResultValue {}
+
+impl PartialEq<u64> for ResultValue {
+    fn eq(&self, other: &u64) -> bool {
+        self.as_u64() == Some(*other)
+    }
+}
+
+impl std::fmt::Display for ResultValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.canonical())
+    }
+}
+
+/// Wire form of `ResultValue`
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum ResultRepr {
+    Number(u64),
+    Negative(i64),
+    Wide(WideResult),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum WideResult {
+    U128(String),
+    Big(String),
+    Digest(String),
+}
+
+impl Serialize for ResultValue {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let repr = match self {
+            ResultValue::U64(value) => ResultRepr::Number(*value),
+            ResultValue::U128(value) => ResultRepr::Wide(WideResult::U128(value.to_string())),
+            ResultValue::Big(digits) => ResultRepr::Wide(WideResult::Big(digits.clone())),
+            ResultValue::Digest(hex) => ResultRepr::Wide(WideResult::Digest(hex.clone())),
+            ResultValue::I64(value) => match u64::try_from(*value) {
+                Ok(unsigned) => ResultRepr::Number(unsigned),
+                Err(_) => ResultRepr::Negative(*value),
+            },
+        };
+        repr.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ResultValue {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+        match ResultRepr::deserialize(deserializer)? {
+            ResultRepr::Number(value) => Ok(ResultValue::U64(value)),
+            ResultRepr::Negative(value) => Ok(ResultValue::I64(value)),
+            ResultRepr::Wide(WideResult::U128(digits)) => digits
+                .parse()
+                .map(ResultValue::U128)
+                .map_err(|_| D::Error::custom(format!("invalid u128 result: {}", digits))),
+            ResultRepr::Wide(WideResult::Big(digits)) => {
+                if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+                    return Err(D::Error::custom(format!("invalid big result: {}", digits)));
+                }
+                Ok(ResultValue::Big(digits))
+            }
+            ResultRepr::Wide(WideResult::Digest(hex)) => {
+                if hex.is_empty() || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return Err(D::Error::custom(format!("invalid digest result: {}", hex)));
+                }
+                Ok(ResultValue::Digest(hex.to_ascii_lowercase()))
+            }
+        }
+    }
+}
+
 /// A STARK Proof
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Proof {
     pub version: String,
     pub computation: String,
-    pub result: u64,
+    pub result: ResultValue,
+    #[serde(deserialize_with = "lowercase_hex")]
     pub trace_commitment: String,
     pub constraint_evaluations: Vec<u64>,
+    #[serde(deserialize_with = "lowercase_hex")]
     pub challenge: String,
+    #[serde(deserialize_with = "lowercase_hex")]
     pub fri_layers: Vec<String>,
-    pub timestamp: u64,
+    /// Creation time in Unix seconds; absent when the prover omitted it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
     pub security_bits: u32,
+    /// Public inputs of the computation (e.g. `n` for fibonacci)
+    #[serde(default)]
+    pub public_inputs: BTreeMap<String, u64>,
+    /// Whether `public_inputs` were hashed into the challenge; false for
+    /// proofs predating the binding
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub inputs_bound: bool,
+    /// Number of trace steps proved; 0 for proofs predating this field
+    #[serde(default)]
+    pub num_steps: usize,
+    /// Hash of every constraint evaluation (padding included) under
+    /// `hash_algorithm`; empty for proofs predating this field
+    #[serde(default, skip_serializing_if = "String::is_empty", deserialize_with = "lowercase_hex")]
+    pub constraint_commitment: String,
+    /// Hash algorithm (and implied integer byte order) used by the prover
+    #[serde(default = "default_hash_algorithm")]
+    pub hash_algorithm: String,
+    /// Factor by which each FRI layer shrinks the evaluation domain
+    #[serde(default = "default_fold_factor")]
+    pub fri_fold_factor: usize,
+    /// Degree of the constraint polynomial; FRI must fold away
+    /// `constraint_degree * evaluations` worth of degree
+    #[serde(default = "default_constraint_degree")]
+    pub constraint_degree: usize,
+    /// Constraints evaluated per trace step. The evaluations are grouped
+    /// per constraint: every step's value for constraint 0, then every
+    /// step's value for constraint 1, and so on.
+    #[serde(default = "default_constraints_per_step", skip_serializing_if = "is_single_constraint")]
+    pub constraints_per_step: usize,
+    /// Power-of-two padding applied to the evaluations before FRI, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub padding: Option<PaddingInfo>,
+    /// Final value of the running hash chain over trace steps, if requested
+    #[serde(default, skip_serializing_if = "Option::is_none", deserialize_with = "lowercase_hex")]
+    pub trace_chain: Option<String>,
+    /// Every intermediate hash chain value, if requested
+    #[serde(default, skip_serializing_if = "Vec::is_empty", deserialize_with = "lowercase_hex")]
+    pub trace_chain_links: Vec<String>,
+    /// Per-step constraint values, only present when the prover opts in
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub constraint_annotations: Vec<ConstraintAnnotation>,
+    /// Trace steps whose constraints were evaluated, in evaluation order,
+    /// when the prover sampled instead of evaluating every step. Sampled
+    /// proofs say nothing about the other steps.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sampled_steps: Vec<usize>,
+    /// Merkle root over the trace steps, present when steps are opened
+    #[serde(default, skip_serializing_if = "Option::is_none", deserialize_with = "lowercase_hex")]
+    pub step_root: Option<String>,
+    /// Trace steps opened at challenge-derived positions, with Merkle paths
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fri_queries: Vec<StepOpening>,
+    /// Number of queries the prover was asked to open; `fri_queries` holds
+    /// this many openings, or one per step when there are fewer steps.
+    /// 0 for proofs without openings or predating this field
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub num_queries: usize,
+    /// Random salt mixed into `trace_commitment` so it hides the trace;
+    /// absent for deterministic (unsalted) commitments
+    #[serde(default, skip_serializing_if = "Option::is_none", deserialize_with = "lowercase_hex")]
+    pub commitment_salt: Option<String>,
+    /// Set by `STARKProver::prove_commitment`: the proof attests to the
+    /// trace commitment and result but has no FRI layers, so it is weaker
+    /// than a full proof
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub commitment_only: bool,
+    /// Seed of the simulated interactive verifier whose `ChaCha20Rng`
+    /// drew the challenge; absent for the default Fiat-Shamir challenge
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interactive_seed: Option<u64>,
+    /// Merkle root over the constraint evaluations, present when
+    /// evaluations are opened
+    #[serde(default, skip_serializing_if = "Option::is_none", deserialize_with = "lowercase_hex")]
+    pub evaluation_root: Option<String>,
+    /// Constraint evaluations opened at positions derived from the
+    /// challenge and `evaluation_root`, with Merkle paths
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub evaluation_queries: Vec<EvaluationOpening>,
 }
 
 impl Proof {
     /// Create a new proof
     pub fn new(
         computation: impl Into<String>,
-        result: u64,
+        result: impl Into<ResultValue>,
         trace_commitment: String,
         constraint_evaluations: Vec<u64>,
         challenge: String,
@@ -80,72 +563,1843 @@ impl Proof {
         Proof {
             version: "1.0".to_string(),
             computation: computation.into(),
-            result,
+            result: result.into(),
             trace_commitment,
             constraint_evaluations,
             challenge,
             fri_layers,
-            timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
+            timestamp: Some(crate::util::unix_now()),
             security_bits,
+            public_inputs: BTreeMap::new(),
+            inputs_bound: false,
+            num_steps: 0,
+            constraint_commitment: String::new(),
+            hash_algorithm: default_hash_algorithm(),
+            fri_fold_factor: crate::crypto::DEFAULT_FOLD_FACTOR,
+            constraint_degree: default_constraint_degree(),
+            constraints_per_step: default_constraints_per_step(),
+            padding: None,
+            trace_chain: None,
+            trace_chain_links: Vec::new(),
+            constraint_annotations: Vec::new(),
+            sampled_steps: Vec::new(),
+            step_root: None,
+            fri_queries: Vec::new(),
+            num_queries: 0,
+            commitment_salt: None,
+            commitment_only: false,
+            interactive_seed: None,
+            evaluation_root: None,
+            evaluation_queries: Vec::new(),
         }
     }
 }
 
-/// Verification result
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct VerificationResult {
-    pub valid: bool,
-    pub message: String,
-    pub checks_passed: Vec<String>,
-    pub checks_failed: Vec<String>,
+/// What a proof asserts: `computation` applied to `inputs` gives `result`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Claim {
+    pub computation: crate::computation::ComputationKind,
+    pub inputs: BTreeMap<String, u64>,
+    pub result: ResultValue,
 }
 
-impl VerificationResult {
-    /// Create a valid result
-    pub fn valid(message: impl Into<String>, checks_passed: Vec<String>) -> Self {
-        VerificationResult {
-            valid: true,
-            message: message.into(),
-            checks_passed,
-            checks_failed: Vec::new(),
-        }
+impl std::fmt::Display for Claim {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let inputs: Vec<String> = self.inputs.iter().map(|(name, value)| format!("{}={}", name, value)).collect();
+        write!(f, "{}({}) = {}", self.computation, inputs.join(", "), self.result)
     }
+}
 
-    /// Create an invalid result
-    pub fn invalid(message: impl Into<String>, checks_failed: Vec<String>) -> Self {
-        VerificationResult {
-            valid: false,
-            message: message.into(),
-            checks_passed: Vec::new(),
-            checks_failed,
+/// A trace step revealed at a queried position, with its authentication
+/// path against `Proof::step_root`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StepOpening {
+    pub index: usize,
+    pub step: TraceStep,
+    #[serde(deserialize_with = "lowercase_hex")]
+    pub path: Vec<String>,
+}
+
+/// A constraint evaluation revealed at a queried position, with its
+/// authentication path against `Proof::evaluation_root`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EvaluationOpening {
+    pub index: usize,
+    pub value: u64,
+    #[serde(deserialize_with = "lowercase_hex")]
+    pub path: Vec<String>,
+}
+
+/// Fluent construction of a `Proof` with every field under the caller's
+/// control, for building edge cases in tests and tooling.
+///
+/// Starts from an empty proof without a timestamp, or from an existing
+/// proof via `from_proof`. Nothing is validated.
+#[derive(Debug, Clone)]
+pub struct ProofBuilder {
+    proof: Proof,
+}
+
+impl Default for ProofBuilder {
+    fn default() -> Self {
+        let mut proof = Proof::new(String::new(), 0u64, String::new(), Vec::new(), String::new(), Vec::new(), 0);
+        proof.timestamp = None;
+        ProofBuilder { proof }
+    }
+}
+
+impl ProofBuilder {
+    /// Start from an empty proof
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start from a copy of `proof`, e.g. a valid one to tamper with
+    pub fn from_proof(proof: &Proof) -> Self {
+        ProofBuilder { proof: proof.clone() }
+    }
+
+    /// Proof format version
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.proof.version = version.into();
+        self
+    }
+
+    /// Computation name
+    pub fn with_computation(mut self, computation: impl Into<String>) -> Self {
+        self.proof.computation = computation.into();
+        self
+    }
+
+    /// Claimed result
+    pub fn with_result(mut self, result: impl Into<ResultValue>) -> Self {
+        self.proof.result = result.into();
+        self
+    }
+
+    /// Trace commitment
+    pub fn with_trace_commitment(mut self, trace_commitment: impl Into<String>) -> Self {
+        self.proof.trace_commitment = trace_commitment.into();
+        self
+    }
+
+    /// Constraint evaluations
+    pub fn with_constraint_evaluations(mut self, constraint_evaluations: Vec<u64>) -> Self {
+        self.proof.constraint_evaluations = constraint_evaluations;
+        self
+    }
+
+    /// Fiat-Shamir challenge
+    pub fn with_challenge(mut self, challenge: impl Into<String>) -> Self {
+        self.proof.challenge = challenge.into();
+        self
+    }
+
+    /// FRI layer hashes
+    pub fn with_fri_layers(mut self, fri_layers: Vec<String>) -> Self {
+        self.proof.fri_layers = fri_layers;
+        self
+    }
+
+    /// Creation time in Unix seconds
+    pub fn with_timestamp(mut self, timestamp: u64) -> Self {
+        self.proof.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Security level in bits
+    pub fn with_security_bits(mut self, security_bits: u32) -> Self {
+        self.proof.security_bits = security_bits;
+        self
+    }
+
+    /// Public inputs
+    pub fn with_public_inputs(mut self, public_inputs: BTreeMap<String, u64>) -> Self {
+        self.proof.public_inputs = public_inputs;
+        self
+    }
+
+    /// Whether the public inputs are bound into the challenge
+    pub fn with_inputs_bound(mut self, inputs_bound: bool) -> Self {
+        self.proof.inputs_bound = inputs_bound;
+        self
+    }
+
+    /// Number of trace steps
+    pub fn with_num_steps(mut self, num_steps: usize) -> Self {
+        self.proof.num_steps = num_steps;
+        self
+    }
+
+    /// Constraint commitment
+    pub fn with_constraint_commitment(mut self, constraint_commitment: impl Into<String>) -> Self {
+        self.proof.constraint_commitment = constraint_commitment.into();
+        self
+    }
+
+    /// Hash algorithm name
+    pub fn with_hash_algorithm(mut self, hash_algorithm: impl Into<String>) -> Self {
+        self.proof.hash_algorithm = hash_algorithm.into();
+        self
+    }
+
+    /// FRI folding factor
+    pub fn with_fri_fold_factor(mut self, fri_fold_factor: usize) -> Self {
+        self.proof.fri_fold_factor = fri_fold_factor;
+        self
+    }
+
+    /// Constraint polynomial degree
+    pub fn with_constraint_degree(mut self, constraint_degree: usize) -> Self {
+        self.proof.constraint_degree = constraint_degree;
+        self
+    }
+
+    /// Constraints evaluated per trace step
+    pub fn with_constraints_per_step(mut self, constraints_per_step: usize) -> Self {
+        self.proof.constraints_per_step = constraints_per_step;
+        self
+    }
+
+    /// Evaluation padding
+    pub fn with_padding(mut self, padding: Option<PaddingInfo>) -> Self {
+        self.proof.padding = padding;
+        self
+    }
+
+    /// Final hash chain value
+    pub fn with_trace_chain(mut self, trace_chain: Option<String>) -> Self {
+        self.proof.trace_chain = trace_chain;
+        self
+    }
+
+    /// Intermediate hash chain values
+    pub fn with_trace_chain_links(mut self, trace_chain_links: Vec<String>) -> Self {
+        self.proof.trace_chain_links = trace_chain_links;
+        self
+    }
+
+    /// Per-step constraint annotations
+    pub fn with_constraint_annotations(mut self, constraint_annotations: Vec<ConstraintAnnotation>) -> Self {
+        self.proof.constraint_annotations = constraint_annotations;
+        self
+    }
+
+    /// Sampled step indices
+    pub fn with_sampled_steps(mut self, sampled_steps: Vec<usize>) -> Self {
+        self.proof.sampled_steps = sampled_steps;
+        self
+    }
+
+    /// Step Merkle root
+    pub fn with_step_root(mut self, step_root: Option<String>) -> Self {
+        self.proof.step_root = step_root;
+        self
+    }
+
+    /// Opened trace steps
+    pub fn with_fri_queries(mut self, fri_queries: Vec<StepOpening>) -> Self {
+        self.proof.fri_queries = fri_queries;
+        self
+    }
+
+    /// Number of queries the openings were generated for
+    pub fn with_num_queries(mut self, num_queries: usize) -> Self {
+        self.proof.num_queries = num_queries;
+        self
+    }
+
+    /// Salt mixed into the trace commitment
+    pub fn with_commitment_salt(mut self, commitment_salt: Option<String>) -> Self {
+        self.proof.commitment_salt = commitment_salt;
+        self
+    }
+
+    /// Commitment-only flag
+    pub fn with_commitment_only(mut self, commitment_only: bool) -> Self {
+        self.proof.commitment_only = commitment_only;
+        self
+    }
+
+    /// Seed of the interactive challenge, if any
+    pub fn with_interactive_seed(mut self, interactive_seed: Option<u64>) -> Self {
+        self.proof.interactive_seed = interactive_seed;
+        self
+    }
+
+    /// Merkle root over the constraint evaluations
+    pub fn with_evaluation_root(mut self, evaluation_root: Option<String>) -> Self {
+        self.proof.evaluation_root = evaluation_root;
+        self
+    }
+
+    /// Opened constraint evaluations
+    pub fn with_evaluation_queries(mut self, evaluation_queries: Vec<EvaluationOpening>) -> Self {
+        self.proof.evaluation_queries = evaluation_queries;
+        self
+    }
+
+    /// Finish building
+    pub fn build(self) -> Proof {
+        self.proof
+    }
+}
+
+/// How constraint evaluations are padded to a power-of-two domain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PaddingMode {
+    /// No padding (default)
+    #[default]
+    None,
+    /// Pad with zeros
+    Zero,
+    /// Pad by repeating the last evaluation
+    RepeatLast,
+}
+
+impl PaddingMode {
+    /// Pad `evaluations` up to the next power of two.
+    /// Returns the padding record, or `None` if nothing was applied.
+    pub fn apply(&self, evaluations: &mut Vec<u64>) -> Option<PaddingInfo> {
+        let fill = match self {
+            PaddingMode::None => return None,
+            PaddingMode::Zero => 0,
+            PaddingMode::RepeatLast => *evaluations.last()?,
+        };
+        let original_len = evaluations.len();
+        let padded_len = original_len.next_power_of_two();
+        evaluations.resize(padded_len, fill);
+        Some(PaddingInfo {
+            mode: *self,
+            original_len,
+            padded_len,
+        })
+    }
+}
+
+/// Record of the padding applied to a proof's evaluations
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaddingInfo {
+    pub mode: PaddingMode,
+    pub original_len: usize,
+    pub padded_len: usize,
+}
+
+impl PaddingInfo {
+    /// Check that `evaluations` were padded exactly as this record describes
+    pub fn matches(&self, evaluations: &[u64]) -> bool {
+        if !self.padded_len.is_power_of_two()
+            || self.original_len == 0
+            || self.original_len > self.padded_len
+            || evaluations.len() != self.padded_len
+        {
+            return false;
         }
+        let (original, tail) = evaluations.split_at(self.original_len);
+        let fill = match self.mode {
+            PaddingMode::None => return tail.is_empty(),
+            PaddingMode::Zero => 0,
+            PaddingMode::RepeatLast => original[original.len() - 1],
+        };
+        tail.iter().all(|&value| value == fill)
     }
+}
 
-    /// Print the result nicely
-    pub fn print_report(&self) {
-        println!("\n{}", "=".repeat(60));
-        println!("STARK PROOF VERIFICATION REPORT");
-        println!("{}", "=".repeat(60));
-        println!("\nStatus: {}", self.message);
-        println!("Overall Valid: {}", if self.valid { "YES" } else { "NO" });
+/// Values holding hex digits whose case carries no meaning. Used by the
+/// `lowercase_hex` deserializer and `Proof::canonicalize`, so both
+/// normalize the same way.
+trait HexCase {
+    /// Lowercase every hex digit in place, leaving anything else as is
+    fn make_hex_lowercase(&mut self);
+}
 
-        if !self.checks_passed.is_empty() {
-            println!("\nChecks Passed ({}):", self.checks_passed.len());
-            for check in &self.checks_passed {
-                println!("  ✓ {}", check);
-            }
+impl HexCase for String {
+    fn make_hex_lowercase(&mut self) {
+        self.make_ascii_lowercase();
+    }
+}
+
+impl HexCase for ResultValue {
+    fn make_hex_lowercase(&mut self) {
+        if let ResultValue::Digest(hex) = self {
+            hex.make_hex_lowercase();
         }
+    }
+}
 
-        if !self.checks_failed.is_empty() {
-            println!("\nChecks Failed ({}):", self.checks_failed.len());
-            for check in &self.checks_failed {
-                println!("  ✗ {}", check);
-            }
+impl<T: HexCase> HexCase for Option<T> {
+    fn make_hex_lowercase(&mut self) {
+        if let Some(value) = self {
+            value.make_hex_lowercase();
         }
+    }
+}
+
+impl<T: HexCase> HexCase for Vec<T> {
+    fn make_hex_lowercase(&mut self) {
+        self.iter_mut().for_each(HexCase::make_hex_lowercase);
+    }
+}
+
+/// Deserialize hex digests in lowercase, so proofs from tools that emit
+/// uppercase hex compare equal to ours
+fn lowercase_hex<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de> + HexCase,
+{
+    let mut value = T::deserialize(deserializer)?;
+    value.make_hex_lowercase();
+    Ok(value)
+}
+
+fn default_hash_algorithm() -> String {
+    crate::crypto::HashAlgorithm::default().name().to_string()
+}
+
+fn default_fold_factor() -> usize {
+    crate::crypto::DEFAULT_FOLD_FACTOR
+}
+
+fn default_constraint_degree() -> usize {
+    1
+}
+
+fn default_constraints_per_step() -> usize {
+    1
+}
+
+fn is_single_constraint(constraints_per_step: &usize) -> bool {
+    *constraints_per_step == 1
+}
+
+fn is_zero(value: &usize) -> bool {
+    *value == 0
+}
+
+/// A computation trace saved alongside its result (and optionally its proof)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceBundle {
+    pub computation: String,
+    pub result: ResultValue,
+    pub trace: ProofTrace,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proof: Option<Proof>,
+}
+
+impl TraceBundle {
+    /// Create a new bundle
+    pub fn new(computation: impl Into<String>, result: impl Into<ResultValue>, trace: ProofTrace, proof: Option<Proof>) -> Self {
+        TraceBundle {
+            computation: computation.into(),
+            result: result.into(),
+            trace,
+            proof,
+        }
+    }
+
+    /// Save the bundle to a JSON file
+    pub fn save(&self, filename: impl AsRef<Path>) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(filename, json)
+    }
+
+    /// Load a bundle from a JSON file
+    pub fn load(filename: impl AsRef<Path>) -> Result<Self, LoadError> {
+        let json = std::fs::read_to_string(filename)?;
+        if json.trim().is_empty() {
+            return Err(LoadError::Empty);
+        }
+        Ok(serde_json::from_str(&json)?)
+    }
+}
+
+/// Several proofs stored together in one JSON file for distribution
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProofArchive {
+    pub proofs: Vec<Proof>,
+}
+
+impl ProofArchive {
+    /// Create an archive holding `proofs`
+    pub fn new(proofs: Vec<Proof>) -> Self {
+        ProofArchive { proofs }
+    }
+
+    /// Save the archive to a JSON file
+    pub fn save(&self, filename: impl AsRef<Path>) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(filename, json)
+    }
+
+    /// Load an archive from a JSON file
+    pub fn load(filename: impl AsRef<Path>) -> Result<Self, LoadError> {
+        let json = std::fs::read_to_string(filename)?;
+        if json.trim().is_empty() {
+            return Err(LoadError::Empty);
+        }
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    /// Histogram of the proofs' trace lengths (`Proof::num_steps`)
+    pub fn trace_length_histogram(&self) -> TraceLengthHistogram {
+        let mut histogram = TraceLengthHistogram::default();
+        for proof in &self.proofs {
+            histogram.add(proof.num_steps);
+        }
+        histogram
+    }
+}
+
+/// Number of proofs per trace length, in power-of-two buckets
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TraceLengthHistogram {
+    /// Proof count keyed by bucket start: the bucket starting at `k`
+    /// holds lengths `k..2k`
+    pub buckets: BTreeMap<usize, usize>,
+    /// Proofs predating `num_steps`, whose length is not recorded
+    pub unknown: usize,
+}
+
+impl TraceLengthHistogram {
+    /// Count one proof with `num_steps` steps; 0 means unknown
+    pub fn add(&mut self, num_steps: usize) {
+        if num_steps == 0 {
+            self.unknown += 1;
+        } else {
+            *self.buckets.entry(1 << num_steps.ilog2()).or_default() += 1;
+        }
+    }
+
+    /// Number of proofs counted
+    pub fn total(&self) -> usize {
+        self.buckets.values().sum::<usize>() + self.unknown
+    }
+}
+
+impl std::fmt::Display for TraceLengthHistogram {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const WIDTH: usize = 40;
+        write!(f, "Trace lengths ({} proofs)", self.total())?;
+        let largest = self.buckets.values().copied().chain([self.unknown]).max().unwrap_or(0).max(1);
+        let bar = |count: usize| "█".repeat((count * WIDTH).div_ceil(largest));
+        for (&start, &count) in &self.buckets {
+            let range = format!("{}-{}", start, 2 * start - 1);
+            write!(f, "\n  {:>13} {:>5} {}", range, count, bar(count))?;
+        }
+        if self.unknown > 0 {
+            write!(f, "\n  {:>13} {:>5} {}", "unknown", self.unknown, bar(self.unknown))?;
+        }
+        Ok(())
+    }
+}
+
+/// Aggregate statistics over every proof file in a directory
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProofStats {
+    /// Proofs loaded successfully
+    pub count: usize,
+    /// `.json` files that could not be read or parsed as a proof
+    pub unreadable: usize,
+    /// Number of proofs per computation
+    pub computations: BTreeMap<String, usize>,
+    /// Smallest result (results wider than u128 are skipped)
+    pub min_result: Option<u128>,
+    /// Largest result (results wider than u128 are skipped)
+    pub max_result: Option<u128>,
+    /// Mean of the results counted in `min_result`/`max_result`
+    pub avg_result: Option<f64>,
+    /// Mean number of FRI layers per proof
+    pub avg_fri_layers: f64,
+    /// Sum of `Proof::serialized_size` over all proofs
+    pub total_bytes: usize,
+}
+
+impl ProofStats {
+    /// Load every `.json` file directly inside `dir` and aggregate the
+    /// proofs among them; files that fail to load, or exceed
+    /// `MAX_PROOF_BYTES`, count as `unreadable`
+    pub fn from_dir(dir: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        let mut paths: Vec<_> = std::fs::read_dir(dir)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<std::io::Result<_>>()?;
+        paths.retain(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "json"));
+        paths.sort();
+
+        let mut stats = ProofStats::default();
+        let mut fri_layers = 0;
+        let (mut result_sum, mut result_count) = (0f64, 0usize);
+        for path in paths {
+            let Ok(proof) = Proof::from_file_limited(&path, MAX_PROOF_BYTES) else {
+                stats.unreadable += 1;
+                continue;
+            };
+            stats.count += 1;
+            *stats.computations.entry(proof.computation.clone()).or_default() += 1;
+            fri_layers += proof.fri_layers.len();
+            stats.total_bytes += proof.serialized_size();
+            if let Some(result) = proof.result.as_u128() {
+                stats.min_result = Some(stats.min_result.map_or(result, |min| min.min(result)));
+                stats.max_result = Some(stats.max_result.map_or(result, |max| max.max(result)));
+                result_sum += result as f64;
+                result_count += 1;
+            }
+        }
+        if stats.count > 0 {
+            stats.avg_fri_layers = fri_layers as f64 / stats.count as f64;
+        }
+        if result_count > 0 {
+            stats.avg_result = Some(result_sum / result_count as f64);
+        }
+        Ok(stats)
+    }
+}
+
+impl std::fmt::Display for ProofStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Proofs: {} ({} unreadable)", self.count, self.unreadable)?;
+        for (computation, count) in &self.computations {
+            writeln!(f, "  {}: {}", computation, count)?;
+        }
+        if let (Some(min), Some(max), Some(avg)) = (self.min_result, self.max_result, self.avg_result) {
+            writeln!(f, "Result: min {}, max {}, avg {:.2}", min, max, avg)?;
+        }
+        writeln!(f, "Average FRI layers: {:.2}", self.avg_fri_layers)?;
+        write!(f, "Total size: {} bytes", self.total_bytes)
+    }
+}
+
+/// Security level below which `Proof::lint` warns
+pub const RECOMMENDED_SECURITY_BITS: u32 = 128;
+
+/// Bits `Proof::effective_security_bits` credits each opened query: log2
+/// of the default fold factor, independent of what the proof claims.
+///
+/// A proof that cheats on a folding step must be wrong on at least a
+/// `1 / DEFAULT_FOLD_FACTOR` share of the domain, so each distinct opening
+/// catches it with at least that probability and `k` openings leave a
+/// cheat undetected with probability at most `DEFAULT_FOLD_FACTOR^-k`.
+pub const BITS_PER_QUERY: u32 = crate::crypto::DEFAULT_FOLD_FACTOR.ilog2();
+
+/// An advisory finding about a proof's parameters.
+///
+/// Lints never affect validity: a proof can verify and still be weak.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Lint {
+    /// Security level below `RECOMMENDED_SECURITY_BITS`
+    WeakSecurity { bits: u32 },
+    /// Too few FRI layers for folding to constrain anything
+    FewFriLayers { layers: usize },
+    /// Challenge carries fewer bits than the security level implies
+    ShortChallenge { len: usize, expected: usize },
+}
+
+impl std::fmt::Display for Lint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Lint::WeakSecurity { bits } => write!(
+                f,
+                "security_bits {} below recommended {}",
+                bits, RECOMMENDED_SECURITY_BITS
+            ),
+            Lint::FewFriLayers { layers } => write!(f, "only {} FRI layer(s)", layers),
+            Lint::ShortChallenge { len, expected } => write!(
+                f,
+                "challenge has {} hex characters, shorter than the {} its security level implies",
+                len, expected
+            ),
+        }
+    }
+}
+
+/// Clock skew tolerated when checking that a timestamp is not in the future
+pub const MAX_CLOCK_SKEW_SECS: u64 = 300;
+
+/// Default limit on the size of a proof read from an untrusted file,
+/// stream or URL (see `STARKVerifier::with_max_file_bytes`)
+pub const MAX_PROOF_BYTES: usize = 4 * 1024 * 1024;
+
+impl Proof {
+    /// Cheap structural sanity checks that don't affect validity.
+    /// Returns a warning for each suspicious field. A proof without a
+    /// timestamp has nothing to check.
+    ///
+    /// A challenge shorter than `security_bits` calls for is flagged here,
+    /// before any check runs, since it caps the proof's real security.
+    pub fn validate_schema(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        match self.timestamp {
+            Some(0) => warnings.push("Timestamp is zero".to_string()),
+            Some(timestamp) if timestamp > crate::util::unix_now() + MAX_CLOCK_SKEW_SECS => {
+                warnings.push(format!("Timestamp {} is in the future", timestamp))
+            }
+            _ => {}
+        }
+        let expected_len = crate::crypto::challenge_hex_len(self.security_bits);
+        if self.challenge.len() < expected_len {
+            warnings.push(format!(
+                "Challenge has {} hex characters ({} bits) but security_bits {} calls for {}",
+                self.challenge.len(),
+                self.challenge.len() * 4,
+                self.security_bits,
+                expected_len
+            ));
+        }
+        warnings
+    }
+
+    /// Advisory warnings about weak parameters in an otherwise valid proof
+    pub fn lint(&self) -> Vec<Lint> {
+        let mut lints = Vec::new();
+        if self.security_bits < RECOMMENDED_SECURITY_BITS {
+            lints.push(Lint::WeakSecurity { bits: self.security_bits });
+        }
+        if self.fri_layers.len() <= 1 {
+            lints.push(Lint::FewFriLayers { layers: self.fri_layers.len() });
+        }
+        let expected = crate::crypto::challenge_hex_len(self.security_bits);
+        if self.challenge.len() < expected {
+            lints.push(Lint::ShortChallenge { len: self.challenge.len(), expected });
+        }
+        lints
+    }
+
+    /// The hash algorithm named by `hash_algorithm`, or `None` if unknown
+    pub fn parsed_hash_algorithm(&self) -> Option<crate::crypto::HashAlgorithm> {
+        crate::crypto::HashAlgorithm::from_name(&self.hash_algorithm)
+    }
+
+    /// Hash of the constraint evaluations under the proof's hash algorithm,
+    /// or `None` if the algorithm is unknown
+    pub fn compute_constraint_commitment(&self) -> Option<String> {
+        Some(self.parsed_hash_algorithm()?.hash_integers(&self.constraint_evaluations))
+    }
+
+    /// Size in bytes of the proof serialized as compact JSON
+    pub fn serialized_size(&self) -> usize {
+        serde_json::to_vec(self).expect("Failed to serialize proof").len()
+    }
+
+    /// The constraint evaluations with any domain padding stripped
+    pub fn unpadded_evaluations(&self) -> &[u64] {
+        match &self.padding {
+            Some(padding) => {
+                &self.constraint_evaluations[..padding.original_len.min(self.constraint_evaluations.len())]
+            }
+            None => &self.constraint_evaluations,
+        }
+    }
+
+    /// Normalize formatting so logically equal proofs serialize identically.
+    ///
+    /// Maps are already ordered (`BTreeMap`); this lowercases every hex
+    /// digest, including a `ResultValue::Digest` result, since hex case
+    /// carries no meaning. Deserialized proofs are
+    /// already lowercase; this covers proofs built or edited in memory.
+    pub fn canonicalize(&mut self) {
+        self.result.make_hex_lowercase();
+        self.trace_commitment.make_hex_lowercase();
+        self.constraint_commitment.make_hex_lowercase();
+        self.challenge.make_hex_lowercase();
+        self.fri_layers.make_hex_lowercase();
+        self.trace_chain.make_hex_lowercase();
+        self.trace_chain_links.make_hex_lowercase();
+        self.step_root.make_hex_lowercase();
+        self.commitment_salt.make_hex_lowercase();
+        for opening in &mut self.fri_queries {
+            opening.path.make_hex_lowercase();
+        }
+        self.evaluation_root.make_hex_lowercase();
+        for opening in &mut self.evaluation_queries {
+            opening.path.make_hex_lowercase();
+        }
+    }
+
+    /// The field at a JSON Pointer (RFC 6901) into the proof's JSON form,
+    /// e.g. `/fri_layers/0` or `/public_inputs/n`.
+    ///
+    /// Returns `None` for paths that do not exist and for malformed
+    /// pointers; the empty pointer selects the whole proof.
+    pub fn get_field(&self, pointer: &str) -> Option<serde_json::Value> {
+        serde_json::to_value(self).ok()?.pointer(pointer).cloned()
+    }
+
+    /// Security actually provided by the proof's parameters, which may be
+    /// less than the claimed `security_bits`.
+    ///
+    /// The weakest of: the challenge's entropy (4 bits per hex character),
+    /// the FRI folding (0 when there are too few layers to fold the domain
+    /// away), and the openings actually present (`BITS_PER_QUERY` each),
+    /// so a proof that opens no steps provides no query soundness and more
+    /// openings never lower the estimate. The per-query credit is fixed here
+    /// rather than taken from the proof's `fri_fold_factor`, which a proof
+    /// could inflate. This format has no proof-of-work grinding, so none
+    /// is added. A heuristic for spotting overclaiming proofs, not a proof
+    /// of soundness.
+    pub fn effective_security_bits(&self) -> u32 {
+        let hex_digits = self.challenge.chars().take_while(|c| c.is_ascii_hexdigit()).count();
+        let mut bits = (hex_digits as u32).saturating_mul(4);
+
+        let required_layers = crate::crypto::fri_layers_for_degree(
+            self.constraint_evaluations.len(),
+            self.constraint_degree,
+            self.fri_fold_factor,
+        );
+        if required_layers == 0 || self.fri_layers.len() < required_layers {
+            bits = 0;
+        }
+
+        bits.min((self.fri_queries.len() as u32).saturating_mul(BITS_PER_QUERY))
+    }
+
+    /// The statement this proof asserts, built from `computation`,
+    /// `public_inputs` and `result`. Only meaningful once the proof has
+    /// verified.
+    pub fn statement(&self) -> Claim {
+        Claim {
+            computation: crate::computation::ComputationKind::from_name(&self.computation),
+            inputs: self.public_inputs.clone(),
+            result: self.result.clone(),
+        }
+    }
+
+    /// SHA-256 of the canonical JSON form, identifying the proof's contents
+    pub fn fingerprint(&self) -> String {
+        let mut canonical = self.clone();
+        canonical.canonicalize();
+        let json = serde_json::to_string(&canonical).expect("Failed to serialize proof");
+        crate::crypto::hash_string(&json)
+    }
+
+    /// True for a commitment-only proof, which skipped FRI. A proof
+    /// carrying FRI layers is a full proof whatever its flag says.
+    pub fn is_commitment_only(&self) -> bool {
+        self.commitment_only && self.fri_layers.is_empty()
+    }
+
+    /// The evaluation of constraint 0 recorded for trace step `index`, if
+    /// that step was evaluated
+    pub fn evaluation_for_step(&self, index: usize) -> Option<u64> {
+        self.constraint_evaluation_for_step(index, 0)
+    }
+
+    /// The evaluation of `constraint` recorded for trace step `index`, if
+    /// that step was evaluated
+    pub fn constraint_evaluation_for_step(&self, index: usize, constraint: usize) -> Option<u64> {
+        if constraint >= self.constraints_per_step {
+            return None;
+        }
+        let position = if self.sampled_steps.is_empty() {
+            index
+        } else {
+            self.sampled_steps.iter().position(|&sampled| sampled == index)?
+        };
+        let evaluations = self.unpadded_evaluations();
+        let rows = evaluations.len() / self.constraints_per_step;
+        if position >= rows {
+            return None;
+        }
+        evaluations.get(constraint * rows + position).copied()
+    }
+
+    /// Load a single proof from a JSON file
+    pub fn from_file(filename: impl AsRef<Path>) -> Result<Proof, LoadError> {
+        let filename = filename.as_ref();
+        if filename.is_dir() {
+            return Err(LoadError::IsDirectory { path: filename.display().to_string() });
+        }
+        let json = std::fs::read_to_string(filename)?;
+        Proof::from_json(&json)
+    }
+
+    /// Like `from_file`, but fail with `LoadError::TooLarge` once more
+    /// than `max_bytes` have been read.
+    ///
+    /// The limit applies to what is read, not to the reported file size,
+    /// so it also holds for pipes, devices and files that grow meanwhile.
+    pub fn from_file_limited(filename: impl AsRef<Path>, max_bytes: usize) -> Result<Proof, LoadError> {
+        use std::io::Read;
+
+        let filename = filename.as_ref();
+        if filename.is_dir() {
+            return Err(LoadError::IsDirectory { path: filename.display().to_string() });
+        }
+        // Read one byte past the limit so an oversized file is detected
+        // without buffering all of it
+        let mut bytes = Vec::new();
+        std::fs::File::open(filename)?.take(max_bytes as u64 + 1).read_to_end(&mut bytes)?;
+        Proof::from_json_limited(&bytes, max_bytes)
+    }
+
+    /// Parse a proof from untrusted bytes, refusing input longer than
+    /// `max_bytes` before any parsing starts.
+    ///
+    /// Otherwise behaves like `from_json`; invalid UTF-8 is reported as
+    /// `LoadError::Malformed`.
+    pub fn from_json_limited(bytes: &[u8], max_bytes: usize) -> Result<Proof, LoadError> {
+        if bytes.len() > max_bytes {
+            return Err(LoadError::TooLarge { max: max_bytes });
+        }
+        let json = std::str::from_utf8(bytes)
+            .map_err(|e| LoadError::Malformed(serde::de::Error::custom(e)))?;
+        Proof::from_json(json)
+    }
+
+    /// Parse a single proof from JSON.
+    ///
+    /// Surrounding whitespace is accepted, but any further content after the
+    /// proof object (such as a second newline-delimited proof) is rejected
+    /// with `LoadError::TrailingData`.
+    pub fn from_json(json: &str) -> Result<Proof, LoadError> {
+        let mut stream = serde_json::Deserializer::from_str(json).into_iter::<Proof>();
+        let proof = match stream.next() {
+            Some(proof) => proof?,
+            None => return Err(LoadError::Empty),
+        };
+
+        let end = stream.byte_offset();
+        let rest = &json[end..];
+        let trimmed = rest.trim_start();
+        if trimmed.is_empty() {
+            Ok(proof)
+        } else {
+            Err(LoadError::TrailingData {
+                offset: end + (rest.len() - trimmed.len()),
+            })
+        }
+    }
+
+    /// Parse a single proof like `from_json`, but reject any field the
+    /// proof format does not define, at any depth.
+    ///
+    /// `from_json` ignores unknown fields so newer proofs still load; use
+    /// this to catch misspelled fields, format drift or injected data. An
+    /// unknown field is reported as `LoadError::Malformed`.
+    pub fn from_json_strict(json: &str) -> Result<Proof, LoadError> {
+        let proof = Proof::from_json(json)?;
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        reject_unknown_fields::<Proof>(&value, "")?;
+        for (i, opening) in json_array(&value, "fri_queries").iter().enumerate() {
+            let path = format!("/fri_queries/{}", i);
+            reject_unknown_fields::<StepOpening>(opening, &path)?;
+            reject_unknown_fields::<TraceStep>(&opening["step"], &format!("{}/step", path))?;
+        }
+        for (i, opening) in json_array(&value, "evaluation_queries").iter().enumerate() {
+            reject_unknown_fields::<EvaluationOpening>(opening, &format!("/evaluation_queries/{}", i))?;
+        }
+        for (i, annotation) in json_array(&value, "constraint_annotations").iter().enumerate() {
+            reject_unknown_fields::<ConstraintAnnotation>(annotation, &format!("/constraint_annotations/{}", i))?;
+        }
+        reject_unknown_fields::<PaddingInfo>(&value["padding"], "/padding")?;
+        Ok(proof)
+    }
+}
+
+fn json_array<'a>(value: &'a serde_json::Value, key: &str) -> &'a [serde_json::Value] {
+    value[key].as_array().map(Vec::as_slice).unwrap_or_default()
+}
+
+/// Fail if `value` is an object with a key that `T` has no field for
+fn reject_unknown_fields<T: serde::de::DeserializeOwned>(value: &serde_json::Value, path: &str) -> Result<(), LoadError> {
+    let Some(object) = value.as_object() else {
+        return Ok(());
+    };
+    let fields = serde_fields::<T>();
+    match object.keys().find(|key| !fields.contains(&key.as_str())) {
+        Some(key) => Err(LoadError::Malformed(serde::de::Error::custom(format!(
+            "unknown field `{}` at {}",
+            key,
+            if path.is_empty() { "/" } else { path }
+        )))),
+        None => Ok(()),
+    }
+}
+
+/// The field names serde's derive gives `T`, so the strict check can never
+/// drift from the structs themselves
+fn serde_fields<T: serde::de::DeserializeOwned>() -> &'static [&'static str] {
+    struct FieldCapture<'a>(&'a mut &'static [&'static str]);
+
+    impl<'de> serde::Deserializer<'de> for FieldCapture<'_> {
+        type Error = serde::de::value::Error;
+
+        fn deserialize_any<V: serde::de::Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+            Err(serde::de::Error::custom("not a struct"))
+        }
+
+        fn deserialize_struct<V: serde::de::Visitor<'de>>(
+            self,
+            _name: &'static str,
+            fields: &'static [&'static str],
+            _visitor: V,
+        ) -> Result<V::Value, Self::Error> {
+            *self.0 = fields;
+            Err(serde::de::Error::custom("fields captured"))
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf option unit
+            unit_struct newtype_struct seq tuple tuple_struct map enum identifier ignored_any
+        }
+    }
+
+    let mut fields: &'static [&'static str] = &[];
+    let _ = T::deserialize(FieldCapture(&mut fields));
+    fields
+}
+
+/// The constraint value evaluated at a single trace step
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConstraintAnnotation {
+    pub step: usize,
+    pub operation: String,
+    pub value: u64,
+    pub satisfied: bool,
+}
+
+impl std::fmt::Display for ConstraintAnnotation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "step {}: {} value {} ({})",
+            self.step,
+            self.operation,
+            self.value,
+            if self.satisfied { "satisfied" } else { "violated" }
+        )
+    }
+}
+
+/// Verification result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerificationResult {
+    pub valid: bool,
+    pub message: String,
+    /// Messages of the passing checks, in check order
+    pub checks_passed: Vec<String>,
+    /// Messages of the failing checks, in check order
+    pub checks_failed: Vec<String>,
+    /// Constraint evaluations satisfying their relation, as (satisfied, total)
+    #[serde(default)]
+    pub constraints_satisfied: (usize, usize),
+    /// Non-fatal issues noticed during verification
+    #[serde(default)]
+    pub warnings: Vec<String>,
+    /// Wall-clock time spent in each verification phase, keyed by phase name
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub timings: HashMap<String, Duration>,
+}
+
+impl VerificationResult {
+    /// Create a valid result
+    pub fn valid(message: impl Into<String>, checks_passed: Vec<String>) -> Self {
+        VerificationResult {
+            valid: true,
+            message: message.into(),
+            checks_passed,
+            checks_failed: Vec::new(),
+            constraints_satisfied: (0, 0),
+            warnings: Vec::new(),
+            timings: HashMap::new(),
+        }
+    }
+
+    /// Create an invalid result
+    pub fn invalid(message: impl Into<String>, checks_failed: Vec<String>) -> Self {
+        VerificationResult {
+            valid: false,
+            message: message.into(),
+            checks_passed: Vec::new(),
+            checks_failed,
+            constraints_satisfied: (0, 0),
+            warnings: Vec::new(),
+            timings: HashMap::new(),
+        }
+    }
+
+    /// Record the outcome of an extra check, invalidating the result on failure
+    pub(crate) fn record_check(&mut self, passed: bool, description: String) {
+        if passed {
+            self.checks_passed.push(description);
+        } else {
+            self.checks_failed.push(description);
+            self.valid = false;
+            self.message = "Proof is INVALID".to_string();
+        }
+    }
+
+    /// True when both results agree on validity and on the sets of passed
+    /// and failed checks, ignoring message order, warnings and timings
+    pub fn matches(&self, other: &Self) -> bool {
+        self.diff(other).is_empty()
+    }
+
+    /// Checks that appeared or disappeared going from `self` to `other`,
+    /// ignoring message order
+    pub fn diff(&self, other: &Self) -> VerificationDiff {
+        VerificationDiff {
+            validity_changed: self.valid != other.valid,
+            passed_added: added_messages(&self.checks_passed, &other.checks_passed),
+            passed_removed: added_messages(&other.checks_passed, &self.checks_passed),
+            failed_added: added_messages(&self.checks_failed, &other.checks_failed),
+            failed_removed: added_messages(&other.checks_failed, &self.checks_failed),
+        }
+    }
+
+    /// Print the result nicely
+    pub fn print_report(&self) {
+        self.print_report_with(ReportVerbosity::Full);
+    }
+
+    /// Print the result to stdout at the given verbosity
+    pub fn print_report_with(&self, verbosity: ReportVerbosity) {
+        self.write_report(&mut std::io::stdout().lock(), verbosity)
+            .expect("Failed to write verification report");
+    }
+
+    /// Write the report to `out` at the given verbosity
+    pub fn write_report(&self, out: &mut impl std::io::Write, verbosity: ReportVerbosity) -> std::io::Result<()> {
+        match verbosity {
+            ReportVerbosity::Silent => Ok(()),
+            ReportVerbosity::Summary => writeln!(
+                out,
+                "{} ({} passed, {} failed)",
+                self.message,
+                self.checks_passed.len(),
+                self.checks_failed.len()
+            ),
+            ReportVerbosity::Full => self.write_full_report(out),
+        }
+    }
+
+    fn write_full_report(&self, out: &mut impl std::io::Write) -> std::io::Result<()> {
+        writeln!(out, "\n{}", "=".repeat(60))?;
+        writeln!(out, "STARK PROOF VERIFICATION REPORT")?;
+        writeln!(out, "{}", "=".repeat(60))?;
+        writeln!(out, "\nStatus: {}", self.message)?;
+        writeln!(out, "Overall Valid: {}", if self.valid { "YES" } else { "NO" })?;
+        let (satisfied, total) = self.constraints_satisfied;
+        if total > 0 {
+            writeln!(out, "Constraints satisfied: {}/{}", satisfied, total)?;
+        }
+
+        if !self.checks_passed.is_empty() {
+            writeln!(out, "\nChecks Passed ({}):", self.checks_passed.len())?;
+            for check in &self.checks_passed {
+                writeln!(out, "  ✓ {}", check)?;
+            }
+        }
+
+        if !self.checks_failed.is_empty() {
+            writeln!(out, "\nChecks Failed ({}):", self.checks_failed.len())?;
+            for check in &self.checks_failed {
+                writeln!(out, "  ✗ {}", check)?;
+            }
+        }
+
+        if !self.warnings.is_empty() {
+            writeln!(out, "\nWarnings ({}):", self.warnings.len())?;
+            for warning in &self.warnings {
+                writeln!(out, "  ⚠ {}", warning)?;
+            }
+        }
+
+        if !self.timings.is_empty() {
+            let mut timings: Vec<_> = self.timings.iter().collect();
+            timings.sort();
+            writeln!(out, "\nTimings:")?;
+            for (phase, elapsed) in timings {
+                writeln!(out, "  {:<24} {:>10.3} ms", phase, elapsed.as_secs_f64() * 1000.0)?;
+            }
+        }
+
+        writeln!(out, "\n{}\n", "=".repeat(60))
+    }
+}
+
+/// Write verification results as a JUnit XML report for CI dashboards.
+///
+/// Each `(name, result)` pair becomes a test case of the suite `suite`; an
+/// invalid result gets a `<failure>` listing its failed checks, one per line.
+pub fn write_junit_report(
+    out: &mut impl std::io::Write,
+    suite: &str,
+    cases: &[(String, &VerificationResult)],
+) -> std::io::Result<()> {
+    use crate::util::xml_escape;
+
+    let failures = cases.iter().filter(|(_, result)| !result.valid).count();
+    writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        out,
+        r#"<testsuite name="{}" tests="{}" failures="{}" errors="0">"#,
+        xml_escape(suite),
+        cases.len(),
+        failures
+    )?;
+    for (name, result) in cases {
+        let seconds: f64 = result.timings.values().map(Duration::as_secs_f64).sum();
+        let open = format!(
+            r#"  <testcase name="{}" classname="{}" time="{:.6}""#,
+            xml_escape(name),
+            xml_escape(suite),
+            seconds
+        );
+        if result.valid {
+            writeln!(out, "{}/>", open)?;
+        } else {
+            writeln!(out, "{}>", open)?;
+            writeln!(
+                out,
+                r#"    <failure message="{}">{}</failure>"#,
+                xml_escape(&result.message),
+                xml_escape(&result.checks_failed.join("\n"))
+            )?;
+            writeln!(out, "  </testcase>")?;
+        }
+    }
+    writeln!(out, "</testsuite>")
+}
+
+/// Messages in `after` but not in `before`, sorted
+fn added_messages(before: &[String], after: &[String]) -> Vec<String> {
+    let before: std::collections::BTreeSet<&String> = before.iter().collect();
+    let after: std::collections::BTreeSet<&String> = after.iter().collect();
+    after.difference(&before).map(|message| message.to_string()).collect()
+}
+
+/// Differences between two verification results, from `VerificationResult::diff`
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct VerificationDiff {
+    pub validity_changed: bool,
+    pub passed_added: Vec<String>,
+    pub passed_removed: Vec<String>,
+    pub failed_added: Vec<String>,
+    pub failed_removed: Vec<String>,
+}
+
+impl VerificationDiff {
+    /// True when the results match
+    pub fn is_empty(&self) -> bool {
+        !self.validity_changed
+            && self.passed_added.is_empty()
+            && self.passed_removed.is_empty()
+            && self.failed_added.is_empty()
+            && self.failed_removed.is_empty()
+    }
+}
+
+impl std::fmt::Display for VerificationDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return write!(f, "Verification results match");
+        }
+        if self.validity_changed {
+            writeln!(f, "Validity changed")?;
+        }
+        for (sign, label, messages) in [
+            ('+', "passed", &self.passed_added),
+            ('-', "passed", &self.passed_removed),
+            ('+', "failed", &self.failed_added),
+            ('-', "failed", &self.failed_removed),
+        ] {
+            for message in messages {
+                writeln!(f, "{} {}: {}", sign, label, message)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// How much of a verification report to print
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReportVerbosity {
+    /// Print nothing; callers inspect the result programmatically
+    Silent,
+    /// A single status line
+    Summary,
+    /// The full decorated report with every check (default)
+    #[default]
+    Full,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::computation::fibonacci_with_trace;
+
+    fn sample_proof_json() -> String {
+        let (result, trace) = fibonacci_with_trace(5).unwrap();
+        let proof = crate::STARKProver::new(128).prove("fibonacci", result, &trace).unwrap();
+        serde_json::to_string(&proof).unwrap()
+    }
+
+    #[test]
+    fn test_from_json_accepts_whitespace() {
+        let json = format!("\n  {}\n\n", sample_proof_json());
+        assert!(Proof::from_json(&json).is_ok());
+    }
+
+    #[test]
+    fn test_from_json_rejects_trailing_object() {
+        let single = sample_proof_json();
+        let json = format!("{}\n{}\n", single, single);
+
+        match Proof::from_json(&json) {
+            Err(LoadError::TrailingData { offset }) => assert_eq!(offset, single.len() + 1),
+            other => panic!("expected trailing data error, got {:?}", other),
+        }
+    }
+
+    /// Minimal XML well-formedness check: every element closes in order
+    /// and text contains no raw `<` or `&` outside entities
+    fn xml_elements(xml: &str) -> Vec<String> {
+        let mut open = Vec::new();
+        let mut seen = Vec::new();
+        let mut rest = xml;
+        while let Some(start) = rest.find('<') {
+            let text = &rest[..start];
+            for (i, _) in text.match_indices('&') {
+                assert!(text[i..].contains(';'), "bare & in {:?}", text);
+            }
+            let end = start + rest[start..].find('>').expect("unterminated tag");
+            let tag = &rest[start + 1..end];
+            rest = &rest[end + 1..];
+            if tag.starts_with('?') {
+                continue;
+            }
+            assert!(tag.matches('"').count().is_multiple_of(2), "unbalanced quotes in {:?}", tag);
+            if let Some(name) = tag.strip_prefix('/') {
+                assert_eq!(open.pop().as_deref(), Some(name));
+            } else {
+                let name = tag.split_whitespace().next().unwrap().trim_end_matches('/').to_string();
+                seen.push(name.clone());
+                if !tag.ends_with('/') {
+                    open.push(name);
+                }
+            }
+        }
+        assert!(open.is_empty(), "unclosed elements {:?}", open);
+        seen
+    }
+
+    #[test]
+    fn test_junit_report() {
+        let passed = VerificationResult::valid("Proof is VALID", vec!["ok".to_string()]);
+        let failed = VerificationResult::invalid(
+            "Proof is INVALID",
+            vec!["Result mismatch (claimed <56>, recomputed 55)".to_string(), "a & b \"quoted\"".to_string()],
+        );
+        let cases = vec![
+            ("[0] fibonacci(n=10) = 55".to_string(), &passed),
+            ("[1] fibonacci(n=10) = 56".to_string(), &failed),
+            ("[2] it's <odd>".to_string(), &passed),
+        ];
+        let mut out = Vec::new();
+        write_junit_report(&mut out, "archive.json", &cases).unwrap();
+        let xml = String::from_utf8(out).unwrap();
+
+        let elements = xml_elements(&xml);
+        assert_eq!(elements.iter().filter(|name| *name == "testcase").count(), 3);
+        assert_eq!(elements.iter().filter(|name| *name == "failure").count(), 1);
+        assert!(xml.contains(r#"tests="3" failures="1""#));
+        assert!(xml.contains("claimed &lt;56&gt;, recomputed 55)\na &amp; b &quot;quoted&quot;</failure>"));
+        assert!(xml.contains(r#"name="[2] it&apos;s &lt;odd&gt;""#));
+    }
+
+    #[test]
+    fn test_trace_length_histogram() {
+        use crate::prover::STARKProver;
+
+        let prover = STARKProver::new(128);
+        let mut proofs: Vec<Proof> = [3, 5, 10, 11]
+            .iter()
+            .map(|&n| {
+                let (result, trace) = fibonacci_with_trace(n).unwrap();
+                prover.prove("fibonacci", result, &trace).unwrap()
+            })
+            .collect();
+        let mut legacy = proofs[0].clone();
+        legacy.num_steps = 0;
+        proofs.push(legacy);
+
+        // fibonacci(n) takes 2n - 1 steps: 5, 9, 19 and 21
+        let histogram = ProofArchive::new(proofs).trace_length_histogram();
+        assert_eq!(histogram.buckets, BTreeMap::from([(4, 1), (8, 1), (16, 2)]));
+        assert_eq!(histogram.unknown, 1);
+        assert_eq!(histogram.total(), 5);
+
+        let rendered = histogram.to_string();
+        assert!(rendered.starts_with("Trace lengths (5 proofs)"));
+        assert!(rendered.contains("16-31     2"));
+        assert!(rendered.contains("unknown     1"));
+    }
+
+    #[test]
+    fn test_proof_stats_from_dir() {
+        use crate::computation::factorial_with_trace;
+        use crate::prover::STARKProver;
+
+        let dir = std::env::temp_dir().join(format!("stark_stats_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let prover = STARKProver::new(128);
+        let mut total_bytes = 0;
+        for (name, proof) in [
+            ("fib_5.json", prover.prove("fibonacci", 5u64, &fibonacci_with_trace(5).unwrap().1).unwrap()),
+            ("fib_10.json", prover.prove("fibonacci", 55u64, &fibonacci_with_trace(10).unwrap().1).unwrap()),
+            ("fact_4.json", prover.prove("factorial", 24u64, &factorial_with_trace(4).1).unwrap()),
+        ] {
+            total_bytes += proof.serialized_size();
+            prover.save_proof(&proof, dir.join(name).to_str().unwrap()).unwrap();
+        }
+        std::fs::write(dir.join("broken.json"), "{ not a proof").unwrap();
+        std::fs::write(dir.join("notes.txt"), "ignored").unwrap();
+        // A valid proof padded past the size limit is not read in full
+        let mut padded = serde_json::to_string(&prover.prove("fibonacci", 5u64, &fibonacci_with_trace(5).unwrap().1).unwrap()).unwrap();
+        padded.push_str(&" ".repeat(MAX_PROOF_BYTES));
+        std::fs::write(dir.join("padded.json"), padded).unwrap();
+
+        let stats = ProofStats::from_dir(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(stats.count, 3);
+        assert_eq!(stats.unreadable, 2);
+        assert_eq!(stats.computations.get("fibonacci"), Some(&2));
+        assert_eq!(stats.computations.get("factorial"), Some(&1));
+        assert_eq!((stats.min_result, stats.max_result), (Some(5), Some(55)));
+        assert_eq!(stats.avg_result, Some(28.0));
+        assert_eq!(stats.total_bytes, total_bytes);
+        assert!(stats.avg_fri_layers > 0.0);
+    }
+
+    #[test]
+    fn test_from_file_rejects_directory() {
+        let dir = std::env::temp_dir();
+        match Proof::from_file(dir.to_str().unwrap()) {
+            Err(e @ LoadError::IsDirectory { .. }) => {
+                assert!(e.to_string().starts_with("expected a file, found a directory"));
+                assert!(!e.is_transient());
+            }
+            other => panic!("expected a directory error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_get_field() {
+        let proof = Proof::from_json(&sample_proof_json()).unwrap();
+        assert_eq!(proof.get_field("/computation"), Some(serde_json::json!(proof.computation)));
+        assert_eq!(proof.get_field("/fri_layers/0"), Some(serde_json::json!(proof.fri_layers[0])));
+        assert_eq!(proof.get_field("/public_inputs/n"), proof.public_inputs.get("n").map(|n| serde_json::json!(n)));
+        assert_eq!(proof.get_field("/fri_layers/999"), None);
+        assert_eq!(proof.get_field("/no_such_field/0"), None);
+        assert_eq!(proof.get_field("fri_layers"), None);
+        assert!(proof.get_field("").is_some());
+    }
+
+    #[test]
+    fn test_steps_by_operation() {
+        let (_, trace) = fibonacci_with_trace(10).unwrap();
+        let computed = trace.steps_by_operation("fib_compute").count();
+        let lookups = trace.steps_by_operation("memo_lookup").count();
+        assert_eq!(computed, 11);
+        assert_eq!(computed + lookups, trace.steps.len());
+        assert_eq!(trace.steps_by_operation("no_such_op").count(), 0);
+    }
+
+    #[test]
+    fn test_effective_security_bits() {
+        use crate::prover::STARKProver;
+
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let unqueried = STARKProver::new(128).prove("fibonacci", result, &trace).unwrap();
+        assert_eq!(unqueried.effective_security_bits(), 0);
+
+        // More openings never lower the estimate, up to one credit per step
+        let mut previous = 0;
+        for num_queries in [1, 3, 8, 11, 64] {
+            let proof = STARKProver::new(128).with_num_queries(num_queries).prove("fibonacci", result, &trace).unwrap();
+            let bits = proof.effective_security_bits();
+            assert_eq!(bits, num_queries.min(proof.num_steps) as u32 * BITS_PER_QUERY);
+            assert!(bits >= previous, "{} queries", num_queries);
+            previous = bits;
+        }
+
+        let (result, trace) = fibonacci_with_trace(90).unwrap();
+        let proof = STARKProver::new(128).with_num_queries(128).prove("fibonacci", result, &trace).unwrap();
+        assert!(proof.effective_security_bits() > 64);
+
+        // A 16-character challenge carries only 64 bits whatever is claimed
+        let mut short = proof.clone();
+        short.challenge.truncate(16);
+        assert_eq!(short.security_bits, 128);
+        assert_eq!(short.effective_security_bits(), 64);
+
+        let mut truncated_fri = proof.clone();
+        truncated_fri.fri_layers.pop();
+        assert_eq!(truncated_fri.effective_security_bits(), 0);
+
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let opened = STARKProver::new(128).with_num_queries(3).prove("fibonacci", result, &trace).unwrap();
+        assert_eq!(opened.effective_security_bits(), 3);
+
+        // Openings promised by num_queries but stripped count for nothing
+        let mut stripped = opened.clone();
+        stripped.fri_queries.clear();
+        assert_eq!(stripped.effective_security_bits(), 0);
+
+        // A claimed fold factor does not buy extra bits per query
+        let two_queries = STARKProver::new(128).with_num_queries(2).prove("fibonacci", result, &trace).unwrap();
+        let mut bogus_fold = two_queries.clone();
+        bogus_fold.fri_fold_factor = usize::MAX;
+        assert_eq!(two_queries.effective_security_bits(), 2);
+        assert!(bogus_fold.effective_security_bits() <= 2);
+    }
+
+    #[test]
+    fn test_from_json_limited() {
+        let json = sample_proof_json();
+        assert!(Proof::from_json_limited(json.as_bytes(), MAX_PROOF_BYTES).is_ok());
+        assert!(matches!(
+            Proof::from_json_limited(json.as_bytes(), 16),
+            Err(LoadError::TooLarge { max: 16, .. })
+        ));
+        assert!(matches!(
+            Proof::from_json_limited(&[b'{', 0xff, b'}'], MAX_PROOF_BYTES),
+            Err(LoadError::Malformed(_))
+        ));
+    }
+
+    // Devices and pipes report a length of 0, so only a bounded read stops them
+    #[cfg(unix)]
+    #[test]
+    fn test_from_file_limited_bounds_devices() {
+        assert!(matches!(Proof::from_file_limited("/dev/zero", 1024), Err(LoadError::TooLarge { max: 1024 })));
+    }
+
+    #[test]
+    fn test_from_json_strict() {
+        let (result, trace) = crate::computation::fibonacci_with_trace(10).unwrap();
+        let proof = crate::STARKProver::new(128).with_num_queries(2).prove("fibonacci", result, &trace).unwrap();
+        let json = serde_json::to_string(&proof).unwrap();
+        assert!(Proof::from_json_strict(&json).is_ok());
+
+        let extra = json.replacen("{", "{\"fri_layerz\":[],", 1);
+        assert!(Proof::from_json(&extra).is_ok());
+        let error = Proof::from_json_strict(&extra).unwrap_err();
+        assert!(error.to_string().contains("unknown field `fri_layerz` at /"), "{}", error);
+
+        let nested = json.replacen("\"operation\"", "\"note\":1,\"operation\"", 1);
+        assert!(Proof::from_json(&nested).is_ok());
+        let error = Proof::from_json_strict(&nested).unwrap_err();
+        assert!(error.to_string().contains("at /fri_queries/0/step"), "{}", error);
+    }
+
+    #[test]
+    fn test_from_json_empty() {
+        assert!(matches!(Proof::from_json("  \n"), Err(LoadError::Empty)));
+        assert!(matches!(Proof::from_json("{"), Err(LoadError::Malformed(_))));
+    }
+
+    #[test]
+    fn test_statement() {
+        use crate::computation::ComputationKind;
+
+        let proof = Proof::from_json(&sample_proof_json()).unwrap();
+        let claim = proof.statement();
+        assert_eq!(claim.computation, ComputationKind::Fibonacci);
+        assert_eq!(claim.inputs, BTreeMap::from([("n".to_string(), 5)]));
+        assert_eq!(claim.result, ResultValue::U64(5));
+        assert_eq!(claim.to_string(), "fibonacci(n=5) = 5");
+    }
+
+    #[test]
+    fn test_validate_schema_timestamps() {
+        let json = sample_proof_json();
+        let mut proof = Proof::from_json(&json).unwrap();
+        assert!(proof.validate_schema().is_empty());
+
+        proof.timestamp = Some(0);
+        assert_eq!(proof.validate_schema(), vec!["Timestamp is zero".to_string()]);
+
+        proof.timestamp = Some(crate::util::unix_now() + 365 * 24 * 3600);
+        assert_eq!(proof.validate_schema().len(), 1);
+        assert!(proof.validate_schema()[0].contains("in the future"));
+
+        proof.timestamp = None;
+        assert!(proof.validate_schema().is_empty());
+    }
+
+    #[test]
+    fn test_validate_schema_challenge_length() {
+        let mut proof = Proof::from_json(&sample_proof_json()).unwrap();
+        assert_eq!(proof.security_bits, 128);
+        proof.challenge.truncate(16);
+        assert_eq!(
+            proof.validate_schema(),
+            vec!["Challenge has 16 hex characters (64 bits) but security_bits 128 calls for 32".to_string()]
+        );
+
+        // Claiming less security makes the same challenge consistent
+        proof.security_bits = 64;
+        assert!(proof.validate_schema().is_empty());
+    }
+
+    #[test]
+    fn test_padding_modes() {
+        let mut zero = vec![1, 2, 3, 4, 5];
+        let info = PaddingMode::Zero.apply(&mut zero).unwrap();
+        assert_eq!(zero, vec![1, 2, 3, 4, 5, 0, 0, 0]);
+        assert_eq!((info.original_len, info.padded_len), (5, 8));
+        assert!(info.matches(&zero));
+
+        let mut repeat = vec![1, 2, 3];
+        let info = PaddingMode::RepeatLast.apply(&mut repeat).unwrap();
+        assert_eq!(repeat, vec![1, 2, 3, 3]);
+        assert!(info.matches(&repeat));
+        assert!(!info.matches(&[1, 2, 3, 0]));
+
+        let mut untouched = vec![1, 2, 3];
+        assert!(PaddingMode::None.apply(&mut untouched).is_none());
+        assert_eq!(untouched.len(), 3);
+    }
+
+    #[test]
+    fn test_compare_identical_traces() {
+        let (_, a) = fibonacci_with_trace(8).unwrap();
+        let (_, b) = fibonacci_with_trace(8).unwrap();
+        assert!(a.compare(&b).is_identical());
+    }
+
+    #[test]
+    fn test_compare_different_traces() {
+        let (_, a) = fibonacci_with_trace(8).unwrap();
+        let mut b = a.clone();
+        b.steps[3].output += 1;
+        b.steps.pop();
+        b.set_output("result", 99);
+        b.set_input("extra", 1);
+
+        let diff = a.compare(&b);
+        assert!(!diff.is_identical());
+        assert_eq!(diff.first_differing_step, Some(3));
+        assert_eq!(diff.step_count_delta, -1);
+        assert_eq!(diff.differing_inputs, vec!["extra".to_string()]);
+        assert_eq!(diff.differing_outputs, vec!["result".to_string()]);
+    }
+
+    #[test]
+    fn test_compare_prefix_trace() {
+        let (_, a) = fibonacci_with_trace(8).unwrap();
+        let mut b = a.clone();
+        b.steps.truncate(4);
+
+        let diff = a.compare(&b);
+        assert_eq!(diff.first_differing_step, Some(4));
+        assert_eq!(diff.step_count_delta, 4 - a.steps.len() as i64);
+    }
+
+    #[test]
+    fn test_report_verbosity_output_length() {
+        let result = VerificationResult::valid("Proof is VALID", vec!["a".to_string(), "b".to_string()]);
+        let render = |verbosity| {
+            let mut out = Vec::new();
+            result.write_report(&mut out, verbosity).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+
+        assert!(render(ReportVerbosity::Silent).is_empty());
+        assert_eq!(render(ReportVerbosity::Summary), "Proof is VALID (2 passed, 0 failed)\n");
+        let full = render(ReportVerbosity::Full);
+        assert!(full.contains("Checks Passed (2)"));
+        assert!(full.len() > render(ReportVerbosity::Summary).len());
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_hex_case() {
+        let proof = Proof::from_json(&sample_proof_json()).unwrap();
+        let mut shouting = proof.clone();
+        shouting.trace_commitment.make_ascii_uppercase();
+        shouting.challenge.make_ascii_uppercase();
+        shouting.fri_layers.iter_mut().for_each(|layer| layer.make_ascii_uppercase());
+
+        assert_ne!(serde_json::to_string(&shouting).unwrap(), serde_json::to_string(&proof).unwrap());
+        assert_eq!(shouting.fingerprint(), proof.fingerprint());
+
+        let mut digest = proof.clone();
+        digest.result = ResultValue::Digest("ab".repeat(32));
+        let mut shouting_digest = digest.clone();
+        shouting_digest.result = ResultValue::Digest("AB".repeat(32));
+        assert_eq!(shouting_digest.fingerprint(), digest.fingerprint());
+
+        let mut other = proof.clone();
+        other.result = ResultValue::U64(6);
+        assert_ne!(other.fingerprint(), proof.fingerprint());
+    }
+
+    #[test]
+    fn test_uppercase_hex_proof_verifies_like_lowercase() {
+        fn shout(value: &mut serde_json::Value) {
+            match value {
+                serde_json::Value::String(text) if text.len() >= 16 && text.chars().all(|c| c.is_ascii_hexdigit()) => {
+                    text.make_ascii_uppercase();
+                }
+                serde_json::Value::Array(items) => items.iter_mut().for_each(shout),
+                serde_json::Value::Object(fields) => fields.values_mut().for_each(shout),
+                _ => {}
+            }
+        }
+
+        let (result, trace) = crate::computation::fibonacci_with_trace(10).unwrap();
+        let prover = crate::STARKProver::new(128)
+            .with_num_queries(2)
+            .with_hash_chain(crate::HashChainMode::Full)
+            .with_commitment_salt(true);
+        let proof = prover.prove("fibonacci", result, &trace).unwrap();
+        let mut json = serde_json::to_value(&proof).unwrap();
+        shout(&mut json);
+        assert!(json["challenge"].as_str().unwrap().chars().any(|c| c.is_ascii_uppercase()));
+
+        let loaded: Proof = serde_json::from_value(json).unwrap();
+        assert_eq!(serde_json::to_string(&loaded).unwrap(), serde_json::to_string(&proof).unwrap());
+        let verifier = crate::STARKVerifier::new(128);
+        let (original, shouted) = (verifier.verify(&proof), verifier.verify(&loaded));
+        assert!(shouted.valid, "{:?}", shouted.checks_failed);
+        assert_eq!(shouted.checks_passed, original.checks_passed);
+    }
+
+    #[test]
+    fn test_verification_results_match_ignoring_order() {
+        let messages = |list: &[&str]| list.iter().map(|m| m.to_string()).collect::<Vec<_>>();
+        let mut stored = VerificationResult::invalid("Proof is INVALID", messages(&["result", "fri"]));
+        stored.checks_passed = messages(&["structure", "challenge"]);
+        let mut fresh = VerificationResult::invalid("Proof is INVALID", messages(&["fri", "result"]));
+        fresh.checks_passed = messages(&["challenge", "structure"]);
+        fresh.warnings.push("only in one result".to_string());
+
+        assert!(stored.matches(&fresh) && fresh.matches(&stored));
+        assert!(stored.diff(&fresh).is_empty());
+
+        fresh.checks_failed.retain(|m| m != "fri");
+        fresh.checks_passed.push("fri ok".to_string());
+        let diff = stored.diff(&fresh);
+        assert!(!stored.matches(&fresh));
+        assert_eq!(diff.passed_added, vec!["fri ok".to_string()]);
+        assert_eq!(diff.failed_removed, vec!["fri".to_string()]);
+        assert!(!diff.validity_changed);
+        assert_eq!(diff.to_string(), "+ passed: fri ok\n- failed: fri\n");
+    }
+
+    #[test]
+    fn test_result_value_round_trips() {
+        let values = [
+            ResultValue::U64(u64::MAX),
+            ResultValue::U128(u128::from(u64::MAX) + 1),
+            ResultValue::Big("340282366920938463463374607431768211456".to_string()),
+            ResultValue::Digest("00ff".repeat(16)),
+        ];
+        for value in values {
+            let json = serde_json::to_string(&value).unwrap();
+            let decoded: ResultValue = serde_json::from_str(&json).unwrap();
+            assert_eq!(decoded, value);
+            assert_eq!(decoded.to_string(), value.to_string());
+        }
+
+        assert_eq!(serde_json::to_string(&ResultValue::U64(55)).unwrap(), "55");
+        assert_eq!(serde_json::from_str::<ResultValue>("55").unwrap(), 55);
+        assert_eq!(ResultValue::U128(5), ResultValue::Big("005".to_string()));
+        assert!(serde_json::from_str::<ResultValue>(r#"{"big": "12a"}"#).is_err());
+
+        let negative = ResultValue::from(-7i64);
+        assert_eq!(serde_json::to_string(&negative).unwrap(), "-7");
+        assert_eq!(serde_json::from_str::<ResultValue>("-7").unwrap(), negative);
+        assert_eq!(negative.as_i64(), Some(-7));
+        assert_eq!(negative.as_u64(), None);
+        assert_eq!(ResultValue::from(7i64), 7);
+
+        // Digests never equal numbers, even when all their digits are decimal
+        let digest = ResultValue::Digest("55".to_string());
+        assert_eq!(serde_json::to_string(&digest).unwrap(), r#"{"digest":"55"}"#);
+        assert_ne!(digest, ResultValue::U64(55));
+        assert_eq!(digest.as_u64(), None);
+        assert_eq!(serde_json::from_str::<ResultValue>(r#"{"digest": "AB"}"#).unwrap().as_digest(), Some("ab"));
+        assert!(serde_json::from_str::<ResultValue>(r#"{"digest": "xyz"}"#).is_err());
+    }
+
+    #[test]
+    fn test_signed_step_reduces_like_its_value() {
+        let step = TraceStep::signed(0, "sub", 3, -5, 0);
+        assert_eq!((step.signed_input(), step.signed_output()), (3, -5));
+        for modulus in [1u64 << 16, 1 << 32] {
+            let expected = (-5i64 + 3).rem_euclid(modulus as i64) as u64;
+            assert_eq!(step.constraint_value(0, modulus), (-5i64).rem_euclid(modulus as i64) as u64);
+            assert_eq!(step.constraint_value(1, modulus), expected);
+        }
+    }
+
+    #[test]
+    fn test_lint_weak_proof() {
+        let mut trace = ProofTrace::new();
+        trace.add_step(TraceStep {
+            step: 0,
+            operation: "op".to_string(),
+            input: 1,
+            output: 1,
+            depth: 0,
+        });
+        let mut proof = crate::STARKProver::new(64).prove("custom", 1u64, &trace).unwrap();
+        assert_eq!(proof.fri_layers.len(), 1);
+        assert_eq!(
+            proof.lint(),
+            vec![Lint::WeakSecurity { bits: 64 }, Lint::FewFriLayers { layers: 1 }]
+        );
+
+        proof.challenge.truncate(8);
+        assert!(proof.lint().contains(&Lint::ShortChallenge { len: 8, expected: 16 }));
+
+        let strong = Proof::from_json(&sample_proof_json()).unwrap();
+        assert!(strong.lint().is_empty());
+    }
+
+    #[test]
+    fn test_try_set_input_rejects_duplicates() {
+        let mut trace = ProofTrace::new();
+        trace.try_set_input("a", 1).unwrap();
+        assert_eq!(
+            trace.try_set_input("a", 2),
+            Err(ComputationError::DuplicateInput { key: "a".to_string() })
+        );
+        assert_eq!(trace.inputs.get("a"), Some(&1));
+
+        trace.set_input("a", 3);
+        assert_eq!(trace.inputs.get("a"), Some(&3));
+    }
+
+    #[test]
+    fn test_append_traces() {
+        let mut first = ProofTrace::new();
+        first.set_input("a", 1);
+        first.add_step(TraceStep { step: 0, operation: "double".to_string(), input: 1, output: 2, depth: 0 });
+        first.add_step(TraceStep { step: 1, operation: "double".to_string(), input: 2, output: 4, depth: 0 });
+
+        let mut second = ProofTrace::new();
+        second.set_input("b", 4);
+        second.set_output("result", 5);
+        second.add_step(TraceStep { step: 0, operation: "inc".to_string(), input: 4, output: 5, depth: 1 });
+
+        first.append(second, 2).unwrap();
+        let steps: Vec<(usize, usize)> = first.steps.iter().map(|step| (step.step, step.depth)).collect();
+        assert_eq!(steps, [(0, 0), (1, 0), (2, 3)]);
+        assert_eq!(first.inputs.len(), 2);
+        assert_eq!(first.claimed_result(), Some(5));
 
-        println!("\n{}\n", "=".repeat(60));
+        let mut clash = ProofTrace::new();
+        clash.set_input("a", 7);
+        clash.add_step(TraceStep { step: 0, operation: "inc".to_string(), input: 7, output: 8, depth: 0 });
+        assert_eq!(
+            first.append(clash, 0),
+            Err(ComputationError::DuplicateInput { key: "a".to_string() })
+        );
+        assert_eq!(first.steps.len(), 3);
     }
 }
@@ -0,0 +1,149 @@
+// src/util.rs - Small helpers
+
+/// Return at most the first `n` characters of a hash for display.
+///
+/// Never panics: short strings are returned whole and multi-byte
+/// characters are never split.
+pub fn truncate_hash(s: &str, n: usize) -> &str {
+    match s.char_indices().nth(n) {
+        Some((idx, _)) => &s[..idx],
+        None => s,
+    }
+}
+
+/// Current Unix time in seconds
+pub fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// Escape `&`, `<`, `>`, `"` and `'` for use in XML text or attribute values
+pub fn xml_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// A fresh 64-character hex salt: 32 bytes from the operating system's
+/// random number generator.
+///
+/// Fails only if the OS generator is unavailable.
+pub fn random_salt() -> Result<String, getrandom::Error> {
+    let mut bytes = [0u8; 32];
+    getrandom::fill(&mut bytes)?;
+    Ok(hex::encode(bytes))
+}
+
+/// File name template for batch-proved proofs, e.g.
+/// `{computation}_{n}_{security}.json`.
+///
+/// Placeholders are `{computation}`, `{n}` and `{security}`. `{n}` is
+/// required so every proof of a batch gets its own file, and the template
+/// must be a bare file name; the directory is chosen separately.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofNameTemplate {
+    template: String,
+}
+
+impl ProofNameTemplate {
+    /// Placeholders a template may use
+    pub const PLACEHOLDERS: &'static [&'static str] = &["computation", "n", "security"];
+
+    /// Validate `template`
+    pub fn parse(template: &str) -> Result<Self, crate::error::ParameterError> {
+        let invalid = |reason: String| crate::error::ParameterError::InvalidTemplate {
+            template: template.to_string(),
+            reason,
+        };
+        if template.contains(['/', '\\']) {
+            return Err(invalid("must be a file name, not a path".to_string()));
+        }
+        let mut rest = template;
+        let mut has_n = false;
+        while let Some(open) = rest.find(['{', '}']) {
+            if rest[open..].starts_with('}') {
+                return Err(invalid("unmatched '}'".to_string()));
+            }
+            let close = rest[open..].find('}').ok_or_else(|| invalid("unclosed '{'".to_string()))? + open;
+            let name = &rest[open + 1..close];
+            if !Self::PLACEHOLDERS.contains(&name) {
+                return Err(invalid(format!("unknown placeholder {{{}}}", name)));
+            }
+            has_n |= name == "n";
+            rest = &rest[close + 1..];
+        }
+        if !has_n {
+            return Err(invalid("must contain {n} so each proof gets its own file".to_string()));
+        }
+        Ok(ProofNameTemplate { template: template.to_string() })
+    }
+
+    /// The file name for one proof of the batch
+    pub fn render(&self, computation: &str, n: u64, security: u32) -> String {
+        self.template
+            .replace("{computation}", computation)
+            .replace("{n}", &n.to_string())
+            .replace("{security}", &security.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_long_hash() {
+        let hash = "0123456789abcdef0123456789abcdef";
+        assert_eq!(truncate_hash(hash, 16), "0123456789abcdef");
+    }
+
+    #[test]
+    fn test_truncate_short_hash() {
+        assert_eq!(truncate_hash("abc", 16), "abc");
+        assert_eq!(truncate_hash("", 16), "");
+        assert_eq!(truncate_hash("abc", 0), "");
+    }
+
+    #[test]
+    fn test_truncate_multibyte() {
+        assert_eq!(truncate_hash("ééé", 2), "éé");
+    }
+
+    #[test]
+    fn test_random_salt() {
+        let salt = random_salt().unwrap();
+        assert_eq!(salt.len(), 64);
+        assert!(salt.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(salt, random_salt().unwrap());
+    }
+
+    #[test]
+    fn test_proof_name_template() {
+        let template = ProofNameTemplate::parse("{computation}_{n}_{security}.json").unwrap();
+        let names: Vec<String> = [5, 8, 13].iter().map(|&n| template.render("fibonacci", n, 128)).collect();
+        assert_eq!(names, ["fibonacci_5_128.json", "fibonacci_8_128.json", "fibonacci_13_128.json"]);
+        assert_eq!(ProofNameTemplate::parse("fib-{n}.json").unwrap().render("fibonacci", 3, 80), "fib-3.json");
+
+        for (bad, reason) in [
+            ("{computation}.json", "must contain {n}"),
+            ("{n}_{bits}.json", "unknown placeholder {bits}"),
+            ("{n.json", "unclosed '{'"),
+            ("n}.json", "unmatched '}'"),
+            ("out/{n}.json", "not a path"),
+        ] {
+            let error = ProofNameTemplate::parse(bad).unwrap_err().to_string();
+            assert!(error.contains(reason), "{}: {}", bad, error);
+        }
+    }
+}
@@ -1,73 +1,215 @@
 // src/verifier.rs - STARK Proof Verification
-use crate::types::{Proof, VerificationResult};
-use crate::crypto::verify_challenge;
+use crate::types::{Proof, ProofArchive, ProofTrace, ResultValue, VerificationResult, MAX_PROOF_BYTES};
+use crate::error::LoadError;
+use crate::checks::{
+    count_satisfied_constraints, ChallengeCheck, CheckOutcome, CommitmentOnlyCheck, ConstraintCheck,
+    ConstraintCommitmentCheck, EvaluationOpeningsCheck, FriFoldingCheck, FriLayersCheck, PaddingCheck,
+    QueryOpeningsCheck, RecomputeCheck, ResultBoundsCheck, SamplingCheck, SecurityLevelCheck, StepCountCheck, TraceCommitmentCheck,
+    VerificationCheck,
+};
+use crate::crypto::{HashAlgorithm, RandomOracle, Sha256Oracle};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::Arc;
+#[cfg(feature = "parallel")]
+use std::sync::{mpsc, Mutex};
+use std::time::{Duration, Instant};
+
+pub use crate::checks::RecomputeFn;
+
+/// Proof format versions this verifier understands
+pub const SUPPORTED_PROOF_VERSIONS: &[&str] = &["1.0"];
 
 /// STARK Verifier
+///
+/// Clones are cheap: checks and the oracle are shared, not copied.
+#[derive(Clone)]
 pub struct STARKVerifier {
     security_level: u32,
+    max_file_bytes: usize,
+    /// The recompute table, shared with the built-in checks
+    recompute: Arc<RecomputeCheck>,
+    oracle: Arc<dyn RandomOracle>,
+    constraint_target: Option<u64>,
+    allow_commitment_only: bool,
+    allow_interactive: bool,
+    builtin_checks: Vec<Arc<dyn VerificationCheck>>,
+    custom_checks: Vec<Arc<dyn VerificationCheck>>,
 }
 
 impl STARKVerifier {
     /// Create a new verifier
     pub fn new(security_level: u32) -> Self {
-        STARKVerifier { security_level }
+        let recompute: [(&str, RecomputeFn); 7] = [
+            ("fibonacci", recompute_fibonacci),
+            ("factorial", recompute_factorial),
+            ("modmul_chain", recompute_modmul_chain),
+            ("nth_prime", recompute_nth_prime),
+            ("linear_recurrence", recompute_linear_recurrence),
+            ("signed_linear_recurrence", recompute_signed_linear_recurrence),
+            ("hash_computation", recompute_hash_computation),
+        ];
+        let recompute = recompute.into_iter().map(|(computation, f)| (computation.to_string(), f)).collect();
+        let mut verifier = STARKVerifier {
+            security_level,
+            max_file_bytes: MAX_PROOF_BYTES,
+            recompute: Arc::new(RecomputeCheck { recompute }),
+            oracle: Arc::new(Sha256Oracle),
+            constraint_target: None,
+            allow_commitment_only: false,
+            allow_interactive: false,
+            builtin_checks: Vec::new(),
+            custom_checks: Vec::new(),
+        };
+        verifier.rebuild_checks();
+        verifier
+    }
+
+    /// Check challenges with a custom random oracle instead of SHA-256
+    pub fn with_oracle(mut self, oracle: impl RandomOracle + 'static) -> Self {
+        self.oracle = Arc::new(oracle);
+        self.rebuild_checks();
+        self
+    }
+
+    /// Require every constraint evaluation to equal `target` (0 for a
+    /// transition constraint that vanishes when satisfied).
+    ///
+    /// Without a target the constraint check is only a range check that
+    /// each evaluation lies below the field modulus.
+    pub fn with_constraint_target(mut self, target: u64) -> Self {
+        self.constraint_target = Some(target);
+        self.rebuild_checks();
+        self
+    }
+
+    /// Accept commitment-only proofs (see `STARKProver::prove_commitment`),
+    /// reporting a warning instead of failing them.
+    ///
+    /// Their trace commitment, challenge, constraints and result are still
+    /// checked; only the FRI checks are skipped.
+    pub fn with_commitment_only_allowed(mut self, allowed: bool) -> Self {
+        self.allow_commitment_only = allowed;
+        self.rebuild_checks();
+        self
+    }
+
+    /// Accept proofs whose challenge was drawn from a recorded seed (see
+    /// `STARKProver::with_interactive_challenge`).
+    ///
+    /// Off by default: the prover picks the seed, so such a challenge is not
+    /// bound to the commitment or the public inputs. Only enable this for
+    /// teaching or when the seed really came from an interactive verifier.
+    pub fn with_interactive_challenges_allowed(mut self, allowed: bool) -> Self {
+        self.allow_interactive = allowed;
+        self.rebuild_checks();
+        self
+    }
+
+    /// Refuse proofs larger than `bytes` (default `MAX_PROOF_BYTES`) in
+    /// every loader: `verify_from_file`, `verify_from_url` and each line
+    /// of `verify_ndjson_file`
+    pub fn with_max_file_bytes(mut self, bytes: usize) -> Self {
+        self.max_file_bytes = bytes;
+        self
+    }
+
+    /// Largest proof, in bytes, this verifier's loaders accept
+    pub fn max_file_bytes(&self) -> usize {
+        self.max_file_bytes
     }
 
-    /// Verify a STARK proof
+    /// Proof `version` values this verifier can check
+    pub fn supported_versions() -> &'static [&'static str] {
+        SUPPORTED_PROOF_VERSIONS
+    }
+
+    /// Register a recompute function for a computation kind.
+    ///
+    /// Proofs whose computation has no registered function still verify,
+    /// but their result is reported as not independently checked.
+    pub fn register_recompute(&mut self, computation: impl Into<String>, recompute: RecomputeFn) {
+        Arc::make_mut(&mut self.recompute).recompute.insert(computation.into(), recompute);
+        self.rebuild_checks();
+    }
+
+    /// Run `check` on every proof after the built-in checks.
+    ///
+    /// A failing custom check invalidates the proof exactly like a built-in
+    /// one, in both `verify` and `is_valid`.
+    pub fn add_check(&mut self, check: impl VerificationCheck + 'static) {
+        self.custom_checks.push(Arc::new(check));
+    }
+
+    /// Every check run after the structure and version gates, in order
+    pub fn checks(&self) -> impl Iterator<Item = &dyn VerificationCheck> {
+        self.builtin_checks.iter().chain(&self.custom_checks).map(|check| check.as_ref())
+    }
+
+    /// Recreate the built-in checks from the current configuration
+    fn rebuild_checks(&mut self) {
+        self.builtin_checks = vec![
+            Arc::new(TraceCommitmentCheck),
+            Arc::new(ConstraintCheck { security_level: self.security_level, target: self.constraint_target }),
+            Arc::new(CommitmentOnlyCheck { allowed: self.allow_commitment_only }),
+            Arc::new(FriLayersCheck),
+            Arc::new(FriFoldingCheck),
+            Arc::new(ChallengeCheck { oracle: Arc::clone(&self.oracle), allow_interactive: self.allow_interactive }),
+            Arc::new(SecurityLevelCheck { min_bits: self.security_level }),
+            Arc::clone(&self.recompute) as Arc<dyn VerificationCheck>,
+            Arc::new(ResultBoundsCheck),
+            Arc::new(SamplingCheck),
+            Arc::new(StepCountCheck),
+            Arc::new(PaddingCheck),
+            Arc::new(QueryOpeningsCheck),
+            Arc::new(EvaluationOpeningsCheck),
+            Arc::new(ConstraintCommitmentCheck),
+        ];
+    }
+
+    /// Verify a STARK proof.
+    ///
+    /// `checks_passed`, `checks_failed` and `warnings` list their messages
+    /// in check order (structure, version, then `checks()`), so reports of
+    /// the same proof are identical and safe to snapshot.
     pub fn verify(&self, proof: &Proof) -> VerificationResult {
         let mut checks_passed = Vec::new();
         let mut checks_failed = Vec::new();
+        let mut warnings = proof.validate_schema();
+        let mut timer = PhaseTimer::start();
 
         // Check 1: Verify proof structure
-        if self.check_proof_structure(proof) {
+        let structure_ok = self.check_proof_structure(proof);
+        timer.lap("structure");
+        if structure_ok {
             checks_passed.push("Proof structure is valid".to_string());
         } else {
             checks_failed.push("Invalid proof structure".to_string());
-            return VerificationResult::invalid(
-                "Proof structure validation failed",
-                checks_failed,
-            );
-        }
-
-        // Check 2: Verify trace commitment
-        if self.verify_trace_commitment(proof) {
-            checks_passed.push("Trace commitment verified".to_string());
-        } else {
-            checks_failed.push("Trace commitment verification failed".to_string());
+            return rejected("Proof structure validation failed", checks_passed, checks_failed, warnings, timer);
         }
 
-        // Check 3: Verify constraint evaluations
-        if self.verify_constraints(proof) {
-            checks_passed.push("Constraint evaluations verified".to_string());
-        } else {
-            checks_failed.push("Constraint evaluation verification failed".to_string());
-        }
-
-        // Check 4: Verify FRI layers
-        if self.verify_fri_layers(proof) {
-            checks_passed.push("FRI proof layers verified".to_string());
-        } else {
-            checks_failed.push("FRI proof layer verification failed".to_string());
+        // A proof from a newer format may mean something else entirely, so
+        // stop before interpreting any of its fields
+        let version_error = unsupported_version_error(proof);
+        timer.lap("version");
+        if let Some(error) = version_error {
+            checks_failed.push(error);
+            return rejected("Proof version is not supported", checks_passed, checks_failed, warnings, timer);
         }
 
-        // Check 5: Verify challenge consistency
-        if self.verify_challenge_consistency(proof) {
-            checks_passed.push("Challenge generation verified".to_string());
-        } else {
-            checks_failed.push("Challenge verification failed".to_string());
-        }
-
-        // Check 6: Verify security level
-        if proof.security_bits >= self.security_level {
-            checks_passed.push(format!(
-                "Security level adequate ({} bits)",
-                proof.security_bits
-            ));
-        } else {
-            checks_failed.push(format!(
-                "Insufficient security level ({} bits)",
-                proof.security_bits
-            ));
+        // Checks 2 onwards: built-in consistency checks, then custom ones
+        let constraints_satisfied = count_satisfied_constraints(proof, self.security_level, self.constraint_target);
+        for check in self.checks() {
+            match check.run(proof) {
+                CheckOutcome::Passed(message) => checks_passed.push(message),
+                CheckOutcome::Failed(message) => checks_failed.push(message),
+                CheckOutcome::Warning(message) => warnings.push(message),
+                CheckOutcome::Skipped => {
+                    timer.restart();
+                    continue;
+                }
+            }
+            timer.lap(check.name());
         }
 
         // Determine overall validity
@@ -78,7 +220,7 @@ impl STARKVerifier {
             "Proof is INVALID"
         };
 
-        if is_valid {
+        let mut result = if is_valid {
             VerificationResult::valid(message, checks_passed)
         } else {
             VerificationResult {
@@ -86,8 +228,85 @@ impl STARKVerifier {
                 message: message.to_string(),
                 checks_passed,
                 checks_failed,
+                constraints_satisfied: (0, 0),
+                warnings: Vec::new(),
+                timings: HashMap::new(),
             }
+        };
+        result.constraints_satisfied = constraints_satisfied;
+        result.warnings = warnings;
+        result.timings = timer.finish();
+        result
+    }
+
+    /// Check only the cheap preconditions for verifying `proof`: its
+    /// structure, version, computation, security level and hash algorithm.
+    ///
+    /// `Ok` means a full `verify` is possible, not that it will pass; use
+    /// it to route proofs before committing to the expensive checks.
+    pub fn can_verify(&self, proof: &Proof) -> Result<(), String> {
+        if !self.check_proof_structure(proof) {
+            return Err("Invalid proof structure".to_string());
+        }
+        if let Some(error) = unsupported_version_error(proof) {
+            return Err(error);
         }
+        if crate::computation::info(&proof.computation).is_none() && !self.recompute.recompute.contains_key(&proof.computation) {
+            return Err(format!("Unsupported computation {:?}", proof.computation));
+        }
+        if proof.security_bits < self.security_level {
+            return Err(format!(
+                "Insufficient security level ({} bits, need {})",
+                proof.security_bits, self.security_level
+            ));
+        }
+        if HashAlgorithm::from_name(&proof.hash_algorithm).is_none() {
+            return Err(format!("Unsupported hash algorithm {:?}", proof.hash_algorithm));
+        }
+        Ok(())
+    }
+
+    /// Verify a proof and additionally require its timestamp to be no older
+    /// than `max_age` (and not in the future).
+    ///
+    /// Plain `verify` only warns about implausible timestamps; this turns
+    /// them into hard failures. A proof without a timestamp cannot show
+    /// its age, so it fails here even though `verify` accepts it.
+    pub fn verify_with_max_age(&self, proof: &Proof, max_age: std::time::Duration) -> VerificationResult {
+        let mut result = self.verify(proof);
+        let now = crate::util::unix_now();
+
+        let timestamp = proof.timestamp.unwrap_or(0);
+        if timestamp == 0 {
+            result.record_check(false, "Proof has no timestamp".to_string());
+        } else if timestamp > now + crate::types::MAX_CLOCK_SKEW_SECS {
+            result.record_check(false, "Proof timestamp is in the future".to_string());
+        } else {
+            let age = now.saturating_sub(timestamp);
+            result.record_check(
+                age <= max_age.as_secs(),
+                format!("Proof age {}s within limit of {}s", age, max_age.as_secs()),
+            );
+        }
+        result
+    }
+
+    /// Verify a proof, returning only whether it is valid.
+    ///
+    /// Runs the same checks as `verify` but short-circuits on the first
+    /// failure and never builds report messages.
+    pub fn is_valid(&self, proof: &Proof) -> bool {
+        self.check_proof_structure(proof)
+            && Self::supported_versions().contains(&proof.version.as_str())
+            && self.checks().all(|check| check.passes(proof))
+    }
+
+    /// The result recomputed independently from the proof's public inputs.
+    ///
+    /// `None` if no recompute function is registered for the computation or
+    /// the public inputs are missing or out of range.
+    pub fn recompute_result(&self, proof: &Proof) -> Option<ResultValue> {
+        self.recompute.recompute_result(proof)
     }
 
     /// Check if proof has all required fields
@@ -97,58 +316,414 @@ impl STARKVerifier {
             && !proof.trace_commitment.is_empty()
             && !proof.constraint_evaluations.is_empty()
             && !proof.challenge.is_empty()
-            && !proof.fri_layers.is_empty()
+            && (!proof.fri_layers.is_empty() || proof.commitment_only)
+            && proof.constraints_per_step > 0
     }
 
-    /// Verify the trace commitment is properly formed
-    fn verify_trace_commitment(&self, proof: &Proof) -> bool {
-        let commitment = &proof.trace_commitment;
+    /// Check only that `constraint_commitment` is the hash of the proof's
+    /// evaluations under its hash algorithm.
+    ///
+    /// Stricter than the check inside `verify`: a proof without a
+    /// commitment (or with an unknown hash algorithm) returns false.
+    pub fn verify_constraint_commitment(&self, proof: &Proof) -> bool {
+        !proof.constraint_commitment.is_empty()
+            && proof.compute_constraint_commitment().as_ref() == Some(&proof.constraint_commitment)
+    }
 
-        // A valid SHA-256 hash should be 64 hex characters
-        commitment.len() == 64 && commitment.chars().all(|c| c.is_ascii_hexdigit())
+    /// Check that the proof's trace commitment binds exactly this trace
+    pub fn verify_trace_binding(&self, proof: &Proof, trace: &ProofTrace) -> bool {
+        let Some(algorithm) = proof.parsed_hash_algorithm() else {
+            return false;
+        };
+        let commitment = match &proof.commitment_salt {
+            Some(salt) => algorithm.commit_trace_salted(trace, salt),
+            None => algorithm.commit_trace(trace),
+        };
+        commitment == proof.trace_commitment
     }
 
-    /// Verify constraint evaluations
-    fn verify_constraints(&self, proof: &Proof) -> bool {
-        if proof.constraint_evaluations.is_empty() {
+    /// Recompute the trace hash chain and compare it with the proof.
+    ///
+    /// Returns false when the proof carries no chain, or when the trace's
+    /// steps were reordered, omitted or altered.
+    pub fn verify_hash_chain(&self, proof: &Proof, trace: &ProofTrace) -> bool {
+        let (Some(expected_final), Some(algorithm)) = (&proof.trace_chain, proof.parsed_hash_algorithm()) else {
             return false;
+        };
+        let links = algorithm.hash_chain(&trace.steps);
+        links.last() == Some(expected_final)
+            && (proof.trace_chain_links.is_empty() || proof.trace_chain_links == links)
+    }
+
+    /// Check that every `memo_lookup` step of a memoized trace (such as
+    /// fibonacci's) returns the value its input's `fib_compute` step
+    /// produced, see `ProofTrace::first_inconsistent_memo_lookup`.
+    ///
+    /// Traces without memo lookups pass trivially.
+    pub fn verify_memo_lookups(&self, trace: &ProofTrace) -> bool {
+        trace.first_inconsistent_memo_lookup().is_none()
+    }
+
+    /// Load and verify a proof from a JSON file
+    ///
+    /// The file must contain exactly one proof; use `verify_ndjson_file`
+    /// for newline-delimited files holding several. Files larger than the
+    /// limit set with `with_max_file_bytes` fail with `LoadError::TooLarge`
+    /// once the limit is reached.
+    pub fn verify_from_file(&self, filename: impl AsRef<Path>) -> Result<VerificationResult, LoadError> {
+        let proof = Proof::from_file_limited(filename, self.max_file_bytes)?;
+        Ok(self.verify(&proof))
+    }
+
+    /// Download a proof over HTTP and verify it.
+    ///
+    /// Download failures are reported as `LoadError::Network`, distinct
+    /// from a proof that loads but fails verification.
+    #[cfg(feature = "network")]
+    pub fn verify_from_url(&self, url: &str) -> Result<VerificationResult, LoadError> {
+        let body = crate::net::fetch(url, self.max_file_bytes)?;
+        let proof = Proof::from_json_limited(&body, self.max_file_bytes)?;
+        Ok(self.verify(&proof))
+    }
+
+    /// Like `verify_from_file`, but retry up to `attempts` times, sleeping
+    /// `delay` in between, while loading fails transiently (missing or still
+    /// empty file). Malformed proofs fail immediately.
+    pub fn verify_from_file_retry(
+        &self,
+        filename: impl AsRef<Path>,
+        attempts: usize,
+        delay: std::time::Duration,
+    ) -> Result<VerificationResult, LoadError> {
+        let filename = filename.as_ref();
+        let mut remaining = attempts.max(1);
+        loop {
+            remaining -= 1;
+            match self.verify_from_file(filename) {
+                Err(e) if e.is_transient() && remaining > 0 => std::thread::sleep(delay),
+                outcome => return outcome,
+            }
         }
+    }
 
-        // Check that constraint values are within expected range
-        let max_val = 1u64 << self.security_level.min(32);
-        proof
-            .constraint_evaluations
-            .iter()
-            .all(|&val| val < max_val)
+    /// Verify proofs on a pool of `threads` worker threads, yielding the
+    /// results in input order.
+    ///
+    /// The workers live as long as the returned iterator and take proofs
+    /// from a bounded channel, so a slow proof only holds up the others once
+    /// `2 * threads` proofs are in flight or awaiting their turn. Proofs are
+    /// pulled from `proofs` only as results are consumed, so at most that
+    /// many are held in memory. `threads` below 1 is treated as 1.
+    #[cfg(feature = "parallel")]
+    pub fn verify_stream<'a>(
+        &self,
+        proofs: impl Iterator<Item = Proof> + 'a,
+        threads: usize,
+    ) -> impl Iterator<Item = VerificationResult> + 'a {
+        let threads = threads.max(1);
+        let verifier = Arc::new(self.clone());
+        let (jobs, job_queue) = mpsc::sync_channel::<(usize, Proof)>(threads);
+        let job_queue = Arc::new(Mutex::new(job_queue));
+        let (results_tx, results) = mpsc::channel();
+        let workers = (0..threads)
+            .map(|_| {
+                let verifier = Arc::clone(&verifier);
+                let job_queue = Arc::clone(&job_queue);
+                let results_tx = results_tx.clone();
+                std::thread::spawn(move || loop {
+                    // The lock is released before verifying, so workers only
+                    // contend while taking the next job
+                    let job = job_queue.lock().expect("job queue poisoned").recv();
+                    let Ok((seq, proof)) = job else { break };
+                    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| verifier.verify(&proof)));
+                    if results_tx.send((seq, result)).is_err() {
+                        break;
+                    }
+                })
+            })
+            .collect();
+        VerifyStream {
+            proofs,
+            jobs: Some(jobs),
+            results,
+            pending: BTreeMap::new(),
+            sent: 0,
+            yielded: 0,
+            max_in_flight: 2 * threads,
+            workers,
+        }
     }
 
-    /// Verify FRI proof layers
-    fn verify_fri_layers(&self, proof: &Proof) -> bool {
-        if proof.fri_layers.is_empty() || proof.fri_layers.len() > 10 {
-            return false;
+    /// Verify every proof in an archive, in order
+    pub fn verify_archive(&self, archive: &ProofArchive) -> Vec<VerificationResult> {
+        archive.proofs.iter().map(|proof| self.verify(proof)).collect()
+    }
+
+    /// Verify every proof in a newline-delimited JSON file.
+    ///
+    /// Blank lines are skipped. A line that fails to parse, or is longer
+    /// than the limit set with `with_max_file_bytes`, yields an invalid
+    /// result naming the line rather than aborting the whole file. At most
+    /// one line past the limit is held in memory, however large the file.
+    pub fn verify_ndjson_file(&self, filename: impl AsRef<Path>) -> Result<Vec<VerificationResult>, LoadError> {
+        use std::io::{BufRead, Read};
+
+        let mut reader = std::io::BufReader::new(std::fs::File::open(filename)?);
+        let mut results = Vec::new();
+        let mut line = Vec::new();
+        for line_number in 1.. {
+            line.clear();
+            // Read one byte past the limit so an oversized line is detected
+            // without buffering all of it
+            if (&mut reader).take(self.max_file_bytes as u64 + 1).read_until(b'\n', &mut line)? == 0 {
+                break;
+            }
+            let oversized = line.last() != Some(&b'\n') && line.len() > self.max_file_bytes;
+            if oversized {
+                reader.skip_until(b'\n')?;
+            } else if line.trim_ascii().is_empty() {
+                continue;
+            }
+            let content = line.strip_suffix(b"\n").unwrap_or(&line);
+            results.push(match Proof::from_json_limited(content, self.max_file_bytes) {
+                Ok(proof) => self.verify(&proof),
+                Err(e) => VerificationResult::invalid(
+                    format!("Line {}: proof could not be loaded", line_number),
+                    vec![e.to_string()],
+                ),
+            });
         }
+        Ok(results)
+    }
+}
 
-        // Check that each layer is a valid hash
-        proof.fri_layers.iter().all(|layer| {
-            layer.len() == 64 && layer.chars().all(|c| c.is_ascii_hexdigit())
-        })
+/// Measures consecutive verification phases for `VerificationResult::timings`
+struct PhaseTimer {
+    last: Instant,
+    timings: HashMap<String, Duration>,
+}
+
+impl PhaseTimer {
+    fn start() -> Self {
+        PhaseTimer { last: Instant::now(), timings: HashMap::new() }
     }
 
-    /// Verify challenge was properly generated
-    fn verify_challenge_consistency(&self, proof: &Proof) -> bool {
-        verify_challenge(
-            &proof.trace_commitment,
-            proof.security_bits,
-            &proof.challenge,
+    /// Record the time since the previous lap under `phase`
+    fn lap(&mut self, phase: &str) {
+        let now = Instant::now();
+        self.timings.insert(phase.to_string(), now - self.last);
+        self.last = now;
+    }
+
+    /// Start the next lap now, discarding the time since the previous one
+    fn restart(&mut self) {
+        self.last = Instant::now();
+    }
+
+    fn finish(self) -> HashMap<String, Duration> {
+        self.timings
+    }
+}
+
+/// Iterator behind `STARKVerifier::verify_stream`: feeds the worker pool
+/// and puts results back in input order by sequence number
+#[cfg(feature = "parallel")]
+struct VerifyStream<I> {
+    proofs: I,
+    jobs: Option<mpsc::SyncSender<(usize, Proof)>>,
+    results: mpsc::Receiver<(usize, std::thread::Result<VerificationResult>)>,
+    /// Results that arrived ahead of an earlier proof, keyed by position
+    pending: BTreeMap<usize, VerificationResult>,
+    sent: usize,
+    yielded: usize,
+    max_in_flight: usize,
+    workers: Vec<std::thread::JoinHandle<()>>,
+}
+
+#[cfg(feature = "parallel")]
+impl<I: Iterator<Item = Proof>> Iterator for VerifyStream<I> {
+    type Item = VerificationResult;
+
+    fn next(&mut self) -> Option<VerificationResult> {
+        loop {
+            let jobs = self.jobs.as_ref()?;
+            while self.sent - self.yielded < self.max_in_flight {
+                let Some(proof) = self.proofs.next() else { break };
+                jobs.send((self.sent, proof)).expect("verification workers stopped");
+                self.sent += 1;
+            }
+            if let Some(result) = self.pending.remove(&self.yielded) {
+                self.yielded += 1;
+                return Some(result);
+            }
+            if self.yielded == self.sent {
+                return None;
+            }
+            let (seq, result) = self.results.recv().expect("verification workers stopped");
+            match result {
+                Ok(result) => self.pending.insert(seq, result),
+                Err(panic) => std::panic::resume_unwind(panic),
+            };
+        }
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl<I> Drop for VerifyStream<I> {
+    fn drop(&mut self) {
+        // Closing the job channel stops each worker after its current proof
+        self.jobs = None;
+        for worker in self.workers.drain(..) {
+            worker.join().ok();
+        }
+    }
+}
+
+/// Check that all proofs commit to the same trace.
+///
+/// Useful when several proofs (e.g. at different security levels) are meant
+/// to cover one computation. Empty and single-proof inputs are trivially
+/// consistent. Proofs with salted commitments never match, even for equal
+/// traces.
+pub fn verify_same_trace(proofs: &[Proof]) -> bool {
+    proofs
+        .windows(2)
+        .all(|pair| pair[0].trace_commitment == pair[1].trace_commitment)
+}
+
+/// The report for a proof rejected before the checks run, keeping what was
+/// already found
+fn rejected(
+    message: &str,
+    checks_passed: Vec<String>,
+    checks_failed: Vec<String>,
+    warnings: Vec<String>,
+    timer: PhaseTimer,
+) -> VerificationResult {
+    let mut result = VerificationResult::invalid(message, checks_failed);
+    result.checks_passed = checks_passed;
+    result.warnings = warnings;
+    result.timings = timer.finish();
+    result
+}
+
+/// Why the proof's format version is not understood, if it is not
+fn unsupported_version_error(proof: &Proof) -> Option<String> {
+    (!STARKVerifier::supported_versions().contains(&proof.version.as_str())).then(|| {
+        format!(
+            "Unsupported proof version {:?} (supported: {})",
+            proof.version,
+            STARKVerifier::supported_versions().join(", ")
         )
+    })
+}
+
+/// Recompute fibonacci(n) iteratively from the `n` public input
+fn recompute_fibonacci(inputs: &BTreeMap<String, u64>) -> Option<ResultValue> {
+    let n = *inputs.get("n")?;
+    let (mut a, mut b) = (0u128, 1u128);
+    for _ in 0..n {
+        let next = a.checked_add(b)?;
+        a = b;
+        b = next;
     }
+    Some(ResultValue::from_u128(a))
+}
 
-    /// Load and verify a proof from a JSON file
-    pub fn verify_from_file(&self, filename: &str) -> Result<VerificationResult, Box<dyn std::error::Error>> {
-        let json = std::fs::read_to_string(filename)?;
-        let proof: Proof = serde_json::from_str(&json)?;
-        Ok(self.verify(&proof))
+/// Recompute n! from the `n` public input
+fn recompute_factorial(inputs: &BTreeMap<String, u64>) -> Option<ResultValue> {
+    let n = *inputs.get("n")?;
+    (1..=n as u128).try_fold(1u128, |acc, i| acc.checked_mul(i)).map(ResultValue::from_u128)
+}
+
+/// Recompute a modular multiplication chain from its public inputs
+fn recompute_modmul_chain(inputs: &BTreeMap<String, u64>) -> Option<ResultValue> {
+    let modulus = *inputs.get("modulus")?;
+    let count = *inputs.get("count")?;
+    if modulus < 2 {
+        return None;
+    }
+    let mut acc = 1 % modulus;
+    for i in 0..count {
+        let value = *inputs.get(&format!("value_{}", i))?;
+        acc = ((acc as u128 * value as u128) % modulus as u128) as u64;
+    }
+    Some(ResultValue::U64(acc))
+}
+
+/// Recompute the n-th prime from the `n` public input
+fn recompute_nth_prime(inputs: &BTreeMap<String, u64>) -> Option<ResultValue> {
+    let n = *inputs.get("n")?;
+    if n == 0 || n > crate::computation::NTH_PRIME.max_input {
+        return None;
+    }
+    let mut found = 0;
+    let mut candidate = 1u64;
+    while found < n {
+        candidate += 1;
+        if (2..).take_while(|d| d * d <= candidate).all(|d| !candidate.is_multiple_of(d)) {
+            found += 1;
+        }
+    }
+    Some(ResultValue::U64(candidate))
+}
+
+/// Recompute the n-th term of a linear recurrence from its order,
+/// coefficients and initial values
+fn recompute_linear_recurrence(inputs: &BTreeMap<String, u64>) -> Option<ResultValue> {
+    let n = *inputs.get("n")?;
+    let order = *inputs.get("order")? as usize;
+    if order == 0 || n > crate::computation::LINEAR_RECURRENCE.max_input {
+        return None;
+    }
+    let coeffs: Vec<u128> = (0..order)
+        .map(|j| inputs.get(&format!("coeff_{}", j)).map(|&c| c as u128))
+        .collect::<Option<_>>()?;
+    let mut terms: Vec<u128> = (0..order)
+        .map(|j| inputs.get(&format!("initial_{}", j)).map(|&a| a as u128))
+        .collect::<Option<_>>()?;
+    while terms.len() <= n as usize {
+        let next = coeffs
+            .iter()
+            .zip(terms.iter().rev())
+            .try_fold(0u128, |acc, (&c, &a)| c.checked_mul(a).and_then(|w| acc.checked_add(w)))?;
+        terms.push(next);
+    }
+    Some(ResultValue::from_u128(terms[n as usize]))
+}
+
+/// Recompute the n-th term of a signed linear recurrence from its order,
+/// coefficients and initial values (stored in two's complement), or
+/// `None` if a term overflows i64
+fn recompute_signed_linear_recurrence(inputs: &BTreeMap<String, u64>) -> Option<ResultValue> {
+    let n = *inputs.get("n")?;
+    let order = *inputs.get("order")? as usize;
+    if order == 0 || n > crate::computation::SIGNED_LINEAR_RECURRENCE.max_input {
+        return None;
+    }
+    // Signed inputs are stored in two's complement
+    let coeffs: Vec<i64> = (0..order)
+        .map(|j| inputs.get(&format!("coeff_{}", j)).map(|&c| c as i64))
+        .collect::<Option<_>>()?;
+    let mut terms: Vec<i64> = (0..order)
+        .map(|j| inputs.get(&format!("initial_{}", j)).map(|&a| a as i64))
+        .collect::<Option<_>>()?;
+    while terms.len() <= n as usize {
+        let next = coeffs
+            .iter()
+            .zip(terms.iter().rev())
+            .try_fold(0i64, |acc, (&c, &a)| c.checked_mul(a).and_then(|w| acc.checked_add(w)))?;
+        terms.push(next);
     }
+    Some(ResultValue::from(terms[n as usize]))
+}
+
+/// Re-run the SHA-256 rounds of `hash_computation` from the `input` public input
+fn recompute_hash_computation(inputs: &BTreeMap<String, u64>) -> Option<ResultValue> {
+    let input = *inputs.get("input")?;
+    let digest = (0..crate::computation::HASH_ROUNDS)
+        .fold(format!("{:x}", input), |current, _| crate::crypto::hash_string(&current));
+    Some(ResultValue::Digest(digest))
 }
 
 #[cfg(test)]
@@ -156,12 +731,14 @@ mod tests {
     use super::*;
     use crate::prover::STARKProver;
     use crate::computation::fibonacci_with_trace;
+    use crate::checks::hex_digest_error;
+    use crate::crypto::query_indices;
 
     #[test]
     fn test_verify_valid_proof() {
         let prover = STARKProver::new(128);
-        let (result, trace) = fibonacci_with_trace(10);
-        let proof = prover.prove("fibonacci", result, &trace);
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let proof = prover.prove("fibonacci", result, &trace).unwrap();
 
         let verifier = STARKVerifier::new(128);
         let result = verifier.verify(&proof);
@@ -169,20 +746,1105 @@ mod tests {
         assert!(result.valid);
     }
 
+    #[test]
+    fn test_query_openings() {
+        let prover = STARKProver::new(128).with_num_queries(4);
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let proof = prover.prove("fibonacci", result, &trace).unwrap();
+        let verifier = STARKVerifier::new(128);
+
+        assert_eq!(proof.fri_queries.len(), 4);
+        assert!(proof.step_root.is_some());
+        let report = verifier.verify(&proof);
+        assert!(report.valid, "{:?}", report.checks_failed);
+        assert!(verifier.is_valid(&proof));
+
+        // A forged step no longer matches its authentication path
+        let mut forged = proof.clone();
+        forged.fri_queries[0].step.output += 1;
+        assert!(!verifier.verify(&forged).valid);
+        assert!(!verifier.is_valid(&forged));
+
+        // Openings at positions the challenge did not select are rejected
+        let mut moved = proof.clone();
+        moved.fri_queries[0].index = (moved.fri_queries[0].index + 1) % proof.num_steps;
+        assert!(!verifier.is_valid(&moved));
+
+        // Dropping the openings entirely is caught because the root is bound
+        // into the challenge
+        let mut stripped = proof.clone();
+        stripped.fri_queries.clear();
+        stripped.step_root = None;
+        assert!(!verifier.is_valid(&stripped));
+
+        // So is dropping some of the recorded number of queries
+        let mut truncated = proof.clone();
+        truncated.fri_queries.truncate(2);
+        assert!(!verifier.is_valid(&truncated));
+    }
+
+    #[test]
+    fn test_huge_query_count_rejected_without_allocating() {
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let proof = STARKProver::new(128).with_num_queries(4).prove("fibonacci", result, &trace).unwrap();
+        let verifier = STARKVerifier::new(128);
+
+        // Used to size the query position buffers and abort the process
+        let mut hostile = proof.clone();
+        hostile.num_queries = usize::MAX / 16;
+        hostile.num_steps = usize::MAX / 16;
+        let report = verifier.verify(&hostile);
+        assert!(!report.valid);
+        assert!(report.checks_failed.contains(&"Query opening verification failed".to_string()));
+        assert!(!verifier.is_valid(&hostile));
+
+        // Each field is checked on its own against the openings and evaluations
+        let mut more_queries = proof.clone();
+        more_queries.num_queries = 5;
+        assert!(!verifier.is_valid(&more_queries));
+        let mut more_steps = proof.clone();
+        more_steps.num_steps += 1;
+        assert!(!verifier.is_valid(&more_steps));
+    }
+
+    #[test]
+    fn test_more_queries_grow_the_proof() {
+        let (result, trace) = fibonacci_with_trace(20).unwrap();
+        let verifier = STARKVerifier::new(128);
+        let mut previous_size = 0;
+        for num_queries in [1, 4, 8] {
+            let proof = STARKProver::new(128).with_num_queries(num_queries).prove("fibonacci", result, &trace).unwrap();
+            assert_eq!(proof.num_queries, num_queries);
+            assert_eq!(proof.fri_queries.len(), num_queries);
+            let report = verifier.verify(&proof);
+            assert!(report.valid, "{:?}", report.checks_failed);
+            assert!(report.checks_passed.contains(&format!("Query openings verified ({} steps)", num_queries)));
+
+            let size = serde_json::to_string(&proof).unwrap().len();
+            assert!(size > previous_size);
+            previous_size = size;
+        }
+    }
+
+    #[test]
+    fn test_registered_recompute_is_used_by_verify() {
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let proof = STARKProver::new(128).prove("fibonacci", result, &trace).unwrap();
+
+        let builtin = STARKVerifier::new(128);
+        let mut overridden = builtin.clone();
+        overridden.register_recompute("fibonacci", |_| Some(ResultValue::U64(56)));
+
+        // `verify` and `recompute_result` read the same table
+        assert_eq!(overridden.recompute_result(&proof), Some(ResultValue::U64(56)));
+        let report = overridden.verify(&proof);
+        assert_eq!(report.checks_failed, ["Result mismatch (claimed 55, recomputed 56)"]);
+
+        // Registering on a clone leaves the original untouched
+        assert_eq!(builtin.recompute_result(&proof), Some(ResultValue::U64(55)));
+        assert!(builtin.verify(&proof).valid);
+    }
+
     #[test]
     fn test_verify_invalid_result() {
         let prover = STARKProver::new(128);
-        let (_, trace) = fibonacci_with_trace(10);
-        let mut proof = prover.prove("fibonacci", 55, &trace);
+        let (_, trace) = fibonacci_with_trace(10).unwrap();
+        let mut proof = prover.prove("fibonacci", 55u64, &trace).unwrap();
 
         // Tamper with the result
-        proof.result = 56;
+        proof.result = ResultValue::U64(56);
 
         let verifier = STARKVerifier::new(128);
         let result = verifier.verify(&proof);
 
-        // Result check still passes (we don't verify computation)
-        // but this shows the structure is sound
+        // Structural checks still pass, but recomputation catches the lie
         assert!(result.checks_passed.len() >= 4);
+        assert!(!result.valid);
+        assert!(result.checks_failed.iter().any(|c| c.contains("Result mismatch")));
+        assert_eq!(verifier.recompute_result(&proof), Some(ResultValue::U64(55)));
+        assert_ne!(verifier.recompute_result(&proof), Some(proof.result.clone()));
+    }
+
+    #[test]
+    fn test_constraints_satisfied_ratio() {
+        let prover = STARKProver::new(128);
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let mut proof = prover.prove("fibonacci", result, &trace).unwrap();
+        let total = proof.constraint_evaluations.len();
+
+        let verifier = STARKVerifier::new(128);
+        assert_eq!(verifier.verify(&proof).constraints_satisfied, (total, total));
+
+        proof.constraint_evaluations[2] = u64::MAX;
+        let result = verifier.verify(&proof);
+        assert!(!result.valid);
+        assert_eq!(result.constraints_satisfied, (total - 1, total));
+    }
+
+    #[test]
+    fn test_dropped_evaluation_detected() {
+        let prover = STARKProver::new(128);
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let mut proof = prover.prove("fibonacci", result, &trace).unwrap();
+        assert_eq!(proof.num_steps, trace.steps.len());
+
+        proof.constraint_evaluations.pop();
+        let result = STARKVerifier::new(128).verify(&proof);
+
+        assert!(!result.valid);
+        assert!(result
+            .checks_failed
+            .iter()
+            .any(|c| c.contains("does not match step count")));
+    }
+
+    #[test]
+    fn test_two_constraints_per_step() {
+        let prover = STARKProver::new(128).with_constraints_per_step(2).with_num_queries(3);
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let proof = prover.prove("fibonacci", result, &trace).unwrap();
+        let steps = trace.steps.len();
+        let modulus = 1u64 << 32;
+
+        assert_eq!(proof.constraints_per_step, 2);
+        assert_eq!(proof.constraint_evaluations.len(), 2 * steps);
+        for (index, step) in trace.steps.iter().enumerate() {
+            assert_eq!(proof.constraint_evaluation_for_step(index, 0), Some(step.constraint_value(0, modulus)));
+            assert_eq!(proof.constraint_evaluation_for_step(index, 1), Some(step.constraint_value(1, modulus)));
+        }
+        let verifier = STARKVerifier::new(128);
+        let result = verifier.verify(&proof);
+        assert!(result.valid, "{:?}", result.checks_failed);
+        assert!(result
+            .checks_passed
+            .contains(&format!("Evaluation count matches step count ({} x 2 constraints)", steps)));
+
+        // Dropping the second constraint's evaluations is a truncation
+        let mut truncated = proof.clone();
+        truncated.constraint_evaluations.truncate(steps);
+        truncated.constraint_commitment = truncated.compute_constraint_commitment().unwrap();
+        assert!(!verifier.is_valid(&truncated));
+
+        // An opened step must agree with every constraint, not just the first
+        let mut tampered = proof.clone();
+        let opened = tampered.fri_queries[0].index;
+        tampered.constraint_evaluations[steps + opened] ^= 1;
+        let result = verifier.verify(&tampered);
+        assert!(result.checks_failed.contains(&"Query opening verification failed".to_string()));
+    }
+
+    #[test]
+    fn test_composite_nth_prime_rejected() {
+        let cancel = crate::prover::CancellationToken::new();
+        let (prime, trace) = crate::computation::nth_prime_with_trace(10, &cancel).unwrap();
+        assert_eq!(prime, 29);
+        let mut proof = STARKProver::new(128).prove("nth_prime", prime, &trace).unwrap();
+        assert!(STARKVerifier::new(128).verify(&proof).valid);
+
+        // Without a recompute function only the sanity check catches it
+        proof.result = ResultValue::U64(27);
+        let mut verifier = STARKVerifier::new(128);
+        Arc::make_mut(&mut verifier.recompute).recompute.clear();
+        verifier.rebuild_checks();
+        let report = verifier.verify(&proof);
+        assert!(!report.valid);
+        assert_eq!(report.checks_failed, ["Result 27 is not a possible nth_prime result"]);
+    }
+
+    #[test]
+    fn test_report_order_is_stable() {
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let mut proof = STARKProver::new(128).prove("fibonacci", result, &trace).unwrap();
+        proof.result = ResultValue::U64(result + 1);
+        proof.fri_layers[1] = "0".repeat(64);
+
+        // Messages follow the check order on every run
+        let verifier = STARKVerifier::new(128);
+        for _ in 0..3 {
+            let report = verifier.verify(&proof);
+            assert_eq!(
+                report.checks_passed,
+                vec![
+                    "Proof structure is valid",
+                    "Trace commitment verified",
+                    "Constraint evaluations verified",
+                    "FRI proof layers verified",
+                    "Challenge generation verified",
+                    "Security level adequate (128 bits)",
+                    "Evaluation count matches step count (19)",
+                    "Constraint commitment verified",
+                ]
+            );
+            assert_eq!(
+                report.checks_failed,
+                vec![
+                    "FRI layer 1 does not match the folded evaluations",
+                    "Result mismatch (claimed 56, recomputed 55)",
+                    "Result 56 is not a possible fibonacci result",
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_forged_evaluation_openings_rejected() {
+        let prover = STARKProver::new(128).with_evaluation_openings(4);
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let proof = prover.prove("fibonacci", result, &trace).unwrap();
+        let verifier = STARKVerifier::new(128);
+        assert_eq!(proof.evaluation_queries.len(), 4);
+        let report = verifier.verify(&proof);
+        assert!(report.valid, "{:?}", report.checks_failed);
+        assert!(report.checks_passed.contains(&"Evaluation openings verified (4 queries)".to_string()));
+
+        let failed = |proof: &Proof| {
+            !verifier.is_valid(proof)
+                && verifier.verify(proof).checks_failed.contains(&"Evaluation opening verification failed".to_string())
+        };
+
+        // A forged value no longer matches the evaluations or its path
+        let mut forged = proof.clone();
+        forged.evaluation_queries[0].value += 1;
+        assert!(failed(&forged));
+
+        // Moving an opening to a position the challenge did not pick
+        let mut moved = proof.clone();
+        let index = (moved.evaluation_queries[0].index + 1) % moved.constraint_evaluations.len();
+        moved.evaluation_queries[0].index = index;
+        moved.evaluation_queries[0].value = moved.constraint_evaluations[index];
+        assert!(failed(&moved));
+
+        // A root over other evaluations
+        let mut rerooted = proof.clone();
+        rerooted.evaluation_root = Some(crate::crypto::evaluation_root(&[1, 2, 3]));
+        assert!(failed(&rerooted));
+
+        let mut dropped = proof.clone();
+        dropped.evaluation_queries.clear();
+        assert!(failed(&dropped));
+    }
+
+    #[test]
+    fn test_missing_num_steps_warns() {
+        let prover = STARKProver::new(128);
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let mut proof = prover.prove("fibonacci", result, &trace).unwrap();
+        proof.num_steps = 0;
+
+        let result = STARKVerifier::new(128).verify(&proof);
+        assert!(result.valid);
+        assert_eq!(result.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_verify_with_fixed_oracle() {
+        use crate::crypto::FixedOracle;
+
+        let oracle = FixedOracle::new("0123456789abcdef".repeat(4));
+        let prover = STARKProver::new(128).with_oracle(oracle.clone());
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let proof = prover.prove("fibonacci", result, &trace).unwrap();
+
+        assert!(STARKVerifier::new(128).with_oracle(oracle).verify(&proof).valid);
+        assert!(!STARKVerifier::new(128).verify(&proof).valid);
+    }
+
+    #[test]
+    fn test_extra_fri_layer_detected() {
+        let prover = STARKProver::new(128);
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let mut proof = prover.prove("fibonacci", result, &trace).unwrap();
+
+        proof.fri_layers.push("ab".repeat(32));
+        let result = STARKVerifier::new(128).verify(&proof);
+
+        assert!(!result.valid);
+        assert!(result
+            .checks_failed
+            .contains(&"FRI proof layer verification failed".to_string()));
+        // The layer count is only checked once
+        assert_eq!(result.checks_failed.len(), 1);
+        assert!(result.checks_passed.contains(&"FRI layers match the folded evaluations".to_string()));
+    }
+
+    #[test]
+    fn test_verify_hash_chain() {
+        use crate::prover::HashChainMode;
+
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let verifier = STARKVerifier::new(128);
+
+        for mode in [HashChainMode::Final, HashChainMode::Full] {
+            let proof = STARKProver::new(128)
+                .with_hash_chain(mode)
+                .prove("fibonacci", result, &trace)
+                .unwrap();
+            assert!(verifier.verify_hash_chain(&proof, &trace));
+
+            let mut reordered = trace.clone();
+            reordered.steps.swap(2, 3);
+            assert!(!verifier.verify_hash_chain(&proof, &reordered));
+        }
+
+        let plain = STARKProver::new(128).prove("fibonacci", result, &trace).unwrap();
+        assert!(!verifier.verify_hash_chain(&plain, &trace));
+    }
+
+    #[test]
+    fn test_is_valid_agrees_with_verify() {
+        let prover = STARKProver::new(128);
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let valid = prover.prove("fibonacci", result, &trace).unwrap();
+
+        let mut wrong_result = valid.clone();
+        wrong_result.result = ResultValue::U64(56);
+        let mut truncated = valid.clone();
+        truncated.constraint_evaluations.pop();
+        let mut bad_layer = valid.clone();
+        bad_layer.fri_layers[0] = "zz".to_string();
+        let mut bad_challenge = valid.clone();
+        bad_challenge.challenge = "0".repeat(32);
+        let unknown = prover.prove("custom", 7u64, &trace).unwrap();
+
+        let verifier = STARKVerifier::new(128);
+        assert_eq!(verifier.recompute_result(&unknown), None);
+        for proof in [valid, wrong_result, truncated, bad_layer, bad_challenge, unknown] {
+            assert_eq!(verifier.is_valid(&proof), verifier.verify(&proof).valid);
+        }
+    }
+
+    #[test]
+    fn test_verify_modmul_chain() {
+        use crate::computation::modmul_chain_with_trace;
+
+        let prover = STARKProver::new(128);
+        let (result, trace) = modmul_chain_with_trace(&[3, 5, 7, 11], 101);
+        let mut proof = prover.prove("modmul_chain", result, &trace).unwrap();
+
+        let verifier = STARKVerifier::new(128);
+        assert!(verifier.verify(&proof).valid);
+
+        proof.result = ResultValue::U64((result + 1) % 101);
+        assert!(!verifier.verify(&proof).valid);
+    }
+
+    #[test]
+    fn test_verify_ndjson_file() {
+        use crate::prover::Format;
+
+        let prover = STARKProver::new(128);
+        let mut contents = Vec::new();
+        for n in [5, 8] {
+            let (result, trace) = fibonacci_with_trace(n).unwrap();
+            let proof = prover.prove("fibonacci", result, &trace).unwrap();
+            prover.write_proof(&proof, &mut contents, Format::Ndjson).unwrap();
+        }
+        contents.extend_from_slice(b"\n{not json}\n");
+
+        let path = std::env::temp_dir().join(format!("stark_ndjson_{}.jsonl", std::process::id()));
+        std::fs::write(&path, &contents).unwrap();
+        let verifier = STARKVerifier::new(128);
+        let results = verifier.verify_ndjson_file(path.to_str().unwrap()).unwrap();
+
+        // The same file is not a single proof
+        let single = verifier.verify_from_file(path.to_str().unwrap());
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(results.len(), 3);
+        assert!(results[0].valid && results[1].valid);
+        assert!(!results[2].valid);
+        assert_eq!(results[2].message, "Line 4: proof could not be loaded");
+        assert!(matches!(single, Err(LoadError::TrailingData { .. })));
+    }
+
+    #[test]
+    fn test_verify_ndjson_file_limits_each_line() {
+        use crate::prover::Format;
+
+        let prover = STARKProver::new(128);
+        let (result, trace) = fibonacci_with_trace(5).unwrap();
+        let proof = prover.prove("fibonacci", result, &trace).unwrap();
+        let mut line = Vec::new();
+        prover.write_proof(&proof, &mut line, Format::Ndjson).unwrap();
+        let limit = line.len() + 16;
+        let mut contents = line.clone();
+        contents.extend_from_slice(&vec![b' '; limit * 3]);
+        contents.extend_from_slice(&line);
+        contents.extend_from_slice(&line);
+
+        let path = std::env::temp_dir().join(format!("stark_ndjson_limit_{}.jsonl", std::process::id()));
+        std::fs::write(&path, &contents).unwrap();
+        let results = STARKVerifier::new(128).with_max_file_bytes(limit).verify_ndjson_file(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        // The padded second line is refused; the lines around it still verify
+        assert_eq!(results.len(), 3);
+        assert!(results[0].valid && results[2].valid);
+        assert!(!results[1].valid);
+        assert_eq!(results[1].message, "Line 2: proof could not be loaded");
+        assert!(results[1].checks_failed[0].starts_with("proof too large"));
+    }
+
+    #[test]
+    fn test_verify_from_file_retry() {
+        use crate::prover::Format;
+        use std::time::{Duration, Instant};
+
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let prover = STARKProver::new(128);
+        let proof = prover.prove("fibonacci", result, &trace).unwrap();
+        let mut contents = Vec::new();
+        prover.write_proof(&proof, &mut contents, Format::Json).unwrap();
+
+        // The proof only appears after the first attempt has failed
+        let path = std::env::temp_dir().join(format!("stark_retry_{}.json", std::process::id()));
+        let writer_path = path.clone();
+        let writer = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(50));
+            std::fs::write(writer_path, contents).unwrap();
+        });
+        let verifier = STARKVerifier::new(128);
+        let report = verifier.verify_from_file_retry(path.to_str().unwrap(), 100, Duration::from_millis(10));
+        writer.join().unwrap();
+        assert!(report.unwrap().valid);
+
+        // Malformed content is returned at once instead of being retried
+        std::fs::write(&path, "{not json").unwrap();
+        let start = Instant::now();
+        let malformed = verifier.verify_from_file_retry(path.to_str().unwrap(), 3, Duration::from_secs(10));
+        std::fs::remove_file(&path).ok();
+        assert!(matches!(malformed, Err(LoadError::Malformed(_))));
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_verify_archive() {
+        let prover = STARKProver::new(128);
+        let mut proofs: Vec<Proof> = [5, 8, 13]
+            .into_iter()
+            .map(|n| {
+                let (result, trace) = fibonacci_with_trace(n).unwrap();
+                prover.prove("fibonacci", result, &trace).unwrap()
+            })
+            .collect();
+        proofs[1].result = ResultValue::U64(22);
+
+        let path = std::env::temp_dir().join(format!("stark_archive_{}.json", std::process::id()));
+        ProofArchive::new(proofs).save(path.to_str().unwrap()).unwrap();
+        let archive = ProofArchive::load(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        let results = STARKVerifier::new(128).verify_archive(&archive);
+        let validity: Vec<bool> = results.iter().map(|r| r.valid).collect();
+        assert_eq!(validity, vec![true, false, true]);
+    }
+
+    #[test]
+    fn test_constraint_commitment_binds_every_evaluation() {
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let proof = STARKProver::new(128).prove("fibonacci", result, &trace).unwrap();
+        let verifier = STARKVerifier::new(128);
+        assert!(verifier.verify(&proof).valid);
+
+        // The FRI layers only hash the first few evaluations; the commitment
+        // catches a change anywhere
+        for index in [0, proof.constraint_evaluations.len() - 1] {
+            let mut tampered = proof.clone();
+            tampered.constraint_evaluations[index] ^= 1;
+            assert_ne!(tampered.compute_constraint_commitment().unwrap(), proof.constraint_commitment);
+            let report = verifier.verify(&tampered);
+            assert!(!report.valid);
+            assert!(report.checks_failed.contains(&"Constraint commitment does not match evaluations".to_string()));
+            assert!(!verifier.is_valid(&tampered));
+        }
+    }
+
+    #[test]
+    fn test_trace_sampling() {
+        let (result, trace) = fibonacci_with_trace(30).unwrap();
+        let prover = STARKProver::new(128).with_trace_sampling(8);
+        let proof = prover.prove("fibonacci", result, &trace).unwrap();
+
+        assert_eq!(proof.sampled_steps.len(), 8);
+        assert_eq!(proof.constraint_evaluations.len(), 8);
+        assert!(proof.sampled_steps.iter().all(|&i| i < trace.steps.len()));
+        assert_eq!(proof.sampled_steps, query_indices(&proof.challenge, 8, trace.steps.len()));
+        assert_eq!(prover.prove("fibonacci", result, &trace).unwrap().sampled_steps, proof.sampled_steps);
+
+        let verifier = STARKVerifier::new(128);
+        let report = verifier.verify(&proof);
+        assert!(report.valid, "{:?}", report.checks_failed);
+        assert!(report.warnings.iter().any(|w| w.contains("unchecked")));
+
+        let mut resampled = proof.clone();
+        resampled.sampled_steps[0] = (resampled.sampled_steps[0] + 1) % trace.steps.len();
+        assert!(!verifier.verify(&resampled).valid);
+        assert!(!verifier.is_valid(&resampled));
+    }
+
+    #[test]
+    fn test_unsupported_version_rejected() {
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let mut proof = STARKProver::new(128).prove("fibonacci", result, &trace).unwrap();
+        proof.version = "2.0".to_string();
+
+        let verifier = STARKVerifier::new(128);
+        let report = verifier.verify(&proof);
+        assert!(!report.valid);
+        assert_eq!(report.message, "Proof version is not supported");
+        assert_eq!(report.checks_failed, vec!["Unsupported proof version \"2.0\" (supported: 1.0)".to_string()]);
+        assert!(!verifier.is_valid(&proof));
+    }
+
+    #[test]
+    fn test_early_rejections_keep_report_context() {
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let mut proof = STARKProver::new(128).prove("fibonacci", result, &trace).unwrap();
+        proof.timestamp = Some(0);
+        let verifier = STARKVerifier::new(128);
+
+        let mut newer = proof.clone();
+        newer.version = "2.0".to_string();
+        let report = verifier.verify(&newer);
+        assert_eq!(report.checks_passed, ["Proof structure is valid"]);
+        assert_eq!(report.warnings, ["Timestamp is zero"]);
+        assert!(report.timings.contains_key("structure") && report.timings.contains_key("version"));
+
+        let mut empty = proof.clone();
+        empty.computation.clear();
+        let report = verifier.verify(&empty);
+        assert_eq!(report.message, "Proof structure validation failed");
+        assert_eq!(report.warnings, ["Timestamp is zero"]);
+        assert!(report.timings.contains_key("structure"));
+    }
+
+    #[test]
+    fn test_builder_proof_with_invalid_fri_layer() {
+        use crate::types::ProofBuilder;
+
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let valid = STARKProver::new(128).prove("fibonacci", result, &trace).unwrap();
+        let mut layers = valid.fri_layers.clone();
+        layers[1] = "not a hash".to_string();
+
+        let proof = ProofBuilder::from_proof(&valid).with_fri_layers(layers).build();
+        let verifier = STARKVerifier::new(128);
+        let report = verifier.verify(&proof);
+        assert!(!report.valid);
+        assert_eq!(
+            report.checks_failed,
+            vec![
+                "FRI proof layer verification failed (fri_layers[1]: non-hex char 'n' at position 0)".to_string(),
+                "FRI layer 1 does not match the folded evaluations".to_string(),
+            ]
+        );
+
+        // A proof built from scratch has an empty structure
+        let empty = ProofBuilder::new().with_version("1.0").with_timestamp(42).build();
+        assert_eq!(empty.timestamp, Some(42));
+        assert!(!verifier.verify(&empty).valid);
+    }
+
+    #[test]
+    fn test_malformed_hashes_pinpointed() {
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let mut proof = STARKProver::new(128).prove("fibonacci", result, &trace).unwrap();
+        proof.trace_commitment.replace_range(5..6, "Z");
+        proof.fri_layers[1].replace_range(12..13, "g");
+        proof.fri_layers[2].truncate(63);
+
+        let report = STARKVerifier::new(128).verify(&proof);
+        assert!(report.checks_failed.contains(
+            &"Trace commitment verification failed (trace_commitment: non-hex char 'Z' at position 5)".to_string()
+        ));
+        assert!(report.checks_failed.contains(
+            &"FRI proof layer verification failed (fri_layers[1]: non-hex char 'g' at position 12)".to_string()
+        ));
+
+        assert_eq!(
+            hex_digest_error("fri_layers[2]", &proof.fri_layers[2]),
+            Some("fri_layers[2]: expected 64 hex characters, found 63".to_string())
+        );
+    }
+
+    #[test]
+    fn test_timings_cover_executed_checks() {
+        let (result, trace) = fibonacci_with_trace(20).unwrap();
+        let proof = STARKProver::new(128).with_num_queries(2).prove("fibonacci", result, &trace).unwrap();
+        let verification = STARKVerifier::new(128).verify(&proof);
+        assert!(verification.valid);
+
+        let mut phases: Vec<&str> = verification.timings.keys().map(String::as_str).collect();
+        phases.sort();
+        assert_eq!(
+            phases,
+            [
+                "challenge",
+                "constraint_commitment",
+                "constraints",
+                "fri_folding",
+                "fri_layers",
+                "query_openings",
+                "recompute",
+                "result_bounds",
+                "security_level",
+                "step_count",
+                "structure",
+                "trace_commitment",
+                "version",
+            ]
+        );
+
+        let mut report = Vec::new();
+        verification.write_report(&mut report, crate::types::ReportVerbosity::Full).unwrap();
+        assert!(String::from_utf8(report).unwrap().contains("Timings:"));
+    }
+
+    #[test]
+    fn test_hostile_parameters_fail_without_panicking() {
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let honest = STARKProver::new(128).with_num_queries(2).prove("fibonacci", result, &trace).unwrap();
+        let verifier = STARKVerifier::new(128);
+
+        let mut huge_degree = honest.clone();
+        huge_degree.constraint_degree = usize::MAX;
+        assert!(!verifier.verify(&huge_degree).valid);
+        assert!(!verifier.is_valid(&huge_degree));
+
+        // A layer count that matches a huge degree is still refused before
+        // the domain is built
+        let mut wide_domain = honest.clone();
+        wide_domain.constraint_degree = usize::MAX / 2;
+        let layers = crate::crypto::fri_layers_for_degree(honest.constraint_evaluations.len(), wide_domain.constraint_degree, 2);
+        wide_domain.fri_layers = vec![honest.fri_layers[0].clone(); layers];
+        assert!(!verifier.verify(&wide_domain).valid);
+        assert!(!verifier.is_valid(&wide_domain));
+
+        let mut many_openings = honest.clone();
+        many_openings.num_steps = usize::MAX;
+        let opening = many_openings.fri_queries[0].clone();
+        many_openings.fri_queries = vec![opening; 20_000];
+        assert!(!verifier.verify(&many_openings).valid);
+        assert!(!verifier.is_valid(&many_openings));
+    }
+
+    #[test]
+    fn test_salted_commitments_differ_but_verify() {
+        let (result, trace) = fibonacci_with_trace(12).unwrap();
+        let prover = STARKProver::new(128).with_commitment_salt(true);
+        let first = prover.prove("fibonacci", result, &trace).unwrap();
+        let second = prover.prove("fibonacci", result, &trace).unwrap();
+        assert_ne!(first.commitment_salt, second.commitment_salt);
+        assert_ne!(first.trace_commitment, second.trace_commitment);
+
+        let verifier = STARKVerifier::new(128);
+        for proof in [&first, &second] {
+            assert!(verifier.verify(proof).valid);
+            assert!(verifier.verify_trace_binding(proof, &trace));
+        }
+
+        // The binding check needs the recorded salt
+        let mut unsalted = first.clone();
+        unsalted.commitment_salt = None;
+        assert!(!verifier.verify_trace_binding(&unsalted, &trace));
+    }
+
+    #[test]
+    fn test_fri_folding_schedule_enforced() {
+        let (result, trace) = fibonacci_with_trace(20).unwrap();
+        let proof = STARKProver::new(128).prove("fibonacci", result, &trace).unwrap();
+        let verifier = STARKVerifier::new(128);
+
+        // Half the evaluations fold in fewer layers than the proof carries
+        let mut shortened = proof.clone();
+        shortened.constraint_evaluations.truncate(proof.constraint_evaluations.len() / 2);
+        let verification = verifier.verify(&shortened);
+        assert!(verification.checks_failed.contains(&"FRI proof layer verification failed".to_string()));
+        assert!(!verifier.is_valid(&shortened));
+
+        // A well-formed digest in the right place still has to be the fold
+        let mut swapped = proof.clone();
+        swapped.fri_layers.swap(1, 2);
+        let verification = verifier.verify(&swapped);
+        assert!(verification.checks_failed.contains(&"FRI layer 1 does not match the folded evaluations".to_string()));
+        assert!(!verifier.is_valid(&swapped));
+    }
+
+    #[test]
+    fn test_verify_from_file_rejects_directory() {
+        let dir = std::env::temp_dir();
+        assert!(matches!(
+            STARKVerifier::new(128).verify_from_file(dir.to_str().unwrap()),
+            Err(LoadError::IsDirectory { .. })
+        ));
+    }
+
+    #[test]
+    fn test_verify_from_file_rejects_oversized_file() {
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let proof = STARKProver::new(128).prove("fibonacci", result, &trace).unwrap();
+        let path = std::env::temp_dir().join(format!("stark_oversized_{}.json", std::process::id()));
+        let mut json = serde_json::to_string(&proof).unwrap();
+        json.push_str(&" ".repeat(4096));
+        std::fs::write(&path, &json).unwrap();
+
+        let filename = path.to_str().unwrap();
+        assert!(STARKVerifier::new(128).verify_from_file(filename).unwrap().valid);
+        let error = STARKVerifier::new(128).with_max_file_bytes(4096).verify_from_file(filename).unwrap_err();
+        assert!(matches!(error, LoadError::TooLarge { max: 4096 }));
+        assert!(error.to_string().starts_with("proof too large"));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_custom_check_participates() {
+        struct AlwaysFail;
+
+        impl VerificationCheck for AlwaysFail {
+            fn name(&self) -> &str {
+                "always_fail"
+            }
+
+            fn run(&self, _proof: &Proof) -> CheckOutcome {
+                CheckOutcome::Failed("Rejected by policy".to_string())
+            }
+        }
+
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let proof = STARKProver::new(128).prove("fibonacci", result, &trace).unwrap();
+        let mut verifier = STARKVerifier::new(128);
+        assert!(verifier.is_valid(&proof));
+
+        verifier.add_check(AlwaysFail);
+        let verification = verifier.verify(&proof);
+        assert!(!verification.valid);
+        assert_eq!(verification.checks_failed, ["Rejected by policy"]);
+        assert!(verification.timings.contains_key("always_fail"));
+        assert!(!verifier.is_valid(&proof));
+    }
+
+    #[test]
+    fn test_can_verify() {
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let proof = STARKProver::new(128).prove("fibonacci", result, &trace).unwrap();
+        let verifier = STARKVerifier::new(128);
+        assert_eq!(verifier.can_verify(&proof), Ok(()));
+
+        let mut unknown_hash = proof.clone();
+        unknown_hash.hash_algorithm = "md5".to_string();
+        assert_eq!(verifier.can_verify(&unknown_hash), Err("Unsupported hash algorithm \"md5\"".to_string()));
+
+        let mut unknown_computation = proof.clone();
+        unknown_computation.computation = "collatz".to_string();
+        assert!(verifier.can_verify(&unknown_computation).is_err());
+
+        assert!(STARKVerifier::new(192).can_verify(&proof).is_err());
+    }
+
+    #[test]
+    fn test_constraint_target() {
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let mut proof = STARKProver::new(128).prove("fibonacci", result, &trace).unwrap();
+        let steps = proof.unpadded_evaluations().len();
+        proof.constraint_evaluations.iter_mut().for_each(|eval| *eval = 0);
+
+        let verifier = STARKVerifier::new(128).with_constraint_target(0);
+        let satisfied = verifier.verify(&proof);
+        assert_eq!(satisfied.constraints_satisfied, (steps, steps));
+        assert!(satisfied.checks_passed.contains(&"Constraint evaluations verified".to_string()));
+
+        // A single violated constraint fails the check
+        proof.constraint_evaluations[3] = 7;
+        let violated = verifier.verify(&proof);
+        assert!(!violated.valid);
+        assert_eq!(violated.constraints_satisfied, (steps - 1, steps));
+        assert!(violated.checks_failed.iter().any(|c| c.contains(&format!("1 of {} evaluations differ from 0", steps))));
+
+        // Without a target the same evaluations pass the range check
+        let range_only = STARKVerifier::new(128).verify(&proof);
+        assert!(range_only.checks_passed.contains(&"Constraint evaluations verified".to_string()));
+    }
+
+    #[test]
+    fn test_linear_recurrence_recomputed() {
+        use crate::computation::linear_recurrence_with_trace;
+
+        let (term, trace) = linear_recurrence_with_trace(&[1, 1, 1], &[0, 0, 1], 20).unwrap();
+        let mut proof = STARKProver::new(128).prove("linear_recurrence", term, &trace).unwrap();
+        let verifier = STARKVerifier::new(128);
+        assert!(verifier.verify(&proof).valid);
+
+        proof.result = ResultValue::U64(term + 1);
+        assert!(!verifier.is_valid(&proof));
+    }
+
+    #[test]
+    fn test_signed_linear_recurrence_with_negative_terms() {
+        use crate::computation::signed_linear_recurrence_with_trace;
+
+        // a_i = a_(i-1) - 2 * a_(i-2): 1, 1, -1, -3, -1, 5, ...
+        let (term, trace) = signed_linear_recurrence_with_trace(&[1, -2], &[1, 1], 4).unwrap();
+        assert_eq!(term, -1);
+        let outputs: Vec<i64> = trace.steps.iter().map(|step| step.signed_output()).collect();
+        assert_eq!(outputs, vec![1, 1, -1, -3, -1]);
+        assert_eq!(trace.signed_input("coeff_1"), Some(-2));
+
+        let mut proof = STARKProver::new(128).prove("signed_linear_recurrence", term, &trace).unwrap();
+        assert_eq!(proof.result.as_i64(), Some(-1));
+        let verifier = STARKVerifier::new(128);
+        let result = verifier.verify(&proof);
+        assert!(result.valid, "{:?}", result.checks_failed);
+        assert_eq!(verifier.recompute_result(&proof), Some(ResultValue::I64(-1)));
+
+        proof.result = ResultValue::from(1i64);
+        assert!(!verifier.is_valid(&proof));
+    }
+
+    #[test]
+    fn test_verify_constraint_commitment() {
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let proof = STARKProver::new(128).prove("fibonacci", result, &trace).unwrap();
+        let verifier = STARKVerifier::new(128);
+        assert!(verifier.verify_constraint_commitment(&proof));
+
+        let mut tampered = proof.clone();
+        tampered.constraint_evaluations[4] += 1;
+        assert!(verifier.check_proof_structure(&tampered));
+        assert!(!verifier.verify_constraint_commitment(&tampered));
+
+        let mut uncommitted = proof.clone();
+        uncommitted.constraint_commitment.clear();
+        assert!(!verifier.verify_constraint_commitment(&uncommitted));
+    }
+
+    #[test]
+    fn test_prove_and_verify_hash_computation() {
+        use crate::computation::hash_computation_with_trace;
+
+        let (digest, trace) = hash_computation_with_trace(42);
+        let proof = STARKProver::new(128)
+            .prove("hash_computation", ResultValue::Digest(digest.clone()), &trace)
+            .unwrap();
+        let verifier = STARKVerifier::new(128);
+        let result = verifier.verify(&proof);
+        assert!(result.valid, "{:?}", result.checks_failed);
+        assert_eq!(verifier.recompute_result(&proof), Some(ResultValue::Digest(digest.clone())));
+
+        let decoded = Proof::from_json(&serde_json::to_string(&proof).unwrap()).unwrap();
+        assert_eq!(decoded.result.as_digest(), Some(digest.as_str()));
+        assert!(verifier.verify(&decoded).valid);
+
+        let mut wrong = proof.clone();
+        wrong.result = ResultValue::Digest(hash_computation_with_trace(43).0);
+        assert!(!verifier.is_valid(&wrong));
+    }
+
+    #[test]
+    fn test_tampered_public_inputs_detected() {
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let proof = STARKProver::new(128).prove("fibonacci", result, &trace).unwrap();
+        let verifier = STARKVerifier::new(128);
+        assert!(proof.inputs_bound);
+        assert!(verifier.verify(&proof).valid);
+
+        // A consistent input/result pair still recomputes, but no longer
+        // matches the challenge
+        let mut tampered = proof.clone();
+        tampered.public_inputs.insert("n".to_string(), 11);
+        tampered.result = ResultValue::from(89u64);
+        let result = verifier.verify(&tampered);
+        assert!(!result.valid);
+        assert!(result.checks_failed.contains(&"Challenge verification failed".to_string()));
+        assert!(!result.checks_failed.iter().any(|check| check.starts_with("Result mismatch")));
+
+        // Clearing the flag changes the expected challenge too
+        let mut unbound = proof.clone();
+        unbound.inputs_bound = false;
+        assert!(!verifier.is_valid(&unbound));
+    }
+
+    #[test]
+    fn test_legacy_proof_without_public_inputs_warns() {
+        // A fibonacci proof in the format from before public inputs were
+        // recorded: no `public_inputs` and no `inputs_bound`
+        let proof = Proof::from_json(include_str!("../tests/fixtures/legacy_fib_10.json")).unwrap();
+        assert!(proof.public_inputs.is_empty() && !proof.inputs_bound);
+
+        let result = STARKVerifier::new(128).verify(&proof);
+        assert!(result.valid, "{:?}", result.checks_failed);
+        assert!(result
+            .warnings
+            .contains(&"Result not independently checked (legacy proof without public inputs)".to_string()));
+
+        // A proof that binds its (empty) inputs is still recomputed
+        let mut bound = proof.clone();
+        bound.inputs_bound = true;
+        assert!(!STARKVerifier::new(128).is_valid(&bound));
+    }
+
+    #[test]
+    fn test_tampered_padding_detected() {
+        use crate::types::PaddingMode;
+
+        let prover = STARKProver::new(128).with_padding(PaddingMode::RepeatLast);
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let mut proof = prover.prove("fibonacci", result, &trace).unwrap();
+        let verifier = STARKVerifier::new(128);
+        assert!(verifier.verify(&proof).valid);
+
+        *proof.constraint_evaluations.last_mut().unwrap() += 1;
+        let result = verifier.verify(&proof);
+        assert!(!result.valid);
+        assert!(result.checks_failed.contains(&"Evaluation padding is inconsistent".to_string()));
+        assert_eq!(verifier.is_valid(&proof), result.valid);
+    }
+
+    #[test]
+    fn test_timestamp_checks() {
+        use std::time::Duration;
+
+        let prover = STARKProver::new(128);
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let proof = prover.prove("fibonacci", result, &trace).unwrap();
+        let verifier = STARKVerifier::new(128);
+        let day = Duration::from_secs(24 * 3600);
+
+        assert!(verifier.verify_with_max_age(&proof, day).valid);
+
+        let mut zero = proof.clone();
+        zero.timestamp = Some(0);
+        let soft = verifier.verify(&zero);
+        assert!(soft.valid);
+        assert!(soft.warnings.contains(&"Timestamp is zero".to_string()));
+        assert!(!verifier.verify_with_max_age(&zero, day).valid);
+
+        let mut future = proof.clone();
+        future.timestamp = future.timestamp.map(|t| t + 10 * 365 * 24 * 3600);
+        assert!(verifier.verify(&future).valid);
+        assert!(!verifier.verify_with_max_age(&future, day).valid);
+
+        let mut old = proof;
+        old.timestamp = old.timestamp.map(|t| t - 2 * 24 * 3600);
+        assert!(!verifier.verify_with_max_age(&old, day).valid);
+    }
+
+    #[test]
+    fn test_timestamp_free_proof_verifies() {
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let prover = STARKProver::new(128).with_timestamp_omitted(true);
+        let proof = prover.prove("fibonacci", result, &trace).unwrap();
+        assert_eq!(proof.timestamp, None);
+        assert!(!serde_json::to_string(&proof).unwrap().contains("timestamp"));
+
+        let report = STARKVerifier::new(128).verify(&proof);
+        assert!(report.valid, "{:?}", report.checks_failed);
+        assert!(report.warnings.is_empty(), "{:?}", report.warnings);
+
+        // Two proofs of the same trace are now byte-for-byte identical
+        let again = prover.prove("fibonacci", result, &trace).unwrap();
+        assert_eq!(serde_json::to_string(&again).unwrap(), serde_json::to_string(&proof).unwrap());
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_verify_stream_preserves_order() {
+        let prover = STARKProver::new(128);
+        let proofs: Vec<Proof> = (5..15)
+            .map(|n| {
+                let (result, trace) = fibonacci_with_trace(n).unwrap();
+                let mut proof = prover.prove("fibonacci", result, &trace).unwrap();
+                // Every third proof claims a wrong result
+                if n % 3 == 0 {
+                    proof.result = ResultValue::U64(result + 1);
+                }
+                proof
+            })
+            .collect();
+
+        let verifier = STARKVerifier::new(128);
+        let expected: Vec<bool> = (5..15).map(|n| n % 3 != 0).collect();
+        for threads in [1, 3, 16] {
+            let valid: Vec<bool> = verifier.verify_stream(proofs.clone().into_iter(), threads).map(|r| r.valid).collect();
+            assert_eq!(valid, expected, "{} threads", threads);
+        }
+
+        // Proofs are pulled only as results are consumed
+        let pulled = std::cell::Cell::new(0);
+        let counted = proofs.iter().cloned().inspect(|_| pulled.set(pulled.get() + 1));
+        let mut stream = verifier.verify_stream(counted, 2);
+        assert_eq!(stream.next().map(|r| r.valid), Some(expected[0]));
+        assert!(pulled.get() <= 4, "pulled {} proofs for one result", pulled.get());
+        let rest: Vec<bool> = stream.take(2).map(|r| r.valid).collect();
+        assert_eq!(rest, expected[1..3]);
+    }
+
+    #[test]
+    fn test_verify_same_trace() {
+        let (result, trace) = fibonacci_with_trace(10).unwrap();
+        let at_80 = STARKProver::new(80).prove("fibonacci", result, &trace).unwrap();
+        let at_128 = STARKProver::new(128).prove("fibonacci", result, &trace).unwrap();
+        let (other_result, other_trace) = fibonacci_with_trace(11).unwrap();
+        let other = STARKProver::new(128).prove("fibonacci", other_result, &other_trace).unwrap();
+
+        assert!(verify_same_trace(&[]));
+        assert!(verify_same_trace(std::slice::from_ref(&other)));
+        assert!(verify_same_trace(&[at_80.clone(), at_128.clone()]));
+        assert!(!verify_same_trace(&[at_80, at_128, other]));
+    }
+
+    #[test]
+    fn test_verify_trace_binding() {
+        use crate::computation::modmul_chain_with_trace;
+        use crate::types::TraceBundle;
+
+        let (result, trace) = modmul_chain_with_trace(&[3, 5, 7], 101);
+        let proof = STARKProver::new(128).prove("modmul_chain", result, &trace).unwrap();
+        let verifier = STARKVerifier::new(128);
+        assert!(verifier.verify_trace_binding(&proof, &trace));
+
+        // A trace that went through a bundle round trip still binds
+        let bundle = TraceBundle::new("modmul_chain", result, trace, None);
+        let reloaded: TraceBundle = serde_json::from_str(&serde_json::to_string(&bundle).unwrap()).unwrap();
+        assert!(verifier.verify_trace_binding(&proof, &reloaded.trace));
+
+        let (_, substitute) = modmul_chain_with_trace(&[3, 5, 8], 101);
+        assert!(!verifier.verify_trace_binding(&proof, &substitute));
+    }
+
+    #[test]
+    fn test_verify_memo_lookups() {
+        let (_, trace) = fibonacci_with_trace(10).unwrap();
+        let verifier = STARKVerifier::new(128);
+        assert!(verifier.verify_memo_lookups(&trace));
+
+        let lookup = trace.steps.iter().position(|step| step.operation == "memo_lookup").unwrap();
+        let mut lying = trace.clone();
+        lying.steps[lookup].output += 1;
+        assert!(!verifier.verify_memo_lookups(&lying));
+        assert_eq!(lying.first_inconsistent_memo_lookup(), Some(lookup));
+
+        // A lookup of a value nothing computed yet is rejected too
+        let mut premature = trace.clone();
+        premature.steps[0].operation = "memo_lookup".to_string();
+        assert_eq!(premature.first_inconsistent_memo_lookup(), Some(0));
+
+        let (_, factorial_trace) = crate::computation::factorial_with_trace(5);
+        assert!(verifier.verify_memo_lookups(&factorial_trace));
+    }
+
+    #[test]
+    fn test_verify_unknown_computation_skips_result_check() {
+        let prover = STARKProver::new(128);
+        let (_, trace) = fibonacci_with_trace(10).unwrap();
+        let proof = prover.prove("custom_vm_program", 12345u64, &trace).unwrap();
+
+        let verifier = STARKVerifier::new(128);
+        let result = verifier.verify(&proof);
+
+        assert!(result.valid);
+        assert!(result
+            .checks_passed
+            .iter()
+            .any(|c| c == "Result not independently checked (no recompute available)"));
     }
 }
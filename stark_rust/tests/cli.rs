@@ -0,0 +1,359 @@
+// tests/cli.rs - End-to-end tests driving the stark-prover binary
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_stark-prover"))
+        .args(args)
+        .output()
+        .expect("failed to run stark-prover")
+}
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("stark_cli_{}_{}", std::process::id(), name))
+}
+
+#[test]
+fn test_prove_then_verify() {
+    let proof_file = temp_path("honest.json");
+    let proof_path = proof_file.to_str().unwrap();
+
+    assert!(run(&["prove", "10", "--output", proof_path]).status.success());
+    assert!(run(&["verify", proof_path]).status.success());
+
+    std::fs::remove_file(proof_file).ok();
+}
+
+#[test]
+fn test_forced_result_fails_verification() {
+    let proof_file = temp_path("forced.json");
+    let proof_path = proof_file.to_str().unwrap();
+
+    let prove = run(&["prove", "10", "--output", proof_path, "--force-result", "56"]);
+    assert!(prove.status.success());
+    assert!(String::from_utf8_lossy(&prove.stdout).contains("DEMONSTRATION ONLY"));
+
+    assert!(!run(&["verify", proof_path]).status.success());
+
+    std::fs::remove_file(proof_file).ok();
+}
+
+#[test]
+fn test_verify_verbosity_flags() {
+    let proof_file = temp_path("verbosity.json");
+    let proof_path = proof_file.to_str().unwrap();
+    assert!(run(&["prove", "10", "--output", proof_path]).status.success());
+
+    let quiet = run(&["verify", proof_path, "--quiet"]);
+    let summary = run(&["verify", proof_path]);
+    let verbose = run(&["verify", proof_path, "--verbose"]);
+    assert!(quiet.status.success() && summary.status.success() && verbose.status.success());
+    assert!(quiet.stdout.is_empty());
+    assert_eq!(String::from_utf8_lossy(&summary.stdout).lines().count(), 1);
+    assert!(String::from_utf8_lossy(&verbose.stdout).contains("Checks Passed"));
+
+    std::fs::remove_file(proof_file).ok();
+}
+
+#[test]
+fn test_prove_sum_file() {
+    let input_file = temp_path("values.txt");
+    let proof_file = temp_path("sum.json");
+    std::fs::write(&input_file, "1\n2\n39\n").unwrap();
+    let input_path = input_file.to_str().unwrap();
+    let proof_path = proof_file.to_str().unwrap();
+
+    let prove = run(&["prove", "--computation", "sum-file", "--input", input_path, "--output", proof_path]);
+    assert!(prove.status.success());
+    assert!(String::from_utf8_lossy(&prove.stdout).contains("sum = 42"));
+    assert!(run(&["verify", proof_path]).status.success());
+
+    std::fs::write(&input_file, "1\nnot a number\n").unwrap();
+    let malformed = run(&["prove", "--computation", "sum-file", "--input", input_path, "--output", proof_path]);
+    assert!(!malformed.status.success());
+    assert!(String::from_utf8_lossy(&malformed.stderr).contains("line 2"));
+
+    std::fs::remove_file(input_file).ok();
+    std::fs::remove_file(proof_file).ok();
+}
+
+#[test]
+fn test_prove_trace_from_stdin() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let prove_trace = |stdin: &str, output: &str| {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_stark-prover"))
+            .args(["prove-trace", "-", "--computation", "piped", "--output", output])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("failed to run stark-prover");
+        child.stdin.take().unwrap().write_all(stdin.as_bytes()).unwrap();
+        child.wait_with_output().unwrap()
+    };
+
+    let proof_file = temp_path("piped.json");
+    let proof_path = proof_file.to_str().unwrap();
+    let trace = r#"{"steps": [{"step": 0, "operation": "add", "input": 1, "output": 3, "depth": 0}],
+                    "inputs": {"count": 1}, "outputs": {"result": 3}}"#;
+
+    let proved = prove_trace(trace, proof_path);
+    assert!(proved.status.success());
+    assert!(run(&["verify", proof_path]).status.success());
+
+    let empty = prove_trace("", proof_path);
+    let malformed = prove_trace("{\"steps\": ", proof_path);
+    assert!(!empty.status.success() && !malformed.status.success());
+    assert!(String::from_utf8_lossy(&empty.stderr).contains("input is empty"));
+    assert!(String::from_utf8_lossy(&malformed.stderr).contains("malformed JSON"));
+
+    std::fs::remove_file(proof_file).ok();
+}
+
+#[test]
+fn test_exit_codes() {
+    let proof_file = temp_path("exit_codes.json");
+    let proof_path = proof_file.to_str().unwrap();
+    assert_eq!(run(&["prove", "10", "--output", proof_path]).status.code(), Some(0));
+    assert_eq!(run(&["verify", proof_path]).status.code(), Some(0));
+
+    // Invalid proof
+    assert_eq!(run(&["prove", "10", "--output", proof_path, "--force-result", "56"]).status.code(), Some(0));
+    assert_eq!(run(&["verify", proof_path, "--quiet"]).status.code(), Some(1));
+
+    // Unreadable file
+    let missing = temp_path("missing.json");
+    assert_eq!(run(&["verify", missing.to_str().unwrap()]).status.code(), Some(2));
+    let dir = std::env::temp_dir();
+    assert_eq!(run(&["prove", "10", "--output", dir.to_str().unwrap()]).status.code(), Some(2));
+
+    // Malformed proof
+    std::fs::write(&proof_file, "{\"version\": ").unwrap();
+    assert_eq!(run(&["verify", proof_path]).status.code(), Some(3));
+
+    // Unsupported version
+    assert_eq!(run(&["prove", "10", "--output", proof_path]).status.code(), Some(0));
+    let json = std::fs::read_to_string(&proof_file).unwrap().replacen("\"1.0\"", "\"9.9\"", 1);
+    std::fs::write(&proof_file, json).unwrap();
+    assert_eq!(run(&["verify", proof_path, "--quiet"]).status.code(), Some(4));
+
+    std::fs::remove_file(proof_file).ok();
+}
+
+#[test]
+fn test_prove_batch_output_dir() {
+    let dir = temp_path("batch_dir");
+    let _ = std::fs::remove_dir_all(&dir);
+    let dir_path = dir.to_str().unwrap();
+    let output = run(&["prove-batch", "5", "8", "--output-dir", dir_path, "--security", "80"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(dir.join("fibonacci_5_80.json").is_file());
+    assert!(dir.join("fibonacci_8_80.json").is_file());
+
+    let output = run(&["prove-batch", "5", "--output-dir", dir_path, "--name-template", "{computation}.json"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("must contain {n}"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_bench_hash_every_backend_verifies() {
+    let output = run(&["bench-hash", "10", "--iterations", "2"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for name in ["sha256", "sha256-be", "blake3", "keccak256"] {
+        let row = stdout.lines().find(|line| line.starts_with(&format!("{} ", name))).unwrap();
+        assert!(row.ends_with('✓'), "{}", row);
+    }
+}
+
+#[test]
+fn test_replay_detects_tampered_step() {
+    let proof_file = temp_path("replay_proof.json");
+    let bundle_file = temp_path("replay_bundle.json");
+    let bundle_path = bundle_file.to_str().unwrap();
+    let output = run(&["prove", "10", "--output", proof_file.to_str().unwrap(), "--bundle", bundle_path]);
+    assert!(output.status.success());
+
+    let output = run(&["replay", bundle_path]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stdout));
+
+    let mut bundle: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&bundle_file).unwrap()).unwrap();
+    bundle["trace"]["steps"][4]["output"] = serde_json::json!(1000);
+    std::fs::write(&bundle_file, bundle.to_string()).unwrap();
+    let output = run(&["replay", bundle_path]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("diverges from the stored trace at step 4"));
+
+    // Bundles that cannot be loaded exit like unreadable proofs
+    std::fs::write(&bundle_file, "  \n").unwrap();
+    assert_eq!(run(&["replay", bundle_path]).status.code(), Some(3));
+    assert_eq!(run(&["trace-diff", bundle_path, bundle_path]).status.code(), Some(3));
+    std::fs::write(&bundle_file, "{\"computation\": ").unwrap();
+    assert_eq!(run(&["replay", bundle_path]).status.code(), Some(3));
+    std::fs::remove_file(&bundle_file).ok();
+    assert_eq!(run(&["replay", bundle_path]).status.code(), Some(2));
+
+    std::fs::remove_file(proof_file).ok();
+}
+
+#[test]
+fn test_verify_batch_file_junit_report() {
+    let archive_file = temp_path("junit_archive.json");
+    let archive_path = archive_file.to_str().unwrap();
+    let junit_file = temp_path("junit.xml");
+    let junit_path = junit_file.to_str().unwrap();
+    assert!(run(&["prove-batch", "5", "8", "13", "--output", archive_path]).status.success());
+
+    let mut archive: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&archive_file).unwrap()).unwrap();
+    archive["proofs"][1]["result"] = serde_json::json!(22);
+    std::fs::write(&archive_file, archive.to_string()).unwrap();
+
+    let output = run(&["verify-batch-file", archive_path, "--junit", junit_path]);
+    assert_eq!(output.status.code(), Some(1));
+    let xml = std::fs::read_to_string(&junit_file).unwrap();
+    assert!(xml.starts_with("<?xml"));
+    assert!(xml.contains(r#"tests="3" failures="1""#));
+    assert_eq!(xml.matches("<testcase ").count(), 3);
+    assert_eq!(xml.matches("<failure ").count(), 1);
+    assert!(xml.contains(r#"<testcase name="[1] fibonacci(n=8) = 22""#));
+    assert!(xml.trim_end().ends_with("</testsuite>"));
+
+    std::fs::remove_file(archive_file).ok();
+    std::fs::remove_file(junit_file).ok();
+}
+
+#[test]
+fn test_prove_compact_output() {
+    let pretty_file = temp_path("pretty.json");
+    let compact_file = temp_path("compact.json");
+    let pretty_path = pretty_file.to_str().unwrap();
+    let compact_path = compact_file.to_str().unwrap();
+    assert!(run(&["prove", "10", "--output", pretty_path]).status.success());
+    assert!(run(&["prove", "10", "--output", compact_path, "--compact"]).status.success());
+
+    let pretty = std::fs::read_to_string(&pretty_file).unwrap();
+    let compact = std::fs::read_to_string(&compact_file).unwrap();
+    assert!(compact.len() < pretty.len());
+    assert_eq!(compact.lines().count(), 1);
+    assert!(run(&["verify", compact_path]).status.success());
+
+    // Archives follow the same switch
+    assert!(run(&["prove-batch", "5", "8", "--output", compact_path, "--compact"]).status.success());
+    let compact = std::fs::read_to_string(&compact_file).unwrap();
+    assert_eq!(compact.lines().count(), 1);
+    assert!(run(&["verify-batch-file", compact_path]).status.success());
+
+    std::fs::remove_file(pretty_file).ok();
+    std::fs::remove_file(compact_file).ok();
+}
+
+#[cfg(not(feature = "network"))]
+#[test]
+fn test_verify_url_without_network_feature() {
+    let output = run(&["verify", "https://example.com/proof.json"]);
+    assert_eq!(output.status.code(), Some(4));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("built without the `network` feature"));
+}
+
+#[test]
+fn test_reprove_warns_on_mismatched_embedded_result() {
+    let proof_file = temp_path("reprove_original.json");
+    let bundle_file = temp_path("reprove_bundle.json");
+    let output_file = temp_path("reprove_output.json");
+    let bundle_path = bundle_file.to_str().unwrap();
+    let output = run(&["prove", "10", "--output", proof_file.to_str().unwrap(), "--bundle", bundle_path]);
+    assert!(output.status.success());
+
+    let mut bundle: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&bundle_file).unwrap()).unwrap();
+    bundle["proof"]["result"] = serde_json::json!(54);
+    std::fs::write(&bundle_file, bundle.to_string()).unwrap();
+
+    let output = run(&["reprove", bundle_path, "--output", output_file.to_str().unwrap()]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("Warning: embedded proof claims result 54 but the trace computes 55"));
+    let output = run(&["verify", output_file.to_str().unwrap()]);
+    assert!(output.status.success());
+
+    for file in [proof_file, bundle_file, output_file] {
+        std::fs::remove_file(file).ok();
+    }
+}
+
+#[test]
+fn test_prove_hash_computation_then_replay() {
+    let proof_file = temp_path("hash_proof.json");
+    let bundle_file = temp_path("hash_bundle.json");
+    let proof_path = proof_file.to_str().unwrap();
+    let bundle_path = bundle_file.to_str().unwrap();
+    let output = run(&["prove", "--computation", "hash-computation", "42", "--output", proof_path, "--bundle", bundle_path]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let proof: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&proof_file).unwrap()).unwrap();
+    assert_eq!(proof["result"]["digest"].as_str().unwrap().len(), 64);
+    assert!(run(&["verify", proof_path]).status.success());
+    let output = run(&["replay", bundle_path]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stdout));
+
+    std::fs::remove_file(&proof_file).ok();
+    std::fs::remove_file(&bundle_file).ok();
+}
+
+#[cfg(unix)]
+#[test]
+fn test_non_utf8_proof_path() {
+    use std::os::unix::ffi::OsStrExt;
+
+    let mut name = temp_path("proof_").into_os_string();
+    name.push(std::ffi::OsStr::from_bytes(b"\xff.json"));
+    let status = |args: &[&std::ffi::OsStr]| {
+        Command::new(env!("CARGO_BIN_EXE_stark-prover")).args(args).output().unwrap().status
+    };
+    assert!(status(&["prove".as_ref(), "10".as_ref(), "--output".as_ref(), &name]).success());
+    assert!(status(&["verify".as_ref(), &name]).success());
+    std::fs::remove_file(&name).ok();
+}
+
+#[cfg(unix)]
+#[test]
+fn test_verify_refuses_unbounded_device() {
+    let output = run(&["verify", "/dev/zero"]);
+    assert_eq!(output.status.code(), Some(3));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("proof too large"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_interrupted_prove_batch_saves_completed_proofs() {
+    use std::io::BufRead;
+
+    let archive_file = temp_path("interrupted_archive.json");
+    let ns = vec!["90"; 5000];
+    let mut child = Command::new(env!("CARGO_BIN_EXE_stark-prover"))
+        .arg("prove-batch")
+        .args(&ns)
+        .args(["--output".as_ref(), archive_file.as_os_str()])
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .unwrap();
+
+    // The first proof is printed after the handler is installed
+    let mut stdout = std::io::BufReader::new(child.stdout.take().unwrap());
+    let mut line = String::new();
+    stdout.read_line(&mut line).unwrap();
+    assert!(line.starts_with("✓ fibonacci(90)"), "{}", line);
+    assert!(Command::new("kill").args(["-INT", &child.id().to_string()]).status().unwrap().success());
+    let mut rest = String::new();
+    std::io::Read::read_to_string(&mut stdout, &mut rest).unwrap();
+    assert_eq!(child.wait().unwrap().code(), Some(130));
+    assert!(rest.contains("of 5000 proofs completed"), "{}", rest);
+
+    let archive: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&archive_file).unwrap()).unwrap();
+    let saved = archive["proofs"].as_array().unwrap().len();
+    assert!(saved > 0 && saved < ns.len(), "{} proofs saved", saved);
+    std::fs::remove_file(archive_file).ok();
+}
@@ -0,0 +1,28 @@
+// tests/proof_size.rs - Guard against accidental proof format bloat
+use stark_prover_verifier::computation::fibonacci_with_trace;
+use stark_prover_verifier::STARKProver;
+
+/// Compact-JSON size of a default 128-bit proof of fibonacci(20), in bytes.
+///
+/// Update this (and explain why in the commit) when the proof format changes
+/// on purpose.
+const EXPECTED_FIB20_PROOF_SIZE: usize = 1069;
+
+/// Allowed deviation, covering e.g. differing digit counts in the timestamp
+const TOLERANCE: usize = 64;
+
+#[test]
+fn test_fibonacci_20_proof_size() {
+    let (result, trace) = fibonacci_with_trace(20).unwrap();
+    let proof = STARKProver::new(128).prove("fibonacci", result, &trace).unwrap();
+    let size = proof.serialized_size();
+
+    assert_eq!(size, serde_json::to_vec(&proof).unwrap().len());
+    assert!(
+        size.abs_diff(EXPECTED_FIB20_PROOF_SIZE) <= TOLERANCE,
+        "fibonacci(20) proof is {} bytes, expected {} ± {}",
+        size,
+        EXPECTED_FIB20_PROOF_SIZE,
+        TOLERANCE
+    );
+}
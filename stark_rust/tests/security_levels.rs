@@ -0,0 +1,29 @@
+// tests/security_levels.rs - Proving and verifying at different security levels
+use stark_prover_verifier::computation::fibonacci_with_trace;
+use stark_prover_verifier::crypto::challenge_hex_len;
+use stark_prover_verifier::{STARKProver, STARKVerifier};
+
+#[test]
+fn test_prove_and_verify_at_each_level() {
+    let (result, trace) = fibonacci_with_trace(15).unwrap();
+
+    for security in [80, 128, 256] {
+        let proof = STARKProver::new(security)
+            .prove("fibonacci", result, &trace)
+            .unwrap();
+        assert_eq!(proof.security_bits, security);
+        assert_eq!(proof.challenge.len(), challenge_hex_len(security));
+
+        let verification = STARKVerifier::new(security).verify(&proof);
+        assert!(verification.valid, "{}-bit proof failed: {:?}", security, verification.checks_failed);
+    }
+}
+
+#[test]
+fn test_low_security_proof_rejected_by_stricter_verifier() {
+    let (result, trace) = fibonacci_with_trace(15).unwrap();
+    let proof = STARKProver::new(80).prove("fibonacci", result, &trace).unwrap();
+
+    assert!(STARKVerifier::new(80).verify(&proof).valid);
+    assert!(!STARKVerifier::new(128).verify(&proof).valid);
+}